@@ -30,7 +30,30 @@ resource "aws_iam_policy" "eks_workers_ebs_csi" {
 POLICY
 }
 
+# the EBS CSI driver add-on has no service account role, it manages the volumes and snapshots with the workers role
 resource "aws_iam_role_policy_attachment" "workers_csi" {
   policy_arn = aws_iam_policy.eks_workers_ebs_csi.arn
   role       = aws_iam_role.eks_workers.name
 }
+
+# EBS CSI driver provisioning the Qovery storage classes volumes, and snapshot controller serving the snapshot api
+# required to snapshot and restore them
+resource "aws_eks_addon" "aws_ebs_csi_driver" {
+  cluster_name      = aws_eks_cluster.eks_cluster.name
+  addon_name        = "aws-ebs-csi-driver"
+  resolve_conflicts = "OVERWRITE"
+
+  tags = local.tags_eks
+
+  depends_on = [aws_iam_role_policy_attachment.workers_csi]
+}
+
+resource "aws_eks_addon" "snapshot_controller" {
+  cluster_name      = aws_eks_cluster.eks_cluster.name
+  addon_name        = "snapshot-controller"
+  resolve_conflicts = "OVERWRITE"
+
+  tags = local.tags_eks
+
+  depends_on = [aws_eks_addon.aws_ebs_csi_driver]
+}
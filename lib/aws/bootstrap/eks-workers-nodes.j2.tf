@@ -9,6 +9,10 @@ resource "aws_eks_node_group" "eks_cluster_workers_{{ loop.index }}" {
   ami_type         = "AL2_x86_64"
   disk_size = "{{ eks_worker_node.disk_size_in_gib }}"
 
+  labels = {
+    "qovery.com/node-group" = "{{ eks_worker_node.name }}"
+  }
+
   tags = merge(
   local.tags_eks,
   {
@@ -14,6 +14,9 @@ resource "digitalocean_kubernetes_cluster" "kubernetes_cluster" {
     tags = concat(local.tags_ks_list, ["QoveryNodeGroupName:{{ doks_worker_nodes[0].name }}", "QoveryNodeGroupId:${var.kubernetes_cluster_id}-0"])
     name = var.kubernetes_cluster_id
     size = "{{ doks_worker_nodes[0].instance_type }}"
+    labels = {
+      "qovery.com/node-group" = "{{ doks_worker_nodes[0].name }}"
+    }
     # use Digital Ocean built-in cluster autoscaler
     auto_scale = true
     min_nodes  = "{{ doks_worker_nodes[0].min_nodes }}"
@@ -8,7 +8,10 @@ resource "digitalocean_kubernetes_node_pool" "app_node_pool_{{ loop.index }}" {
 
   name = "qovery-{{kubernetes_cluster_id}}-{{ loop.index }}"
   size = "{{ doks_worker_node.instance_type }}"
-  tags =  concat(local.tags_doks_list, ["QoveryNodeGroupId:${var.kubernetes_cluster_id}-{{ loop.index }}", "QoveryNodeGroupName:{{ eks_worker_node.name }}"])
+  tags =  concat(local.tags_doks_list, ["QoveryNodeGroupId:${var.kubernetes_cluster_id}-{{ loop.index }}", "QoveryNodeGroupName:{{ doks_worker_node.name }}"])
+  labels = {
+    "qovery.com/node-group" = "{{ doks_worker_node.name }}"
+  }
   auto_scale = true
   min_nodes  = {{ doks_worker_node.min_nodes }}
   max_nodes  = {{ doks_worker_node.max_nodes }}
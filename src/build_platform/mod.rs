@@ -68,6 +68,7 @@ pub trait BuildPlatform: ToTransmitter + Listen {
     }
 }
 
+#[derive(Clone)]
 pub struct Build {
     pub git_repository: GitRepository,
     pub image: Image,
@@ -105,6 +106,7 @@ pub struct SshKey {
     pub public_key: Option<String>,
 }
 
+#[derive(Clone)]
 pub struct GitRepository {
     pub url: Url,
     pub credentials: Option<Credentials>,
@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use tera::Context as TeraContext;
+
+use crate::cloud_provider::helm::ChartSetValue;
+use crate::io_models::Context;
+
+pub const LETS_ENCRYPT_PRODUCTION_URL: &str = "https://acme-v02.api.letsencrypt.org/directory";
+pub const LETS_ENCRYPT_STAGING_URL: &str = "https://acme-staging-v02.api.letsencrypt.org/directory";
+pub const ZEROSSL_URL: &str = "https://acme.zerossl.com/v2/DV90";
+
+/// Name of the ClusterIssuer deployed by the cert-manager-configs chart.
+pub const CLUSTER_ISSUER_NAME: &str = "letsencrypt-qovery";
+
+/// Challenge used to issue custom domains certificates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcmeSolver {
+    /// Each router gets its own Issuer answering challenges through the Ingress controller.
+    #[default]
+    Http01,
+    /// Challenges are answered by the cluster issuer through the cluster `DnsProvider`. Custom domains
+    /// have to delegate their `_acme-challenge` record with a CNAME, wildcard domains are supported.
+    Dns01,
+}
+
+impl AcmeSolver {
+    pub fn to_cert_manager_format(&self) -> &str {
+        match self {
+            AcmeSolver::Http01 => "http01",
+            AcmeSolver::Dns01 => "dns01",
+        }
+    }
+}
+
+/// External account binding, required by some ACME servers like ZeroSSL.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcmeExternalAccountBinding {
+    pub key_id: String,
+    /// base64 url encoded HMAC key
+    pub hmac_key: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcmeSettings {
+    pub directory_url: String,
+    pub email: String,
+    #[serde(default)]
+    pub external_account_binding: Option<AcmeExternalAccountBinding>,
+    #[serde(default)]
+    pub solver: AcmeSolver,
+    /// Only meant for test ACME servers with self signed certificates, like Pebble.
+    #[serde(default)]
+    pub skip_tls_verify: bool,
+}
+
+impl AcmeSettings {
+    /// Let's Encrypt settings used when the cluster doesn't define any, staging is used by test clusters.
+    pub fn lets_encrypt(context: &Context, email: &str) -> Self {
+        let directory_url = match context.is_test_cluster() {
+            true => LETS_ENCRYPT_STAGING_URL,
+            false => LETS_ENCRYPT_PRODUCTION_URL,
+        };
+
+        AcmeSettings {
+            directory_url: directory_url.to_string(),
+            email: email.to_string(),
+            external_account_binding: None,
+            solver: AcmeSolver::default(),
+            skip_tls_verify: false,
+        }
+    }
+
+    /// Cluster settings if any, Let's Encrypt otherwise.
+    pub fn from_options(settings: &Option<AcmeSettings>, context: &Context, default_email: &str) -> Self {
+        match settings {
+            Some(settings) => settings.clone(),
+            None => AcmeSettings::lets_encrypt(context, default_email),
+        }
+    }
+
+    pub fn cert_manager_configs_values(&self) -> Vec<ChartSetValue> {
+        let mut values = vec![
+            ChartSetValue {
+                key: "acme.clusterIssuerName".to_string(),
+                value: CLUSTER_ISSUER_NAME.to_string(),
+            },
+            ChartSetValue {
+                key: "acme.server".to_string(),
+                value: self.directory_url.clone(),
+            },
+            ChartSetValue {
+                key: "acme.email".to_string(),
+                value: self.email.clone(),
+            },
+            ChartSetValue {
+                key: "acme.solver".to_string(),
+                value: self.solver.to_cert_manager_format().to_string(),
+            },
+            ChartSetValue {
+                key: "acme.skipTlsVerify".to_string(),
+                value: self.skip_tls_verify.to_string(),
+            },
+        ];
+
+        if let Some(eab) = &self.external_account_binding {
+            values.push(ChartSetValue {
+                key: "acme.externalAccountBinding.keyId".to_string(),
+                value: eab.key_id.clone(),
+            });
+            values.push(ChartSetValue {
+                key: "acme.externalAccountBinding.hmacKey".to_string(),
+                value: eab.hmac_key.clone(),
+            });
+        }
+
+        values
+    }
+
+    pub fn insert_into_tera_context(&self, context: &mut TeraContext) {
+        context.insert("spec_acme_server", &self.directory_url);
+        context.insert("spec_acme_email", &self.email);
+        context.insert("spec_acme_skip_tls_verify", &self.skip_tls_verify);
+        context.insert(
+            "spec_acme_eab_key_id",
+            &self.external_account_binding.as_ref().map(|eab| eab.key_id.as_str()),
+        );
+        context.insert(
+            "spec_acme_eab_hmac_key",
+            &self.external_account_binding.as_ref().map(|eab| eab.hmac_key.as_str()),
+        );
+        context.insert("acme_solver", self.solver.to_cert_manager_format());
+        context.insert("metadata_annotations_cert_manager_cluster_issuer", CLUSTER_ISSUER_NAME);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AcmeExternalAccountBinding, AcmeSettings, AcmeSolver, ZEROSSL_URL};
+
+    #[test]
+    fn test_acme_settings_deserialization() {
+        // setup:
+        let json = r#"{"directory_url": "https://acme.zerossl.com/v2/DV90", "email": "tls@example.com",
+            "external_account_binding": {"key_id": "kid", "hmac_key": "aG1hYw"}, "solver": "dns01"}"#;
+
+        // execute:
+        let settings: AcmeSettings = serde_json::from_str(json).unwrap();
+
+        // verify:
+        assert_eq!(ZEROSSL_URL, settings.directory_url);
+        assert_eq!(AcmeSolver::Dns01, settings.solver);
+        assert!(!settings.skip_tls_verify);
+        assert_eq!(
+            Some(AcmeExternalAccountBinding {
+                key_id: "kid".to_string(),
+                hmac_key: "aG1hYw".to_string(),
+            }),
+            settings.external_account_binding
+        );
+    }
+
+    #[test]
+    fn test_cert_manager_configs_values() {
+        // setup:
+        let settings = AcmeSettings {
+            directory_url: "https://pebble.local:14000/dir".to_string(),
+            email: "tls@example.com".to_string(),
+            external_account_binding: None,
+            solver: AcmeSolver::Http01,
+            skip_tls_verify: true,
+        };
+
+        // execute:
+        let values = settings.cert_manager_configs_values();
+
+        // verify:
+        let value = |key: &str| values.iter().find(|v| v.key == key).map(|v| v.value.as_str());
+        assert_eq!(Some("https://pebble.local:14000/dir"), value("acme.server"));
+        assert_eq!(Some("tls@example.com"), value("acme.email"));
+        assert_eq!(Some("http01"), value("acme.solver"));
+        assert_eq!(Some("true"), value("acme.skipTlsVerify"));
+        assert_eq!(None, value("acme.externalAccountBinding.keyId"));
+    }
+}
@@ -1,9 +1,15 @@
 use crate::cloud_provider;
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::kubernetes;
 use crate::cloud_provider::aws::kubernetes::Options;
 use crate::cloud_provider::aws::regions::{AwsRegion, AwsZones};
 use crate::cloud_provider::environment::Environment;
-use crate::cloud_provider::kubernetes::{send_progress_on_long_task, Kind, Kubernetes, KubernetesUpgradeStatus};
+use crate::cloud_provider::kubernetes::{
+    custom_config_file_store, send_progress_on_long_task, Kind, Kubernetes, KubernetesApiCapabilities,
+    KubernetesUpgradeStatus,
+};
+use crate::cloud_provider::models::NodeGroups;
+use crate::cloud_provider::pod_security::PodSecurityPolicy;
 use crate::cloud_provider::utilities::print_action;
 use crate::cloud_provider::CloudProvider;
 use crate::dns_provider::DnsProvider;
@@ -12,6 +18,7 @@ use crate::events::{EnvironmentStep, InfrastructureStep, Stage};
 use crate::io_models::{Action, Context, Listen, Listener, Listeners};
 use crate::logger::Logger;
 use crate::object_storage::s3::S3;
+use crate::object_storage::s3_compatible::S3Compatible;
 use crate::object_storage::ObjectStorage;
 use function_name::named;
 use std::borrow::Borrow;
@@ -29,6 +36,7 @@ pub struct EC2 {
     cloud_provider: Arc<Box<dyn CloudProvider>>,
     dns_provider: Arc<Box<dyn DnsProvider>>,
     s3: S3,
+    config_file_store: Option<S3Compatible>,
     template_directory: String,
     options: Options,
     listeners: Listeners,
@@ -54,6 +62,7 @@ impl EC2 {
 
         let aws_zones = kubernetes::aws_zones(zones, &region, &event_details)?;
         let s3 = kubernetes::s3(&context, &region, &**cloud_provider);
+        let config_file_store = custom_config_file_store(&context, options.config_file_store.as_ref());
 
         // copy listeners from CloudProvider
         let listeners = cloud_provider.listeners().clone();
@@ -68,6 +77,7 @@ impl EC2 {
             cloud_provider,
             dns_provider,
             s3,
+            config_file_store,
             options,
             template_directory,
             logger,
@@ -117,6 +127,14 @@ impl Kubernetes for EC2 {
         Some(self.zones.clone())
     }
 
+    fn node_groups(&self) -> &[NodeGroups] {
+        &[]
+    }
+
+    fn volume_snapshot_class(&self, _api_capabilities: &KubernetesApiCapabilities) -> Option<&str> {
+        None
+    }
+
     fn cloud_provider(&self) -> &dyn CloudProvider {
         (*self.cloud_provider).borrow()
     }
@@ -125,12 +143,23 @@ impl Kubernetes for EC2 {
         (*self.dns_provider).borrow()
     }
 
+    fn acme_settings(&self) -> AcmeSettings {
+        AcmeSettings::from_options(&self.options.acme, &self.context, &self.options.tls_email_report)
+    }
+
+    fn pod_security_policy(&self) -> PodSecurityPolicy {
+        self.options.pod_security_policy.clone()
+    }
+
     fn logger(&self) -> &dyn Logger {
         self.logger.borrow()
     }
 
     fn config_file_store(&self) -> &dyn ObjectStorage {
-        &self.s3
+        match &self.config_file_store {
+            Some(config_file_store) => config_file_store,
+            None => &self.s3,
+        }
     }
 
     fn is_valid(&self) -> Result<(), EngineError> {
@@ -152,6 +181,7 @@ impl Kubernetes for EC2 {
             kubernetes::create(
                 self,
                 self.long_id,
+                &self.s3,
                 self.template_directory.as_str(),
                 &self.zones,
                 &[],
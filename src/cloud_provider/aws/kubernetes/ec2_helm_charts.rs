@@ -1,9 +1,12 @@
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::kubernetes::{Options, VpcQoveryNetworkMode};
 use crate::cloud_provider::helm::{
-    get_chart_for_cluster_agent, get_chart_for_shell_agent, ChartInfo, ChartSetValue, ClusterAgentContext, CommonChart,
-    CoreDNSConfigChart, HelmChart, HelmChartNamespaces, ShellAgentContext,
+    get_cert_manager_dns_provider_values, get_chart_for_cluster_agent, get_chart_for_shell_agent, ChartInfo,
+    ChartSetValue, ClusterAgentContext, CommonChart, CoreDNSConfigChart, HelmChart, HelmChartNamespaces,
+    NginxIngressChart, ShellAgentContext,
 };
 use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, QoveryAgent, QoveryAppName};
+use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
@@ -47,10 +50,8 @@ pub struct Ec2ChartsConfigPrerequisites {
     pub managed_dns_helm_format: String,
     pub managed_dns_resolvers_terraform_format: String,
     pub external_dns_provider: String,
-    pub dns_email_report: String,
-    pub acme_url: String,
-    pub cloudflare_email: String,
-    pub cloudflare_api_token: String,
+    pub acme_settings: AcmeSettings,
+    pub dns_provider_configuration: DnsProviderConfiguration,
     pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: Options,
@@ -254,14 +255,6 @@ pub fn ec2_aws_helm_charts(
                     key: "externalDnsProvider".to_string(),
                     value: chart_config_prerequisites.external_dns_provider.clone(),
                 },
-                ChartSetValue {
-                    key: "acme.letsEncrypt.emailReport".to_string(),
-                    value: chart_config_prerequisites.dns_email_report.clone(),
-                },
-                ChartSetValue {
-                    key: "acme.letsEncrypt.acmeUrl".to_string(),
-                    value: chart_config_prerequisites.acme_url.clone(),
-                },
                 ChartSetValue {
                     key: "managedDns".to_string(),
                     value: chart_config_prerequisites.managed_dns_helm_format.clone(),
@@ -270,18 +263,18 @@ pub fn ec2_aws_helm_charts(
             ..Default::default()
         },
     };
-    if chart_config_prerequisites.external_dns_provider == "cloudflare" {
-        cert_manager_config.chart_info.values.push(ChartSetValue {
-            key: "provider.cloudflare.apiToken".to_string(),
-            value: chart_config_prerequisites.cloudflare_api_token.clone(),
-        });
-        cert_manager_config.chart_info.values.push(ChartSetValue {
-            key: "provider.cloudflare.email".to_string(),
-            value: chart_config_prerequisites.cloudflare_email.clone(),
-        })
-    }
+    cert_manager_config
+        .chart_info
+        .values
+        .extend(get_cert_manager_dns_provider_values(
+            &chart_config_prerequisites.dns_provider_configuration,
+        ));
+    cert_manager_config
+        .chart_info
+        .values
+        .extend(chart_config_prerequisites.acme_settings.cert_manager_configs_values());
 
-    let nginx_ingress = CommonChart {
+    let nginx_ingress = NginxIngressChart {
         chart_info: ChartInfo {
             name: "nginx-ingress".to_string(),
             path: chart_path("common/charts/ingress-nginx"),
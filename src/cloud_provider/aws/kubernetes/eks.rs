@@ -1,13 +1,16 @@
 use crate::cloud_provider;
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::kubernetes;
 use crate::cloud_provider::aws::kubernetes::node::AwsInstancesType;
 use crate::cloud_provider::aws::kubernetes::Options;
 use crate::cloud_provider::aws::regions::{AwsRegion, AwsZones};
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::kubernetes::{
-    send_progress_on_long_task, Kind, Kubernetes, KubernetesNodesType, KubernetesUpgradeStatus,
+    custom_config_file_store, send_progress_on_long_task, Kind, Kubernetes, KubernetesApiCapabilities,
+    KubernetesNodesType, KubernetesUpgradeStatus, VOLUME_SNAPSHOT_CLASS,
 };
 use crate::cloud_provider::models::NodeGroups;
+use crate::cloud_provider::pod_security::PodSecurityPolicy;
 use crate::cloud_provider::utilities::print_action;
 use crate::cloud_provider::CloudProvider;
 use crate::cmd::kubectl::{kubectl_exec_scale_replicas, ScalingKind};
@@ -19,6 +22,7 @@ use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, In
 use crate::io_models::{Action, Context, Listen, Listener, Listeners, ListenersHelper};
 use crate::logger::Logger;
 use crate::object_storage::s3::S3;
+use crate::object_storage::s3_compatible::S3Compatible;
 use crate::object_storage::ObjectStorage;
 use function_name::named;
 use std::borrow::Borrow;
@@ -37,6 +41,7 @@ pub struct EKS {
     cloud_provider: Arc<Box<dyn CloudProvider>>,
     dns_provider: Arc<Box<dyn DnsProvider>>,
     s3: S3,
+    config_file_store: Option<S3Compatible>,
     nodes_groups: Vec<NodeGroups>,
     template_directory: String,
     options: Options,
@@ -76,6 +81,7 @@ impl EKS {
         }
 
         let s3 = kubernetes::s3(&context, &region, &**cloud_provider);
+        let config_file_store = custom_config_file_store(&context, options.config_file_store.as_ref());
 
         // copy listeners from CloudProvider
         let listeners = cloud_provider.listeners().clone();
@@ -90,6 +96,7 @@ impl EKS {
             cloud_provider,
             dns_provider,
             s3,
+            config_file_store,
             options,
             nodes_groups,
             template_directory,
@@ -173,6 +180,19 @@ impl Kubernetes for EKS {
         Some(self.zones.clone())
     }
 
+    fn node_groups(&self) -> &[NodeGroups] {
+        &self.nodes_groups
+    }
+
+    fn volume_snapshot_class(&self, api_capabilities: &KubernetesApiCapabilities) -> Option<&str> {
+        // the snapshot class is only installed when the cluster serves the v1 snapshot api
+        if api_capabilities.volume_snapshot_api {
+            Some(VOLUME_SNAPSHOT_CLASS)
+        } else {
+            None
+        }
+    }
+
     fn cloud_provider(&self) -> &dyn CloudProvider {
         (*self.cloud_provider).borrow()
     }
@@ -181,12 +201,23 @@ impl Kubernetes for EKS {
         (*self.dns_provider).borrow()
     }
 
+    fn acme_settings(&self) -> AcmeSettings {
+        AcmeSettings::from_options(&self.options.acme, &self.context, &self.options.tls_email_report)
+    }
+
+    fn pod_security_policy(&self) -> PodSecurityPolicy {
+        self.options.pod_security_policy.clone()
+    }
+
     fn logger(&self) -> &dyn Logger {
         self.logger.borrow()
     }
 
     fn config_file_store(&self) -> &dyn ObjectStorage {
-        &self.s3
+        match &self.config_file_store {
+            Some(config_file_store) => config_file_store,
+            None => &self.s3,
+        }
     }
 
     fn is_valid(&self) -> Result<(), EngineError> {
@@ -208,6 +239,7 @@ impl Kubernetes for EKS {
             kubernetes::create(
                 self,
                 self.long_id,
+                &self.s3,
                 self.template_directory.as_str(),
                 &self.zones,
                 &self.nodes_groups,
@@ -1,11 +1,17 @@
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::kubernetes::{Options, VpcQoveryNetworkMode};
 use crate::cloud_provider::helm::{
-    get_chart_for_cluster_agent, get_chart_for_shell_agent, get_engine_helm_action_from_location, ChartInfo,
-    ChartPayload, ChartSetValue, ChartValuesGenerated, ClusterAgentContext, CommonChart, CoreDNSConfigChart, HelmChart,
-    HelmChartNamespaces, PrometheusOperatorConfigChart, ShellAgentContext,
+    get_cert_manager_dns_provider_values, get_chart_for_cluster_agent, get_chart_for_shell_agent,
+    get_engine_helm_action_from_location, ChartInfo, ChartPayload, ChartSetValue, ChartValuesGenerated,
+    ClusterAgentContext, CommonChart, CoreDNSConfigChart, HelmChart, HelmChartNamespaces, NginxIngressChart,
+    PrometheusOperatorConfigChart, ShellAgentContext,
 };
 use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, QoveryAgent, QoveryAppName, QoveryEngine};
-use crate::cmd::kubectl::{kubectl_delete_crash_looping_pods, kubectl_exec_get_daemonset, kubectl_exec_with_output};
+use crate::cmd::kubectl::{
+    kubectl_delete_crash_looping_pods, kubectl_exec_delete_storage_class, kubectl_exec_get_daemonset,
+    kubectl_exec_get_storage_class, kubectl_exec_with_output,
+};
+use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::{CommandError, ErrorMessageVerbosity};
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -47,10 +53,8 @@ pub struct EksChartsConfigPrerequisites {
     pub managed_dns_helm_format: String,
     pub managed_dns_resolvers_terraform_format: String,
     pub external_dns_provider: String,
-    pub dns_email_report: String,
-    pub acme_url: String,
-    pub cloudflare_email: String,
-    pub cloudflare_api_token: String,
+    pub acme_settings: AcmeSettings,
+    pub dns_provider_configuration: DnsProviderConfiguration,
     pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: Options,
@@ -93,7 +97,7 @@ pub fn eks_aws_helm_charts(
     let loki_kube_dns_prefix = format!("loki.{}.svc", loki_namespace);
 
     // Qovery storage class
-    let q_storage_class = CommonChart {
+    let q_storage_class = AwsStorageClassChart {
         chart_info: ChartInfo {
             name: "q-storageclass".to_string(),
             path: chart_path("/charts/q-storageclass"),
@@ -801,14 +805,6 @@ datasources:
                     key: "externalDnsProvider".to_string(),
                     value: chart_config_prerequisites.external_dns_provider.clone(),
                 },
-                ChartSetValue {
-                    key: "acme.letsEncrypt.emailReport".to_string(),
-                    value: chart_config_prerequisites.dns_email_report.clone(),
-                },
-                ChartSetValue {
-                    key: "acme.letsEncrypt.acmeUrl".to_string(),
-                    value: chart_config_prerequisites.acme_url.clone(),
-                },
                 ChartSetValue {
                     key: "managedDns".to_string(),
                     value: chart_config_prerequisites.managed_dns_helm_format.clone(),
@@ -817,18 +813,18 @@ datasources:
             ..Default::default()
         },
     };
-    if chart_config_prerequisites.external_dns_provider == "cloudflare" {
-        cert_manager_config.chart_info.values.push(ChartSetValue {
-            key: "provider.cloudflare.apiToken".to_string(),
-            value: chart_config_prerequisites.cloudflare_api_token.clone(),
-        });
-        cert_manager_config.chart_info.values.push(ChartSetValue {
-            key: "provider.cloudflare.email".to_string(),
-            value: chart_config_prerequisites.cloudflare_email.clone(),
-        })
-    }
-
-    let nginx_ingress = CommonChart {
+    cert_manager_config
+        .chart_info
+        .values
+        .extend(get_cert_manager_dns_provider_values(
+            &chart_config_prerequisites.dns_provider_configuration,
+        ));
+    cert_manager_config
+        .chart_info
+        .values
+        .extend(chart_config_prerequisites.acme_settings.cert_manager_configs_values());
+
+    let nginx_ingress = NginxIngressChart {
         chart_info: ChartInfo {
             name: "nginx-ingress".to_string(),
             path: chart_path("common/charts/ingress-nginx"),
@@ -1242,6 +1238,43 @@ datasources:
     Ok(vec![level_1, level_2, level_3, level_4, level_5, level_6, level_7])
 }
 
+// AWS storage classes
+
+/// Qovery storage classes, provisioned by the EBS CSI driver to get volumes which can be snapshotted.
+#[derive(Default)]
+pub struct AwsStorageClassChart {
+    pub chart_info: ChartInfo,
+}
+
+impl HelmChart for AwsStorageClassChart {
+    fn get_chart_info(&self) -> &ChartInfo {
+        &self.chart_info
+    }
+
+    fn pre_exec(
+        &self,
+        kubernetes_config: &Path,
+        envs: &[(String, String)],
+        payload: Option<ChartPayload>,
+    ) -> Result<Option<ChartPayload>, CommandError> {
+        let environment_variables: Vec<(&str, &str)> = envs.iter().map(|x| (x.0.as_str(), x.1.as_str())).collect();
+
+        // a storage class provisioner can't be updated, the ones still using the in-tree provisioner are deleted so
+        // the chart recreates them with the same name. Volumes they already provisioned are left untouched and keep
+        // working, but only volumes provisioned by the CSI driver can be snapshotted.
+        for storage_class in ["aws-ebs-gp2-0", "aws-ebs-io1-0", "aws-ebs-st1-0", "aws-ebs-sc1-0"] {
+            match kubectl_exec_get_storage_class(kubernetes_config, storage_class, environment_variables.clone()) {
+                Ok(sc) if sc.provisioner == "kubernetes.io/aws-ebs" => {
+                    kubectl_exec_delete_storage_class(kubernetes_config, storage_class, environment_variables.clone())?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(payload)
+    }
+}
+
 // AWS CNI
 
 #[derive(Default)]
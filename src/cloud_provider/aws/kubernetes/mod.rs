@@ -8,6 +8,7 @@ use retry::OperationResult;
 use serde::{Deserialize, Serialize};
 use tera::Context as TeraContext;
 
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::kubernetes::ec2_helm_charts::{
     ec2_aws_helm_charts, get_aws_ec2_qovery_terraform_config, Ec2ChartsConfigPrerequisites,
 };
@@ -16,9 +17,11 @@ use crate::cloud_provider::aws::kubernetes::roles::get_default_roles_to_create;
 use crate::cloud_provider::aws::regions::{AwsRegion, AwsZones};
 use crate::cloud_provider::helm::{deploy_charts_levels, ChartInfo};
 use crate::cloud_provider::kubernetes::{
-    is_kubernetes_upgrade_required, uninstall_cert_manager, Kind, Kubernetes, ProviderOptions,
+    check_ingress_controller_supports_kubernetes_version, is_kubernetes_upgrade_required,
+    put_kubeconfig_into_config_file_store, uninstall_cert_manager, Kind, Kubernetes, ProviderOptions,
 };
 use crate::cloud_provider::models::{NodeGroups, NodeGroupsFormat};
+use crate::cloud_provider::pod_security::PodSecurityPolicy;
 use crate::cloud_provider::qovery::EngineLocation;
 use crate::cloud_provider::utilities::{wait_until_port_is_open, TcpCheckSource};
 use crate::cloud_provider::CloudProvider;
@@ -27,12 +30,13 @@ use crate::cmd::helm::{to_engine_error, Helm};
 use crate::cmd::kubectl::{kubectl_exec_api_custom_metrics, kubectl_exec_get_all_namespaces, kubectl_exec_get_events};
 use crate::cmd::terraform::{terraform_exec, terraform_init_validate_plan_apply, terraform_init_validate_state_list};
 use crate::deletion_utilities::{get_firsts_namespaces_to_delete, get_qovery_managed_namespaces};
-use crate::dns_provider;
 use crate::dns_provider::DnsProvider;
 use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
 use crate::events::{EngineEvent, EventDetails, EventMessage, InfrastructureStep, Stage, Transmitter};
 use crate::io_models::{Context, Features, ListenersHelper, QoveryIdentifier, ToHelmString, ToTerraformString};
 use crate::object_storage::s3::S3;
+use crate::object_storage::s3_compatible::S3CompatibleSettings;
+use crate::object_storage::ObjectStorage;
 use crate::string::terraform_list_format;
 
 pub mod ec2;
@@ -110,6 +114,12 @@ pub struct Options {
     pub qovery_ssh_key: String,
     // Others
     pub tls_email_report: String,
+    #[serde(default)]
+    pub acme: Option<AcmeSettings>,
+    #[serde(default)]
+    pub pod_security_policy: PodSecurityPolicy,
+    #[serde(default)]
+    pub config_file_store: Option<S3CompatibleSettings>,
 }
 
 impl ProviderOptions for Options {}
@@ -186,14 +196,6 @@ fn check_odd_subnets(
     Ok((subnet_block.len() / 2) as usize)
 }
 
-fn lets_encrypt_url(context: &Context) -> String {
-    match context.is_test_cluster() {
-        true => "https://acme-staging-v02.api.letsencrypt.org/directory",
-        false => "https://acme-v02.api.letsencrypt.org/directory",
-    }
-    .to_string()
-}
-
 fn managed_dns_resolvers_terraform_format(dns_provider: &dyn DnsProvider) -> String {
     let managed_dns_resolvers = dns_provider
         .resolvers()
@@ -355,18 +357,15 @@ fn tera_context(
         &kubernetes.dns_provider().domain().wildcarded().to_string(),
     );
 
-    match kubernetes.dns_provider().kind() {
-        dns_provider::Kind::Cloudflare => {
-            context.insert("external_dns_provider", kubernetes.dns_provider().provider_name());
-            context.insert("cloudflare_api_token", kubernetes.dns_provider().token());
-            context.insert("cloudflare_email", kubernetes.dns_provider().account());
-        }
-    };
+    kubernetes
+        .dns_provider()
+        .configuration()
+        .insert_into_tera_context(&mut context);
 
     context.insert("dns_email_report", &options.tls_email_report);
 
     // TLS
-    context.insert("acme_server_url", &lets_encrypt_url(kubernetes.context()));
+    context.insert("acme_server_url", &kubernetes.acme_settings().directory_url);
 
     // Vault
     context.insert("vault_auth_method", "none");
@@ -501,6 +500,7 @@ fn tera_context(
 fn create(
     kubernetes: &dyn Kubernetes,
     kubernetes_long_id: uuid::Uuid,
+    s3: &S3,
     template_directory: &str,
     aws_zones: &[AwsZones],
     node_groups: &[NodeGroups],
@@ -525,6 +525,12 @@ fn create(
         &listeners_helper,
     );
 
+    check_ingress_controller_supports_kubernetes_version(
+        kubernetes.context().lib_root_dir(),
+        kubernetes.version(),
+        event_details.clone(),
+    )?;
+
     // upgrade cluster instead if required
     match kubernetes.get_kubeconfig_file() {
         Ok((path, _)) => match is_kubernetes_upgrade_required(
@@ -649,6 +655,18 @@ fn create(
         })?;
     };
 
+    // the cluster uploads its kubeconfig to S3 only
+    if options.config_file_store.is_some() {
+        let (kubeconfig_path, _) = s3
+            .get(
+                format!("qovery-kubeconfigs-{}", kubernetes.id()).as_str(),
+                kubernetes.get_kubeconfig_filename().as_str(),
+                false,
+            )
+            .map_err(|e| EngineError::new_cannot_retrieve_cluster_config_file(event_details.clone(), e.into()))?;
+        put_kubeconfig_into_config_file_store(kubernetes, kubeconfig_path.as_str(), event_details.clone())?;
+    }
+
     // kubernetes helm deployments on the cluster
     let kubeconfig_path = kubernetes.get_kubeconfig_file_path()?;
     let kubeconfig_path = Path::new(&kubeconfig_path);
@@ -689,10 +707,8 @@ fn create(
                     kubernetes.dns_provider(),
                 ),
                 external_dns_provider: kubernetes.dns_provider().provider_name().to_string(),
-                dns_email_report: options.tls_email_report.clone(),
-                acme_url: lets_encrypt_url(kubernetes.context()),
-                cloudflare_email: kubernetes.dns_provider().account().to_string(),
-                cloudflare_api_token: kubernetes.dns_provider().token().to_string(),
+                acme_settings: kubernetes.acme_settings(),
+                dns_provider_configuration: kubernetes.dns_provider().configuration(),
                 disable_pleco: kubernetes.context().disable_pleco(),
             };
             eks_aws_helm_charts(
@@ -727,10 +743,8 @@ fn create(
                     kubernetes.dns_provider(),
                 ),
                 external_dns_provider: kubernetes.dns_provider().provider_name().to_string(),
-                dns_email_report: options.tls_email_report.clone(),
-                acme_url: lets_encrypt_url(kubernetes.context()),
-                cloudflare_email: kubernetes.dns_provider().account().to_string(),
-                cloudflare_api_token: kubernetes.dns_provider().token().to_string(),
+                acme_settings: kubernetes.acme_settings(),
+                dns_provider_configuration: kubernetes.dns_provider().configuration(),
                 disable_pleco: kubernetes.context().disable_pleco(),
             };
             ec2_aws_helm_charts(
@@ -0,0 +1,302 @@
+use openssl::asn1::Asn1Time;
+use openssl::pkey::PKey;
+use openssl::x509::{X509VerifyResult, X509};
+use std::cmp::Ordering;
+
+use crate::io_models::CustomDomainCertificate;
+
+/// Certificates expiring in less than this number of days are reported at each deployment.
+pub const CERTIFICATE_EXPIRATION_WARNING_DAYS: i32 = 30;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateInfo {
+    pub subject_alt_names: Vec<String>,
+    pub days_before_expiration: i32,
+    /// Set when the certificate is not valid yet or is expired. It's not a configuration error,
+    /// the certificate becomes invalid over time and the environment must still be manageable.
+    pub validity_period_error: Option<String>,
+}
+
+impl CertificateInfo {
+    pub fn expires_soon(&self) -> bool {
+        self.days_before_expiration < CERTIFICATE_EXPIRATION_WARNING_DAYS
+    }
+}
+
+/// Checks `*.example.com` covers `app.example.com` but neither `example.com` nor `a.app.example.com`.
+fn dns_name_matches(dns_name: &str, domain: &str) -> bool {
+    let dns_name = dns_name.trim_end_matches('.').to_lowercase();
+    let domain = domain.trim_end_matches('.').to_lowercase();
+
+    match dns_name.strip_prefix("*.") {
+        Some(wildcard_parent) => {
+            matches!(domain.split_once('.'), Some((label, parent)) if !label.is_empty() && parent == wildcard_parent)
+        }
+        None => dns_name == domain,
+    }
+}
+
+/// Validates a user supplied certificate for `domain`: the chain must be ordered from the leaf
+/// certificate to its issuers, the key must match the leaf certificate and its SANs must cover the domain.
+/// The validity period is only reported in the returned `CertificateInfo`.
+pub fn validate_certificate(domain: &str, certificate: &CustomDomainCertificate) -> Result<CertificateInfo, String> {
+    let chain = X509::stack_from_pem(certificate.certificate_chain_pem.as_bytes())
+        .map_err(|e| format!("cannot parse PEM certificate chain: {}", e))?;
+    let leaf = chain
+        .first()
+        .ok_or_else(|| "certificate chain doesn't contain any certificate".to_string())?;
+
+    let private_key = PKey::private_key_from_pem(certificate.private_key_pem.as_bytes())
+        .map_err(|e| format!("cannot parse PEM private key: {}", e))?;
+    let leaf_public_key = leaf
+        .public_key()
+        .map_err(|e| format!("cannot read certificate public key: {}", e))?;
+    if !private_key.public_eq(&leaf_public_key) {
+        return Err("private key doesn't match the certificate".to_string());
+    }
+
+    for (certificate, issuer) in chain.iter().zip(chain.iter().skip(1)) {
+        let is_signed_by_issuer = issuer
+            .public_key()
+            .and_then(|issuer_public_key| certificate.verify(&issuer_public_key))
+            .unwrap_or(false);
+        if issuer.issued(certificate) != X509VerifyResult::OK || !is_signed_by_issuer {
+            return Err(format!(
+                "certificate `{:?}` is not issued by the next certificate of the chain `{:?}`",
+                certificate.subject_name(),
+                issuer.subject_name()
+            ));
+        }
+    }
+
+    let subject_alt_names = leaf
+        .subject_alt_names()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.dnsname().map(|dns_name| dns_name.to_string()))
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+    if !subject_alt_names
+        .iter()
+        .any(|dns_name| dns_name_matches(dns_name, domain))
+    {
+        return Err(format!(
+            "certificate subject alternative names {:?} don't cover `{}`",
+            subject_alt_names, domain
+        ));
+    }
+
+    let now = Asn1Time::days_from_now(0).map_err(|e| e.to_string())?;
+    let days_before_expiration = now.diff(leaf.not_after()).map_err(|e| e.to_string())?.days;
+    let validity_period_error = if leaf.not_before().compare(&now).map_err(|e| e.to_string())? == Ordering::Greater {
+        Some(format!("certificate is not valid before {}", leaf.not_before()))
+    } else if leaf.not_after().compare(&now).map_err(|e| e.to_string())? != Ordering::Greater {
+        Some(format!("certificate expired on {}", leaf.not_after()))
+    } else {
+        None
+    };
+
+    Ok(CertificateInfo {
+        subject_alt_names,
+        days_before_expiration,
+        validity_period_error,
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use openssl::bn::BigNum;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::Private;
+    use openssl::x509::extension::SubjectAlternativeName;
+    use openssl::x509::{X509Builder, X509NameBuilder};
+
+    fn new_key() -> PKey<Private> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap()
+    }
+
+    fn new_certificate(
+        common_name: &str,
+        dns_names: &[&str],
+        key: &PKey<Private>,
+        issuer: Option<(&X509, &PKey<Private>)>,
+        validity_days: u32,
+    ) -> X509 {
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", common_name).unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder
+            .set_serial_number(&BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder
+            .set_issuer_name(issuer.map(|(cert, _)| cert.subject_name()).unwrap_or(&name))
+            .unwrap();
+        builder.set_pubkey(key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(validity_days).unwrap())
+            .unwrap();
+        if !dns_names.is_empty() {
+            let mut san = SubjectAlternativeName::new();
+            for dns_name in dns_names {
+                san.dns(dns_name);
+            }
+            let san = san.build(&builder.x509v3_context(issuer.map(|(cert, _)| cert.as_ref()), None));
+            builder.append_extension(san.unwrap()).unwrap();
+        }
+        builder
+            .sign(issuer.map(|(_, key)| key).unwrap_or(key), MessageDigest::sha256())
+            .unwrap();
+        builder.build()
+    }
+
+    /// Returns a certificate chain (leaf and intermediate CA) and the leaf private key.
+    pub(crate) fn new_certificate_chain(dns_names: &[&str], validity_days: u32) -> CustomDomainCertificate {
+        let ca_key = new_key();
+        let ca = new_certificate("Qovery Test CA", &[], &ca_key, None, 365);
+        let leaf_key = new_key();
+        let leaf = new_certificate("leaf", dns_names, &leaf_key, Some((&ca, &ca_key)), validity_days);
+
+        CustomDomainCertificate {
+            certificate_chain_pem: format!(
+                "{}{}",
+                String::from_utf8(leaf.to_pem().unwrap()).unwrap(),
+                String::from_utf8(ca.to_pem().unwrap()).unwrap()
+            ),
+            private_key_pem: String::from_utf8(leaf_key.private_key_to_pem_pkcs8().unwrap()).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_dns_name_matches() {
+        assert!(dns_name_matches("app.example.com", "app.example.com"));
+        assert!(dns_name_matches("APP.example.com.", "app.example.com"));
+        assert!(dns_name_matches("*.example.com", "app.example.com"));
+        assert!(!dns_name_matches("*.example.com", "example.com"));
+        assert!(!dns_name_matches("*.example.com", "a.app.example.com"));
+        assert!(!dns_name_matches("app.example.com", "www.example.com"));
+    }
+
+    #[test]
+    fn test_validate_certificate() {
+        // setup:
+        let valid_chain = new_certificate_chain(&["*.example.com"], 90);
+        let other_chain = new_certificate_chain(&["*.example.com"], 90);
+
+        struct TestCase<'a> {
+            domain: &'a str,
+            certificate: CustomDomainCertificate,
+            expected_valid: bool,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                domain: "app.example.com",
+                certificate: valid_chain.clone(),
+                expected_valid: true,
+                description: "wildcard certificate with its chain",
+            },
+            TestCase {
+                domain: "app.other.com",
+                certificate: valid_chain.clone(),
+                expected_valid: false,
+                description: "SANs not covering the domain",
+            },
+            TestCase {
+                domain: "app.example.com",
+                certificate: CustomDomainCertificate {
+                    private_key_pem: other_chain.private_key_pem.clone(),
+                    ..valid_chain.clone()
+                },
+                expected_valid: false,
+                description: "key of another certificate",
+            },
+            TestCase {
+                domain: "app.example.com",
+                certificate: CustomDomainCertificate {
+                    certificate_chain_pem: format!(
+                        "{}{}",
+                        valid_chain
+                            .certificate_chain_pem
+                            .split_inclusive("-----END CERTIFICATE-----\n")
+                            .next()
+                            .unwrap(),
+                        other_chain
+                            .certificate_chain_pem
+                            .split_inclusive("-----END CERTIFICATE-----\n")
+                            .nth(1)
+                            .unwrap()
+                    ),
+                    ..valid_chain.clone()
+                },
+                expected_valid: false,
+                description: "chain with another CA",
+            },
+            TestCase {
+                domain: "app.example.com",
+                certificate: CustomDomainCertificate {
+                    certificate_chain_pem: "not a certificate".to_string(),
+                    ..valid_chain.clone()
+                },
+                expected_valid: false,
+                description: "invalid PEM",
+            },
+            TestCase {
+                domain: "app.example.com",
+                certificate: new_certificate_chain(&["app.example.com"], 0),
+                expected_valid: true,
+                description: "expired certificate, its validity period is only reported",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_certificate(tc.domain, &tc.certificate);
+
+            // verify:
+            assert_eq!(tc.expected_valid, result.is_ok(), "case: {} {:?}", tc.description, result);
+        }
+    }
+
+    #[test]
+    fn test_certificate_expiration() {
+        // setup:
+        let certificate = new_certificate_chain(&["app.example.com"], 10);
+
+        // execute:
+        let info = validate_certificate("app.example.com", &certificate).unwrap();
+
+        // verify:
+        assert_eq!(vec!["app.example.com".to_string()], info.subject_alt_names);
+        assert!(info.days_before_expiration <= 10);
+        assert!(info.expires_soon());
+        assert_eq!(None, info.validity_period_error);
+    }
+
+    #[test]
+    fn test_expired_certificate() {
+        // setup:
+        let certificate = new_certificate_chain(&["app.example.com"], 0);
+
+        // execute:
+        let info = validate_certificate("app.example.com", &certificate).unwrap();
+
+        // verify:
+        assert!(info.expires_soon());
+        assert!(info
+            .validity_period_error
+            .unwrap_or_default()
+            .starts_with("certificate expired on"));
+    }
+}
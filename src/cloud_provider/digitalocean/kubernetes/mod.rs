@@ -5,6 +5,7 @@ use std::fs::File;
 use serde::{Deserialize, Serialize};
 use tera::Context as TeraContext;
 
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::regions::AwsZones;
 use crate::cloud_provider::digitalocean::do_api_common::{do_get_from_api, DoApiType};
 use crate::cloud_provider::digitalocean::kubernetes::doks_api::{
@@ -20,13 +21,16 @@ use crate::cloud_provider::digitalocean::network::vpc::{
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::helm::{deploy_charts_levels, ChartInfo, ChartSetValue, HelmChartNamespaces};
 use crate::cloud_provider::kubernetes::{
-    is_kubernetes_upgrade_required, send_progress_on_long_task, uninstall_cert_manager, Kind, Kubernetes,
-    KubernetesUpgradeStatus, ProviderOptions,
+    check_ingress_controller_supports_kubernetes_version, custom_config_file_store, is_kubernetes_upgrade_required,
+    put_kubeconfig_into_config_file_store, send_progress_on_long_task, uninstall_cert_manager, Kind, Kubernetes,
+    KubernetesApiCapabilities, KubernetesUpgradeStatus, ProviderOptions, VOLUME_SNAPSHOT_CLASS,
 };
 use crate::cloud_provider::models::NodeGroups;
+use crate::cloud_provider::pod_security::PodSecurityPolicy;
 use crate::cloud_provider::qovery::EngineLocation;
 use crate::cloud_provider::utilities::print_action;
 use crate::cloud_provider::{kubernetes, CloudProvider};
+use crate::cmd;
 use crate::cmd::helm::{to_engine_error, Helm};
 use crate::cmd::kubectl::{
     do_kubectl_exec_get_loadbalancer_id, kubectl_exec_get_all_namespaces, kubectl_exec_get_events,
@@ -46,11 +50,11 @@ use crate::io_models::{
 use crate::logger::Logger;
 use crate::models::digital_ocean::DoRegion;
 use crate::models::types::VersionsNumber;
+use crate::object_storage::s3_compatible::{S3Compatible, S3CompatibleSettings};
 use crate::object_storage::spaces::{BucketDeleteStrategy, Spaces};
 use crate::object_storage::ObjectStorage;
 use crate::runtime::block_on;
 use crate::string::terraform_list_format;
-use crate::{cmd, dns_provider};
 use ::function_name::named;
 use retry::delay::Fibonacci;
 use retry::Error::Operation;
@@ -88,6 +92,12 @@ pub struct DoksOptions {
     pub qovery_ssh_key: String,
     // Others
     pub tls_email_report: String,
+    #[serde(default)]
+    pub acme: Option<AcmeSettings>,
+    #[serde(default)]
+    pub pod_security_policy: PodSecurityPolicy,
+    #[serde(default)]
+    pub config_file_store: Option<S3CompatibleSettings>,
 }
 
 impl ProviderOptions for DoksOptions {}
@@ -103,6 +113,7 @@ pub struct DOKS {
     nodes_groups: Vec<NodeGroups>,
     dns_provider: Arc<Box<dyn DnsProvider>>,
     spaces: Spaces,
+    config_file_store: Option<S3Compatible>,
     template_directory: String,
     options: DoksOptions,
     listeners: Listeners,
@@ -157,6 +168,8 @@ impl DOKS {
             BucketDeleteStrategy::HardDelete,
         );
 
+        let config_file_store = custom_config_file_store(&context, options.config_file_store.as_ref());
+
         let listeners = cloud_provider.listeners().clone();
         Ok(DOKS {
             context,
@@ -168,6 +181,7 @@ impl DOKS {
             cloud_provider,
             dns_provider,
             spaces,
+            config_file_store,
             options,
             nodes_groups,
             template_directory,
@@ -258,13 +272,7 @@ impl DOKS {
             &managed_dns_resolvers_terraform_format,
         );
         context.insert("wildcard_managed_dns", &self.dns_provider().domain().wildcarded().to_string());
-        match self.dns_provider.kind() {
-            dns_provider::Kind::Cloudflare => {
-                context.insert("external_dns_provider", self.dns_provider.provider_name());
-                context.insert("cloudflare_api_token", self.dns_provider.token());
-                context.insert("cloudflare_email", self.dns_provider.account());
-            }
-        };
+        self.dns_provider.configuration().insert_into_tera_context(&mut context);
 
         context.insert("dns_email_report", &self.options.tls_email_report);
 
@@ -312,7 +320,7 @@ impl DOKS {
         context.insert("grafana_admin_password", self.options.grafana_admin_password.as_str());
 
         // TLS
-        context.insert("acme_server_url", &self.lets_encrypt_url());
+        context.insert("acme_server_url", &self.acme_settings().directory_url);
 
         // AWS S3 tfstates storage tfstates
         context.insert(
@@ -422,14 +430,6 @@ impl DOKS {
         format!("qovery-nginx-{}.{}", self.cloud_provider.id(), self.dns_provider().domain())
     }
 
-    fn lets_encrypt_url(&self) -> String {
-        match &self.context.is_test_cluster() {
-            true => "https://acme-staging-v02.api.letsencrypt.org/directory",
-            false => "https://acme-v02.api.letsencrypt.org/directory",
-        }
-        .to_string()
-    }
-
     // return cluster info from name if exists
     fn get_doks_info_from_name_api(&self) -> Result<KubernetesCluster, CommandError> {
         let api_url = format!("{}/clusters", DoApiType::Doks.api_url());
@@ -467,6 +467,12 @@ impl DOKS {
             EventMessage::new_from_safe("Preparing cluster deployment.".to_string()),
         ));
 
+        check_ingress_controller_supports_kubernetes_version(
+            self.context().lib_root_dir(),
+            self.version(),
+            event_details.clone(),
+        )?;
+
         // upgrade cluster instead if required
         match self.get_kubeconfig_file() {
             Ok((path, _)) => match is_kubernetes_upgrade_required(
@@ -585,6 +591,10 @@ impl DOKS {
         }
 
         let kubeconfig_path = &self.get_kubeconfig_file_path()?;
+        // DigitalOcean serves the kubeconfig from its API, it's only stored in a custom config file store
+        if self.config_file_store.is_some() {
+            put_kubeconfig_into_config_file_store(self, kubeconfig_path, event_details.clone())?;
+        }
         let kubeconfig_path = Path::new(kubeconfig_path);
 
         match self.check_workers_on_create() {
@@ -639,10 +649,8 @@ impl DOKS {
             managed_dns_helm_format: self.dns_provider.domain().to_helm_format_string(),
             managed_dns_resolvers_terraform_format: self.managed_dns_resolvers_terraform_format(),
             external_dns_provider: self.dns_provider.provider_name().to_string(),
-            dns_email_report: self.options.tls_email_report.clone(),
-            acme_url: self.lets_encrypt_url(),
-            cloudflare_email: self.dns_provider.account().to_string(),
-            cloudflare_api_token: self.dns_provider.token().to_string(),
+            acme_settings: self.acme_settings(),
+            dns_provider_configuration: self.dns_provider.configuration(),
             disable_pleco: self.context.disable_pleco(),
         };
 
@@ -1131,6 +1139,19 @@ impl Kubernetes for DOKS {
         None
     }
 
+    fn node_groups(&self) -> &[NodeGroups] {
+        &self.nodes_groups
+    }
+
+    fn volume_snapshot_class(&self, api_capabilities: &KubernetesApiCapabilities) -> Option<&str> {
+        // the snapshot class is only installed when the cluster serves the v1 snapshot api
+        if api_capabilities.volume_snapshot_api {
+            Some(VOLUME_SNAPSHOT_CLASS)
+        } else {
+            None
+        }
+    }
+
     fn cloud_provider(&self) -> &dyn CloudProvider {
         self.cloud_provider.as_ref().borrow()
     }
@@ -1139,12 +1160,23 @@ impl Kubernetes for DOKS {
         self.dns_provider.as_ref().borrow()
     }
 
+    fn acme_settings(&self) -> AcmeSettings {
+        AcmeSettings::from_options(&self.options.acme, &self.context, &self.options.tls_email_report)
+    }
+
+    fn pod_security_policy(&self) -> PodSecurityPolicy {
+        self.options.pod_security_policy.clone()
+    }
+
     fn logger(&self) -> &dyn Logger {
         self.logger.borrow()
     }
 
     fn config_file_store(&self) -> &dyn ObjectStorage {
-        &self.spaces
+        match &self.config_file_store {
+            Some(config_file_store) => config_file_store,
+            None => &self.spaces,
+        }
     }
 
     fn is_valid(&self) -> Result<(), EngineError> {
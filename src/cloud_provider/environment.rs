@@ -1,5 +1,7 @@
+use crate::cloud_provider::network_policy::EnvironmentNetworkPolicy;
 use crate::cloud_provider::service::{Action, DatabaseService, RouterService, StatefulService, StatelessService};
 use crate::models::application::ApplicationService;
+use crate::models::job::JobService;
 
 pub struct Environment {
     namespace: String,
@@ -11,6 +13,8 @@ pub struct Environment {
     pub applications: Vec<Box<dyn ApplicationService>>,
     pub routers: Vec<Box<dyn RouterService>>,
     pub databases: Vec<Box<dyn DatabaseService>>,
+    pub jobs: Vec<Box<dyn JobService>>,
+    pub network_policy: EnvironmentNetworkPolicy,
 }
 
 impl Environment {
@@ -23,6 +27,8 @@ impl Environment {
         applications: Vec<Box<dyn ApplicationService>>,
         routers: Vec<Box<dyn RouterService>>,
         databases: Vec<Box<dyn DatabaseService>>,
+        jobs: Vec<Box<dyn JobService>>,
+        network_policy: EnvironmentNetworkPolicy,
     ) -> Self {
         Environment {
             namespace: format!("{}-{}", project_id, id),
@@ -34,12 +40,14 @@ impl Environment {
             applications,
             routers,
             databases,
+            jobs,
+            network_policy,
         }
     }
 
     pub fn stateless_services(&self) -> Vec<&dyn StatelessService> {
         let mut stateless_services: Vec<&dyn StatelessService> =
-            Vec::with_capacity(self.applications.len() + self.routers.len());
+            Vec::with_capacity(self.applications.len() + self.routers.len() + self.jobs.len());
         stateless_services.extend_from_slice(
             self.applications
                 .iter()
@@ -54,6 +62,13 @@ impl Environment {
                 .collect::<Vec<_>>()
                 .as_slice(),
         );
+        stateless_services.extend_from_slice(
+            self.jobs
+                .iter()
+                .map(|x| x.as_stateless_service())
+                .collect::<Vec<_>>()
+                .as_slice(),
+        );
 
         stateless_services
     }
@@ -1,15 +1,18 @@
 use crate::cloud_provider::helm::HelmAction::Deploy;
 use crate::cloud_provider::helm::HelmChartNamespaces::KubeSystem;
+use crate::cloud_provider::network_policy::INGRESS_CONTROLLER_NAMESPACE_LABEL;
 use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, QoveryAppName, QoveryShellAgent};
 use crate::cmd::helm::{to_command_error, Helm};
 use crate::cmd::helm_utils::{
     apply_chart_backup, delete_unused_chart_backup, prepare_chart_backup_on_upgrade, BackupStatus,
 };
 use crate::cmd::kubectl::{
-    kubectl_delete_crash_looping_pods, kubectl_exec_delete_crd, kubectl_exec_get_configmap, kubectl_exec_get_events,
-    kubectl_exec_rollout_restart_deployment, kubectl_exec_with_output,
+    kubectl_delete_crash_looping_pods, kubectl_exec_create_namespace, kubectl_exec_delete_crd,
+    kubectl_exec_get_configmap, kubectl_exec_get_events, kubectl_exec_rollout_restart_deployment,
+    kubectl_exec_with_output,
 };
-use crate::cmd::structs::HelmHistoryRow;
+use crate::cmd::structs::{HelmHistoryRow, LabelsContent};
+use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::{CommandError, ErrorMessageVerbosity};
 use crate::utilities::calculate_hash;
 use semver::Version;
@@ -631,6 +634,53 @@ impl HelmChart for CoreDNSConfigChart {
     }
 }
 
+// Nginx ingress
+
+/// Ingress controller chart, its namespace is labeled for the strict network policies to select it.
+#[derive(Default)]
+pub struct NginxIngressChart {
+    pub chart_info: ChartInfo,
+}
+
+impl HelmChart for NginxIngressChart {
+    fn get_chart_info(&self) -> &ChartInfo {
+        &self.chart_info
+    }
+
+    fn pre_exec(
+        &self,
+        kubernetes_config: &Path,
+        envs: &[(String, String)],
+        payload: Option<ChartPayload>,
+    ) -> Result<Option<ChartPayload>, CommandError> {
+        let environment_variables: Vec<(&str, &str)> = envs.iter().map(|x| (x.0.as_str(), x.1.as_str())).collect();
+        let namespace = self.namespace();
+
+        // Cleaning any existing crash looping pod for this helm chart
+        if let Some(selector) = self.get_selector() {
+            kubectl_delete_crash_looping_pods(
+                kubernetes_config,
+                Some(namespace.as_str()),
+                Some(selector.as_str()),
+                environment_variables.clone(),
+            )?;
+        }
+
+        // the namespace name label is only set by Kubernetes >= 1.21
+        kubectl_exec_create_namespace(
+            kubernetes_config,
+            namespace.as_str(),
+            Some(vec![LabelsContent {
+                name: INGRESS_CONTROLLER_NAMESPACE_LABEL.to_string(),
+                value: "true".to_string(),
+            }]),
+            environment_variables,
+        )?;
+
+        Ok(payload)
+    }
+}
+
 // Prometheus Operator
 
 #[derive(Default)]
@@ -721,6 +771,75 @@ pub struct ShellAgentContext<'a> {
     pub grpc_url: &'a str,
 }
 
+/// cert-manager-configs chart values the DNS-01 solver needs to manage challenge records.
+pub fn get_cert_manager_dns_provider_values(configuration: &DnsProviderConfiguration) -> Vec<ChartSetValue> {
+    match configuration {
+        DnsProviderConfiguration::Cloudflare { api_token, email } => vec![
+            ChartSetValue {
+                key: "provider.cloudflare.apiToken".to_string(),
+                value: api_token.to_string(),
+            },
+            ChartSetValue {
+                key: "provider.cloudflare.email".to_string(),
+                value: email.to_string(),
+            },
+        ],
+        DnsProviderConfiguration::Route53 {
+            access_key_id,
+            secret_access_key,
+            region,
+            hosted_zone_id,
+        } => {
+            let mut values = vec![
+                ChartSetValue {
+                    key: "provider.route53.accessKeyId".to_string(),
+                    value: access_key_id.to_string(),
+                },
+                ChartSetValue {
+                    key: "provider.route53.secretAccessKey".to_string(),
+                    value: secret_access_key.to_string(),
+                },
+                ChartSetValue {
+                    key: "provider.route53.region".to_string(),
+                    value: region.to_string(),
+                },
+            ];
+            if let Some(hosted_zone_id) = hosted_zone_id {
+                values.push(ChartSetValue {
+                    key: "provider.route53.hostedZoneId".to_string(),
+                    value: hosted_zone_id.to_string(),
+                });
+            }
+            values
+        }
+        DnsProviderConfiguration::Rfc2136 {
+            nameserver,
+            port,
+            tsig_key_name,
+            tsig_secret,
+            tsig_algorithm,
+            ..
+        } => vec![
+            ChartSetValue {
+                key: "provider.rfc2136.nameserver".to_string(),
+                value: format!("{}:{}", nameserver, port),
+            },
+            ChartSetValue {
+                key: "provider.rfc2136.tsigKeyName".to_string(),
+                value: tsig_key_name.to_string(),
+            },
+            ChartSetValue {
+                key: "provider.rfc2136.tsigAlgorithm".to_string(),
+                value: tsig_algorithm.to_cert_manager_format().to_string(),
+            },
+            ChartSetValue {
+                key: "provider.rfc2136.tsigSecret".to_string(),
+                value: tsig_secret.to_string(),
+            },
+        ],
+    }
+}
+
 pub fn get_chart_for_shell_agent(
     context: ShellAgentContext,
     chart_path: impl Fn(&str) -> String,
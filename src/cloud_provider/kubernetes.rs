@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::os::unix::fs::PermissionsExt;
@@ -13,17 +14,22 @@ use retry::delay::{Fibonacci, Fixed};
 use retry::Error::Operation;
 use retry::OperationResult;
 use serde::{Deserialize, Serialize};
+use tera::Context as TeraContext;
 
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::regions::AwsZones;
 use crate::cloud_provider::environment::Environment;
+use crate::cloud_provider::helm::ChartInfo;
 use crate::cloud_provider::models::{CpuLimits, NodeGroups};
+use crate::cloud_provider::pod_security::PodSecurityPolicy;
 use crate::cloud_provider::service::CheckAction;
 use crate::cloud_provider::{service, CloudProvider, DeploymentTarget};
+use crate::cmd::helm_utils::get_common_helm_chart_infos;
 use crate::cmd::kubectl;
 use crate::cmd::kubectl::{
-    kubectl_delete_objects_in_all_namespaces, kubectl_exec_count_all_objects, kubectl_exec_delete_pod,
-    kubectl_exec_get_node, kubectl_exec_is_namespace_present, kubectl_exec_version, kubectl_get_crash_looping_pods,
-    kubernetes_get_all_pdbs,
+    kubectl_delete_objects_in_all_namespaces, kubectl_exec_api_versions, kubectl_exec_count_all_objects,
+    kubectl_exec_delete_pod, kubectl_exec_get_node, kubectl_exec_is_namespace_present, kubectl_exec_version,
+    kubectl_get_crash_looping_pods, kubernetes_get_all_pdbs,
 };
 use crate::cmd::structs::KubernetesNodeCondition;
 use crate::dns_provider::DnsProvider;
@@ -33,13 +39,21 @@ use crate::events::{EngineEvent, EventDetails, EventMessage, GeneralStep, Infras
 use crate::fs::workspace_directory;
 use crate::io_models::ProgressLevel::Info;
 use crate::io_models::{
-    Action, Context, Listen, ListenersHelper, ProgressInfo, ProgressLevel, ProgressScope, QoveryIdentifier, StringPath,
+    Action, Context, Listen, ListenersHelper, ProgressInfo, ProgressLevel, ProgressScope, QoveryIdentifier, Scheduling,
+    StringPath, TaintEffect, TolerationOperator,
 };
 use crate::logger::Logger;
 use crate::models::types::VersionsNumber;
+use crate::object_storage::s3_compatible::{S3Compatible, S3CompatibleSettings};
 use crate::object_storage::ObjectStorage;
 use crate::unit_conversion::{any_to_mi, cpu_string_to_float};
 
+/// Node label set by Qovery on every node of a cluster node group, with the node group name as value.
+pub const NODE_GROUP_LABEL: &str = "qovery.com/node-group";
+
+/// CSI volume snapshot class installed with the cluster storage classes.
+pub const VOLUME_SNAPSHOT_CLASS: &str = "qovery-volume-snapshots";
+
 pub trait ProviderOptions {}
 
 pub trait Kubernetes: Listen {
@@ -60,8 +74,14 @@ pub trait Kubernetes: Listen {
     fn region(&self) -> String;
     fn zone(&self) -> &str;
     fn aws_zones(&self) -> Option<Vec<AwsZones>>;
+    /// Worker node groups, their nodes carry the node group name in the `NODE_GROUP_LABEL` label.
+    fn node_groups(&self) -> &[NodeGroups];
+    /// Volume snapshot class of the cluster CSI driver, `None` when volumes can't be snapshotted.
+    fn volume_snapshot_class(&self, api_capabilities: &KubernetesApiCapabilities) -> Option<&str>;
     fn cloud_provider(&self) -> &dyn CloudProvider;
     fn dns_provider(&self) -> &dyn DnsProvider;
+    fn acme_settings(&self) -> AcmeSettings;
+    fn pod_security_policy(&self) -> PodSecurityPolicy;
     fn logger(&self) -> &dyn Logger;
     fn config_file_store(&self) -> &dyn ObjectStorage;
     fn is_valid(&self) -> Result<(), EngineError>;
@@ -181,6 +201,56 @@ pub trait Kubernetes: Listen {
         Ok(path)
     }
 
+    /// Node selector of pods with the given scheduling settings, pinning them to their node pool if any.
+    fn node_selector(
+        &self,
+        scheduling: &Scheduling,
+        event_details: EventDetails,
+    ) -> Result<BTreeMap<String, String>, EngineError> {
+        let mut node_selector = scheduling.node_selector.clone();
+
+        if let Some(node_pool) = &scheduling.node_pool {
+            if !self
+                .node_groups()
+                .iter()
+                .any(|node_group| &node_group.name == node_pool)
+            {
+                return Err(EngineError::new_unknown_node_pool(
+                    event_details,
+                    node_pool.to_string(),
+                    self.node_groups()
+                        .iter()
+                        .map(|node_group| node_group.name.clone())
+                        .collect(),
+                ));
+            }
+            node_selector.insert(NODE_GROUP_LABEL.to_string(), node_pool.to_string());
+        }
+
+        Ok(node_selector)
+    }
+
+    /// APIs served by the cluster, based on the server version or on the requested version when the cluster is unreachable.
+    fn api_capabilities(&self) -> KubernetesApiCapabilities {
+        let kubeconfig = self.get_kubeconfig_file_path().ok();
+        let envs = self.cloud_provider().credentials_environment_variables();
+        let server_version = kubeconfig
+            .as_ref()
+            .and_then(|kubeconfig| kubectl_exec_version(kubeconfig, envs.clone()).ok())
+            .and_then(|v| {
+                VersionsNumber::from_str(format!("{}.{}", v.server_version.major, v.server_version.minor).as_str()).ok()
+            });
+        let served_api_versions = kubeconfig
+            .as_ref()
+            .and_then(|kubeconfig| kubectl_exec_api_versions(kubeconfig, envs.clone()).ok())
+            .unwrap_or_default();
+
+        KubernetesApiCapabilities::from_version(
+            server_version.or_else(|| VersionsNumber::from_str(self.version()).ok()),
+        )
+        .with_served_api_versions(&served_api_versions)
+    }
+
     fn resources(&self, _environment: &Environment) -> Result<Resources, EngineError> {
         let kubernetes_config_file_path = self.get_kubeconfig_file_path()?;
         let stage = Stage::General(GeneralStep::RetrieveClusterResources);
@@ -228,6 +298,12 @@ pub trait Kubernetes: Listen {
     fn upgrade(&self) -> Result<(), EngineError> {
         let event_details = self.get_event_details(Infrastructure(InfrastructureStep::Upgrade));
 
+        check_ingress_controller_supports_kubernetes_version(
+            self.context().lib_root_dir(),
+            self.version(),
+            event_details.clone(),
+        )?;
+
         let kubeconfig = match self.get_kubeconfig_file() {
             Ok((path, _)) => path,
             Err(e) => return Err(e),
@@ -410,23 +486,12 @@ pub fn deploy_environment(
 ) -> Result<(), EngineError> {
     let listeners_helper = ListenersHelper::new(kubernetes.listeners());
 
-    let stateful_deployment_target = match kubernetes.kind() {
-        Kind::Eks => DeploymentTarget {
-            kubernetes,
-            environment,
-        },
-        Kind::Ec2 => DeploymentTarget {
-            kubernetes,
-            environment,
-        },
-        Kind::Doks => DeploymentTarget {
-            kubernetes,
-            environment,
-        },
-        Kind::ScwKapsule => DeploymentTarget {
-            kubernetes,
-            environment,
-        },
+    // the served APIs are looked up once and shared by all the services of the environment
+    let api_capabilities = kubernetes.api_capabilities();
+    let stateful_deployment_target = DeploymentTarget {
+        kubernetes,
+        environment,
+        api_capabilities: &api_capabilities,
     };
 
     // create all stateful services (database)
@@ -445,7 +510,7 @@ pub fn deploy_environment(
 
         // check all deployed services
         let _ = service::check_kubernetes_service_error(
-            service.exec_check_action(),
+            service.exec_check_action(&stateful_deployment_target),
             kubernetes,
             service,
             event_details.clone(),
@@ -464,6 +529,7 @@ pub fn deploy_environment(
     let stateless_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // create all stateless services (router, application...)
@@ -487,7 +553,7 @@ pub fn deploy_environment(
     // check all deployed services
     for service in environment.stateful_services() {
         let _ = service::check_kubernetes_service_error(
-            service.exec_check_action(),
+            service.exec_check_action(&stateless_deployment_target),
             kubernetes,
             service,
             event_details.clone(),
@@ -504,7 +570,7 @@ pub fn deploy_environment(
 
     for service in environment.stateless_services() {
         let _ = service::check_kubernetes_service_error(
-            service.exec_check_action(),
+            service.exec_check_action(&stateless_deployment_target),
             kubernetes,
             service,
             event_details.clone(),
@@ -537,9 +603,11 @@ pub fn deploy_environment_error(
         kubernetes.context().execution_id(),
     ));
 
+    let api_capabilities = kubernetes.api_capabilities();
     let stateful_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // clean up all stateful services (database)
@@ -564,6 +632,7 @@ pub fn deploy_environment_error(
     let stateless_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // clean up all stateless services (router, application...)
@@ -593,15 +662,18 @@ pub fn pause_environment(
 ) -> Result<(), EngineError> {
     let listeners_helper = ListenersHelper::new(kubernetes.listeners());
 
+    let api_capabilities = kubernetes.api_capabilities();
     let stateful_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // stateless services are deployed on kubernetes, that's why we choose the deployment target SelfHosted.
     let stateless_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // create all stateless services (router, application...)
@@ -697,15 +769,18 @@ pub fn delete_environment(
         return Ok(());
     };
 
+    let api_capabilities = kubernetes.api_capabilities();
     let stateful_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // stateless services are deployed on kubernetes, that's why we choose the deployment target SelfHosted.
     let stateless_deployment_target = DeploymentTarget {
         kubernetes,
         environment,
+        api_capabilities: &api_capabilities,
     };
 
     // delete all stateless services (router, application...)
@@ -945,6 +1020,55 @@ where
     }
 }
 
+/// Check the bundled ingress-nginx controller watches the Ingress API served by the cluster version.
+///
+/// Kubernetes 1.22 only serves `networking.k8s.io/v1` Ingress, ingress-nginx controllers older than 1.0 don't watch it
+/// and would stop routing every environment of the cluster.
+pub fn check_ingress_controller_supports_kubernetes_version(
+    lib_root_dir: &str,
+    kubernetes_version: &str,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    let ingress_controller_chart = ChartInfo {
+        name: "ingress-nginx".to_string(),
+        path: format!("{}/common/bootstrap/charts/ingress-nginx", lib_root_dir),
+        ..Default::default()
+    };
+    let ingress_controller_version = get_common_helm_chart_infos(&ingress_controller_chart)
+        .map_err(|e| EngineError::new_helm_error(event_details.clone(), e))?
+        .app_version;
+
+    match (
+        VersionsNumber::from_str(ingress_controller_version.as_str()),
+        VersionsNumber::from_str(kubernetes_version),
+    ) {
+        (Ok(controller_version), Ok(kubernetes_version))
+            if !ingress_controller_supports_kubernetes_version(&controller_version, &kubernetes_version) =>
+        {
+            Err(EngineError::new_ingress_controller_unsupported_kubernetes_version(
+                event_details,
+                kubernetes_version.to_string(),
+                ingress_controller_version,
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn ingress_controller_supports_kubernetes_version(
+    controller_version: &VersionsNumber,
+    kubernetes_version: &VersionsNumber,
+) -> bool {
+    let minor_version = |version: &VersionsNumber| -> Option<(u32, u32)> {
+        Some((version.major.parse::<u32>().ok()?, version.minor.as_ref()?.parse::<u32>().ok()?))
+    };
+
+    match (minor_version(controller_version), minor_version(kubernetes_version)) {
+        (Some((controller_major, _)), Some((1, kubernetes_minor))) => controller_major >= 1 || kubernetes_minor < 22,
+        _ => true,
+    }
+}
+
 pub fn check_workers_upgrade_status<P>(
     kubernetes_config: P,
     envs: Vec<(&str, &str)>,
@@ -1027,6 +1151,63 @@ pub struct KubernetesUpgradeStatus {
     pub older_workers_version_detected: bool,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct KubernetesApiCapabilities {
+    pub version: Option<VersionsNumber>,
+    pub ingress_api_version: String,
+    pub autoscaling_api_version: String,
+    pub cronjob_api_version: String,
+    pub volume_snapshot_api: bool,
+}
+
+impl KubernetesApiCapabilities {
+    pub fn from_version(version: Option<VersionsNumber>) -> Self {
+        let minor_version = |version: &VersionsNumber| -> Option<(u32, u32)> {
+            Some((version.major.parse::<u32>().ok()?, version.minor.as_ref()?.parse::<u32>().ok()?))
+        };
+
+        // networking.k8s.io/v1 Ingress is served since 1.19, v1beta1 has been removed in 1.22
+        let ingress_api_version = match version.as_ref().and_then(minor_version) {
+            Some((major, minor)) if major == 1 && minor < 19 => "networking.k8s.io/v1beta1",
+            _ => "networking.k8s.io/v1",
+        };
+
+        // autoscaling/v2 is served since 1.23, v2beta2 has the same schema and is served since 1.12
+        let autoscaling_api_version = match version.as_ref().and_then(minor_version) {
+            Some((major, minor)) if major == 1 && minor < 23 => "autoscaling/v2beta2",
+            _ => "autoscaling/v2",
+        };
+
+        // batch/v1 CronJob is served since 1.21, v1beta1 has been removed in 1.25
+        let cronjob_api_version = match version.as_ref().and_then(minor_version) {
+            Some((major, minor)) if major == 1 && minor < 21 => "batch/v1beta1",
+            _ => "batch/v1",
+        };
+
+        KubernetesApiCapabilities {
+            version,
+            ingress_api_version: ingress_api_version.to_string(),
+            autoscaling_api_version: autoscaling_api_version.to_string(),
+            cronjob_api_version: cronjob_api_version.to_string(),
+            volume_snapshot_api: false,
+        }
+    }
+
+    /// The snapshot API isn't part of a Kubernetes release, it is served once the snapshot controller CRDs are installed.
+    pub fn with_served_api_versions(mut self, served_api_versions: &[String]) -> Self {
+        self.volume_snapshot_api = served_api_versions
+            .iter()
+            .any(|api_version| api_version == "snapshot.storage.k8s.io/v1");
+        self
+    }
+
+    pub fn insert_into_tera_context(&self, context: &mut TeraContext) {
+        context.insert("ingress_api_version", &self.ingress_api_version);
+        context.insert("autoscaling_api_version", &self.autoscaling_api_version);
+        context.insert("cronjob_api_version", &self.cronjob_api_version);
+    }
+}
+
 /// Check if Kubernetes cluster elements are requiring an upgrade
 ///
 /// It will gives useful info:
@@ -1261,6 +1442,48 @@ impl NodeGroups {
 /// TODO(benjaminch): to be refactored with similar function in services.rs
 /// This function call (start|pause|delete)_in_progress function every 10 seconds when a
 /// long blocking task is running.
+/// S3 compatible store set in the cluster options, used as config file store instead of the cloud provider one.
+pub fn custom_config_file_store(context: &Context, settings: Option<&S3CompatibleSettings>) -> Option<S3Compatible> {
+    settings.map(|settings| {
+        S3Compatible::from_settings(
+            context.clone(),
+            "config-file-store-id".to_string(),
+            "config-file-store".to_string(),
+            settings,
+        )
+    })
+}
+
+/// Pushes the cluster kubeconfig into the config file store, the cluster only uploads it to its cloud provider bucket.
+pub fn put_kubeconfig_into_config_file_store(
+    kubernetes: &dyn Kubernetes,
+    kubeconfig_path: &str,
+    event_details: EventDetails,
+) -> Result<(), EngineError> {
+    let bucket_name = format!("qovery-kubeconfigs-{}", kubernetes.id());
+    let object_key = kubernetes.get_kubeconfig_filename();
+    let config_file_store = kubernetes.config_file_store();
+
+    if let Err(e) = config_file_store.create_bucket(bucket_name.as_str()) {
+        let error = EngineError::new_object_storage_cannot_create_bucket_error(event_details, bucket_name, e);
+        kubernetes.logger().log(EngineEvent::Error(error.clone(), None));
+        return Err(error);
+    }
+
+    if let Err(e) = config_file_store.put(bucket_name.as_str(), object_key.as_str(), kubeconfig_path) {
+        let error = EngineError::new_object_storage_cannot_put_file_into_bucket_error(
+            event_details,
+            bucket_name,
+            object_key,
+            e,
+        );
+        kubernetes.logger().log(EngineEvent::Error(error.clone(), None));
+        return Err(error);
+    }
+
+    Ok(())
+}
+
 pub fn send_progress_on_long_task<K, R, F>(kubernetes: &K, action: Action, long_task: F) -> R
 where
     K: Kubernetes + Listen,
@@ -1379,6 +1602,39 @@ where
     blocking_task_result
 }
 
+/// Checks scheduling settings which don't depend on the cluster, the node pool is checked by `Kubernetes::node_selector`.
+pub fn validate_scheduling(scheduling: &Scheduling) -> Result<(), String> {
+    if scheduling.node_selector.keys().any(|key| key.is_empty()) {
+        return Err("node selector labels can't be empty".to_string());
+    }
+    if scheduling.node_pool.is_some() && scheduling.node_selector.contains_key(NODE_GROUP_LABEL) {
+        return Err(format!("node selector can't set `{}` with a node pool", NODE_GROUP_LABEL));
+    }
+
+    for toleration in &scheduling.tolerations {
+        match toleration.operator {
+            TolerationOperator::Exists if toleration.value.is_some() => {
+                return Err(format!(
+                    "toleration `{}` can't have a value with the Exists operator",
+                    toleration.key
+                ));
+            }
+            TolerationOperator::Equal if toleration.key.is_empty() => {
+                return Err("toleration key can only be empty with the Exists operator".to_string());
+            }
+            _ => {}
+        }
+        if toleration.toleration_seconds.is_some() && toleration.effect != Some(TaintEffect::NoExecute) {
+            return Err(format!(
+                "toleration `{}` seconds are only supported with the NoExecute effect",
+                toleration.key
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 pub fn validate_k8s_required_cpu_and_burstable(
     listener_helper: &ListenersHelper,
     execution_id: &str,
@@ -1450,12 +1706,15 @@ mod tests {
 
     use crate::cloud_provider::kubernetes::{
         check_kubernetes_upgrade_status, compare_kubernetes_cluster_versions_for_upgrade, convert_k8s_cpu_value_to_f32,
-        validate_k8s_required_cpu_and_burstable, KubernetesNodesType,
+        ingress_controller_supports_kubernetes_version, validate_k8s_required_cpu_and_burstable, validate_scheduling,
+        KubernetesApiCapabilities, KubernetesNodesType, NODE_GROUP_LABEL,
     };
     use crate::cloud_provider::models::CpuLimits;
     use crate::cmd::structs::{KubernetesList, KubernetesNode, KubernetesVersion};
     use crate::events::{EventDetails, InfrastructureStep, Stage, Transmitter};
-    use crate::io_models::{ListenersHelper, QoveryIdentifier};
+    use crate::io_models::{
+        ListenersHelper, QoveryIdentifier, Scheduling, TaintEffect, Toleration, TolerationOperator,
+    };
     use crate::logger::StdIoLogger;
     use crate::models::types::VersionsNumber;
     use std::str::FromStr;
@@ -2093,4 +2352,209 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    pub fn test_kubernetes_api_capabilities() {
+        // setup:
+        let test_cases = vec![
+            (
+                Some("1.18"),
+                "networking.k8s.io/v1beta1",
+                "autoscaling/v2beta2",
+                "batch/v1beta1",
+            ),
+            (Some("1.19"), "networking.k8s.io/v1", "autoscaling/v2beta2", "batch/v1beta1"),
+            (Some("1.20"), "networking.k8s.io/v1", "autoscaling/v2beta2", "batch/v1beta1"),
+            (Some("1.21+"), "networking.k8s.io/v1", "autoscaling/v2beta2", "batch/v1"),
+            (Some("1.22"), "networking.k8s.io/v1", "autoscaling/v2beta2", "batch/v1"),
+            (Some("1.23"), "networking.k8s.io/v1", "autoscaling/v2", "batch/v1"),
+            (Some("2.0"), "networking.k8s.io/v1", "autoscaling/v2", "batch/v1"),
+            (None, "networking.k8s.io/v1", "autoscaling/v2", "batch/v1"),
+        ];
+
+        for (version, expected_ingress_api_version, expected_autoscaling_api_version, expected_cronjob_api_version) in
+            test_cases
+        {
+            // execute:
+            let capabilities =
+                KubernetesApiCapabilities::from_version(version.map(|v| VersionsNumber::from_str(v).unwrap()));
+
+            // verify:
+            assert_eq!(
+                expected_ingress_api_version, capabilities.ingress_api_version,
+                "version: {:?}",
+                version
+            );
+            assert_eq!(
+                expected_autoscaling_api_version, capabilities.autoscaling_api_version,
+                "version: {:?}",
+                version
+            );
+            assert_eq!(
+                expected_cronjob_api_version, capabilities.cronjob_api_version,
+                "version: {:?}",
+                version
+            );
+            assert!(!capabilities.volume_snapshot_api, "version: {:?}", version);
+        }
+    }
+
+    #[test]
+    pub fn test_kubernetes_api_capabilities_with_served_api_versions() {
+        // setup:
+        let test_cases = vec![
+            (vec![], false, "discovery failed"),
+            (vec!["v1", "apps/v1", "storage.k8s.io/v1"], false, "no snapshot controller"),
+            (
+                vec!["v1", "snapshot.storage.k8s.io/v1beta1"],
+                false,
+                "only the beta snapshot api",
+            ),
+            (
+                vec!["v1", "snapshot.storage.k8s.io/v1", "snapshot.storage.k8s.io/v1beta1"],
+                true,
+                "snapshot api served",
+            ),
+        ];
+
+        for (served_api_versions, expected_volume_snapshot_api, description) in test_cases {
+            let served_api_versions = served_api_versions
+                .into_iter()
+                .map(|api_version| api_version.to_string())
+                .collect::<Vec<_>>();
+
+            // execute:
+            let capabilities = KubernetesApiCapabilities::from_version(Some(VersionsNumber::from_str("1.23").unwrap()))
+                .with_served_api_versions(&served_api_versions);
+
+            // verify:
+            assert_eq!(
+                expected_volume_snapshot_api, capabilities.volume_snapshot_api,
+                "case: {}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_ingress_controller_supports_kubernetes_version() {
+        // setup:
+        let test_cases = vec![
+            ("0.48.1", "1.21", true),
+            ("0.48.1", "1.21+", true),
+            ("0.48.1", "1.22", false),
+            ("0.48.1", "1.23", false),
+            ("1.0.0", "1.22", true),
+            ("1.1.1", "1.23", true),
+            ("v1.0.4", "1.21", true),
+        ];
+
+        for (controller_version, kubernetes_version, expected) in test_cases {
+            // execute:
+            let supported = ingress_controller_supports_kubernetes_version(
+                &VersionsNumber::from_str(controller_version).unwrap(),
+                &VersionsNumber::from_str(kubernetes_version).unwrap(),
+            );
+
+            // verify:
+            assert_eq!(
+                expected, supported,
+                "controller: {}, kubernetes: {}",
+                controller_version, kubernetes_version
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_validate_scheduling() {
+        // setup:
+        struct TestCase<'a> {
+            scheduling: Scheduling,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let toleration = |operator: TolerationOperator, value: Option<&str>, effect: Option<TaintEffect>| Toleration {
+            key: "spot".to_string(),
+            operator,
+            value: value.map(|value| value.to_string()),
+            effect,
+            toleration_seconds: None,
+        };
+
+        let test_cases = vec![
+            TestCase {
+                scheduling: Scheduling::default(),
+                expected_error: None,
+                description: "no constraint",
+            },
+            TestCase {
+                scheduling: Scheduling {
+                    node_selector: vec![("disktype".to_string(), "ssd".to_string())].into_iter().collect(),
+                    tolerations: vec![
+                        toleration(TolerationOperator::Equal, Some("true"), Some(TaintEffect::NoSchedule)),
+                        Toleration {
+                            toleration_seconds: Some(300),
+                            ..toleration(TolerationOperator::Exists, None, Some(TaintEffect::NoExecute))
+                        },
+                    ],
+                    node_pool: Some("spot".to_string()),
+                },
+                expected_error: None,
+                description: "node selector, tolerations and node pool",
+            },
+            TestCase {
+                scheduling: Scheduling {
+                    node_selector: vec![(NODE_GROUP_LABEL.to_string(), "default".to_string())]
+                        .into_iter()
+                        .collect(),
+                    node_pool: Some("spot".to_string()),
+                    ..Default::default()
+                },
+                expected_error: Some("node selector can't set `qovery.com/node-group` with a node pool"),
+                description: "conflicting node pool",
+            },
+            TestCase {
+                scheduling: Scheduling {
+                    tolerations: vec![toleration(TolerationOperator::Exists, Some("true"), None)],
+                    ..Default::default()
+                },
+                expected_error: Some("can't have a value with the Exists operator"),
+                description: "exists toleration with a value",
+            },
+            TestCase {
+                scheduling: Scheduling {
+                    tolerations: vec![Toleration {
+                        key: "".to_string(),
+                        ..toleration(TolerationOperator::Equal, Some("true"), None)
+                    }],
+                    ..Default::default()
+                },
+                expected_error: Some("toleration key can only be empty with the Exists operator"),
+                description: "equal toleration without key",
+            },
+            TestCase {
+                scheduling: Scheduling {
+                    tolerations: vec![Toleration {
+                        toleration_seconds: Some(300),
+                        ..toleration(TolerationOperator::Equal, Some("true"), Some(TaintEffect::NoSchedule))
+                    }],
+                    ..Default::default()
+                },
+                expected_error: Some("seconds are only supported with the NoExecute effect"),
+                description: "toleration seconds without NoExecute",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_scheduling(&tc.scheduling);
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => assert!(result.unwrap_err().contains(expected_error), "{}", tc.description),
+            }
+        }
+    }
 }
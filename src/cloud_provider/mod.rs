@@ -4,12 +4,14 @@ use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Serialize};
 
 use crate::cloud_provider::environment::Environment;
-use crate::cloud_provider::kubernetes::Kubernetes;
+use crate::cloud_provider::kubernetes::{Kubernetes, KubernetesApiCapabilities};
 use crate::errors::EngineError;
 use crate::events::{EventDetails, Stage, ToTransmitter};
 use crate::io_models::{Context, Listen};
 
+pub mod acme;
 pub mod aws;
+pub mod certificate;
 pub mod digitalocean;
 pub mod environment;
 pub mod helm;
@@ -17,6 +19,8 @@ pub mod io;
 pub mod kubernetes;
 pub mod metrics;
 pub mod models;
+pub mod network_policy;
+pub mod pod_security;
 pub mod qovery;
 pub mod scaleway;
 pub mod service;
@@ -85,4 +89,5 @@ impl TerraformStateCredentials {
 pub struct DeploymentTarget<'a> {
     pub kubernetes: &'a dyn Kubernetes,
     pub environment: &'a Environment,
+    pub api_capabilities: &'a KubernetesApiCapabilities,
 }
@@ -1,4 +1,8 @@
+use crate::io_models::{
+    CustomDomainCertificate, RouteBackendProtocol, RouteRewrite, RouteWeightedApplication, Toleration,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct EnvironmentVariable {
@@ -20,6 +24,8 @@ pub struct Storage<T> {
     pub size_in_gib: u16,
     pub mount_point: String,
     pub snapshot_retention_in_days: u16,
+    /// Volume snapshot the storage is restored from before the application starts
+    pub restore_from_snapshot: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -32,9 +38,162 @@ pub struct StorageDataTemplate {
     pub snapshot_retention_in_days: u16,
 }
 
+/// File mounted with a `subPath` from the `volume_name` ConfigMap or Secret volume, `content` is base64 encoded.
+#[derive(Serialize, Deserialize)]
+pub struct MountedFileDataTemplate {
+    pub key: String,
+    pub mount_path: String,
+    pub content: String,
+    pub mode: u32,
+    pub volume_name: String,
+}
+
+/// Pod toleration with the Kubernetes field names, rendered as JSON in templates and chart values.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TolerationDataTemplate {
+    pub key: String,
+    pub operator: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toleration_seconds: Option<u32>,
+}
+
+impl From<&Toleration> for TolerationDataTemplate {
+    fn from(toleration: &Toleration) -> Self {
+        TolerationDataTemplate {
+            key: toleration.key.clone(),
+            operator: toleration.operator.to_k8s_format().to_string(),
+            value: toleration.value.clone(),
+            effect: toleration.effect.map(|effect| effect.to_k8s_format().to_string()),
+            toleration_seconds: toleration.toleration_seconds,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SeccompProfileDataTemplate {
+    #[serde(rename = "type")]
+    pub profile_type: String,
+}
+
+/// Pod `securityContext` with the Kubernetes field names, rendered as JSON in templates.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PodSecurityContextDataTemplate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_as_non_root: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_as_user: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_as_group: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fs_group: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seccomp_profile: Option<SeccompProfileDataTemplate>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CapabilitiesDataTemplate {
+    pub drop: Vec<String>,
+}
+
+/// Container `securityContext` with the Kubernetes field names, rendered as JSON in templates.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerSecurityContextDataTemplate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_only_root_filesystem: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_privilege_escalation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<CapabilitiesDataTemplate>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PublicPortDataTemplate {
+    pub name: String,
+    pub port: u16,
+    pub target_port: u16,
+}
+
+/// LoadBalancer Service exposing an application TCP or UDP ports, one per protocol since
+/// mixed protocols Services aren't supported by every cloud provider.
+#[derive(Serialize, Deserialize)]
+pub struct PublicServiceDataTemplate {
+    pub name: String,
+    pub protocol: String,
+    pub ports: Vec<PublicPortDataTemplate>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ProbeHeaderDataTemplate {
+    pub name: String,
+    pub value: String,
+}
+
+/// Container probe, `kind` is the Kubernetes container field name: `readinessProbe`, `livenessProbe` or `startupProbe`.
+#[derive(Serialize, Deserialize)]
+pub struct ProbeDataTemplate {
+    pub kind: String,
+    pub probe_type: String,
+    pub path: Option<String>,
+    pub port: Option<u16>,
+    pub scheme: Option<String>,
+    pub headers: Vec<ProbeHeaderDataTemplate>,
+    pub command: Vec<String>,
+    pub initial_delay_seconds: u32,
+    pub period_seconds: u32,
+    pub timeout_seconds: u32,
+    pub success_threshold: u32,
+    pub failure_threshold: u32,
+}
+
+/// HorizontalPodAutoscaler metric, `metric_type` is the Kubernetes metric source type: `Resource`, `Pods` or `External`.
+#[derive(Serialize, Deserialize)]
+pub struct AutoscalingMetricDataTemplate {
+    pub metric_type: String,
+    pub name: String,
+    pub selector: BTreeMap<String, String>,
+    pub average_utilization: Option<u32>,
+    pub average_value: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScalingPolicyDataTemplate {
+    pub policy_type: String,
+    pub value: u32,
+    pub period_seconds: u32,
+}
+
+/// HorizontalPodAutoscaler behavior, `direction` is either `scaleUp` or `scaleDown`.
+#[derive(Serialize, Deserialize)]
+pub struct ScalingRulesDataTemplate {
+    pub direction: String,
+    pub stabilization_window_seconds: Option<u32>,
+    pub select_policy: Option<String>,
+    pub policies: Vec<ScalingPolicyDataTemplate>,
+}
+
 pub struct CustomDomain {
     pub domain: String,
     pub target_domain: String,
+    /// Value expected in the `_qovery-challenge` TXT record before routing traffic to the domain.
+    pub verification_token: String,
+    pub certificate: Option<CustomDomainCertificate>,
+}
+
+impl CustomDomain {
+    pub fn verification_record_name(&self) -> String {
+        custom_domain_verification_record_name(self.domain.as_str())
+    }
+}
+
+pub fn custom_domain_verification_record_name(domain: &str) -> String {
+    format!("_qovery-challenge.{}", domain.trim_end_matches('.'))
 }
 
 #[derive(Serialize, Deserialize)]
@@ -44,9 +203,30 @@ pub struct CustomDomainDataTemplate {
     pub target_domain: String,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct CustomDomainCertificateDataTemplate {
+    pub domain: String,
+    pub domain_hash: String,
+    pub tls_secret_name: String,
+    pub certificate_chain_pem: String,
+    pub private_key_pem: String,
+}
+
 pub struct Route {
     pub path: String,
     pub application_name: String,
+    pub rewrite: Option<RouteRewrite>,
+    pub backend_protocol: RouteBackendProtocol,
+    pub weighted_applications: Vec<RouteWeightedApplication>,
+}
+
+impl Route {
+    /// Application receiving a share of the route traffic besides `application_name`
+    pub fn canary(&self) -> Option<&RouteWeightedApplication> {
+        self.weighted_applications
+            .iter()
+            .find(|weighted_application| weighted_application.application_name != self.application_name)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -54,6 +234,28 @@ pub struct RouteDataTemplate {
     pub path: String,
     pub application_name: String,
     pub application_port: u16,
+    /// Set for routes having a rewrite, their path is then a regex
+    pub rewrite_target: Option<String>,
+    pub backend_protocol: RouteBackendProtocol,
+    /// Annotations of the Ingress serving this route
+    pub annotations: BTreeMap<String, String>,
+}
+
+/// Route path served by a canary Ingress, receiving `weight` percent of the route requests.
+#[derive(Serialize, Deserialize)]
+pub struct CanaryRouteDataTemplate {
+    pub path: String,
+    pub application_name: String,
+    pub application_port: u16,
+    pub weight: u32,
+}
+
+/// Routes sharing a backend protocol other than HTTP, served by their own Ingress.
+#[derive(Serialize, Deserialize)]
+pub struct RouteGroupDataTemplate {
+    pub name: String,
+    pub annotations: BTreeMap<String, String>,
+    pub routes: Vec<RouteDataTemplate>,
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -0,0 +1,298 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use serde::{Deserialize, Serialize};
+use tera::Context as TeraContext;
+
+/// Label set on the ingress controller namespace, strict mode only lets the router reach the services through it.
+pub const INGRESS_CONTROLLER_NAMESPACE_LABEL: &str = "qovery.com/ingress-controller";
+/// Instance metadata server handing out the node credentials, never reachable from the services.
+const METADATA_SERVER_ADDRESS: Ipv4Addr = Ipv4Addr::new(169, 254, 169, 254);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NetworkPolicyMode {
+    /// Services are reachable from the whole cluster on their private port
+    #[default]
+    Default,
+    /// Services are only reachable from the router ingress controller, their own environment and the allowlists
+    Strict,
+}
+
+/// Network isolation of an environment services, applied to applications and container databases.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentNetworkPolicy {
+    pub mode: NetworkPolicyMode,
+    /// Environments whose services can reach this environment in strict mode
+    pub allowed_environment_ids: Vec<String>,
+    /// Sources allowed to reach this environment in strict mode, eq. `10.10.0.0/16`
+    pub allowed_ingress_cidrs: Vec<String>,
+    /// Restricts egress to the environment itself, DNS and `allowed_egress_cidrs`
+    pub restrict_egress: bool,
+    /// Destinations reachable with a restricted egress, managed databases and external APIs have to be listed
+    pub allowed_egress_cidrs: Vec<String>,
+}
+
+impl EnvironmentNetworkPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allowed_environment_ids.iter().any(|id| id.is_empty()) {
+            return Err("allowed environment ids can't be empty".to_string());
+        }
+
+        for cidr in self
+            .allowed_ingress_cidrs
+            .iter()
+            .chain(self.allowed_egress_cidrs.iter())
+        {
+            if !is_valid_cidr(cidr) {
+                return Err(format!("`{}` is not a valid CIDR", cidr));
+            }
+        }
+
+        if self
+            .allowed_egress_cidrs
+            .iter()
+            .any(|cidr| cidr == &format!("{}/32", METADATA_SERVER_ADDRESS))
+        {
+            return Err("the instance metadata server can't be allowed as egress destination".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn insert_into_tera_context(&self, context: &mut TeraContext) {
+        context.insert("network_policy_strict", &(self.mode == NetworkPolicyMode::Strict));
+        context.insert("allowed_environment_ids", &self.allowed_environment_ids);
+        context.insert("allowed_ingress_cidrs", &self.allowed_ingress_cidrs);
+        context.insert("restrict_egress", &self.restrict_egress);
+        context.insert("allowed_egress_cidrs", &self.allowed_egress_cidrs);
+        context.insert(
+            "allowed_egress_ip_blocks",
+            &self
+                .allowed_egress_cidrs
+                .iter()
+                .map(|cidr| IpBlockDataTemplate::without_metadata_server(cidr))
+                .collect::<Vec<_>>(),
+        );
+        context.insert("ingress_controller_namespace_label", INGRESS_CONTROLLER_NAMESPACE_LABEL);
+    }
+}
+
+/// Allowed egress CIDR, the metadata server is carved out of the CIDRs containing it.
+#[derive(Serialize)]
+struct IpBlockDataTemplate {
+    cidr: String,
+    except: Vec<String>,
+}
+
+impl IpBlockDataTemplate {
+    fn without_metadata_server(cidr: &str) -> Self {
+        let contains_metadata_server = match cidr.split_once('/') {
+            Some((address, prefix_length)) => match (address.parse::<Ipv4Addr>(), prefix_length.parse::<u32>()) {
+                (Ok(address), Ok(prefix_length)) if prefix_length < 32 => {
+                    let mask = u32::MAX.checked_shl(32 - prefix_length).unwrap_or(0);
+                    u32::from(address) & mask == u32::from(METADATA_SERVER_ADDRESS) & mask
+                }
+                _ => false,
+            },
+            None => false,
+        };
+
+        IpBlockDataTemplate {
+            cidr: cidr.to_string(),
+            except: if contains_metadata_server {
+                vec![format!("{}/32", METADATA_SERVER_ADDRESS)]
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+fn is_valid_cidr(cidr: &str) -> bool {
+    let (address, prefix_length) = match cidr.split_once('/') {
+        Some(parts) => parts,
+        None => return false,
+    };
+
+    match (address.parse::<IpAddr>(), prefix_length.parse::<u8>()) {
+        (Ok(IpAddr::V4(_)), Ok(prefix_length)) => prefix_length <= 32,
+        (Ok(IpAddr::V6(_)), Ok(prefix_length)) => prefix_length <= 128,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EnvironmentNetworkPolicy, IpBlockDataTemplate, NetworkPolicyMode};
+    use tera::Context as TeraContext;
+
+    #[test]
+    fn test_validate_environment_network_policy() {
+        // setup:
+        struct TestCase<'a> {
+            policy: EnvironmentNetworkPolicy,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let strict_policy = EnvironmentNetworkPolicy {
+            mode: NetworkPolicyMode::Strict,
+            allowed_environment_ids: vec!["staging".to_string()],
+            allowed_ingress_cidrs: vec!["10.10.0.0/16".to_string()],
+            restrict_egress: true,
+            allowed_egress_cidrs: vec!["172.30.0.12/32".to_string(), "2001:db8::/32".to_string()],
+        };
+
+        let test_cases = vec![
+            TestCase {
+                policy: EnvironmentNetworkPolicy::default(),
+                expected_error: None,
+                description: "default policy",
+            },
+            TestCase {
+                policy: strict_policy.clone(),
+                expected_error: None,
+                description: "strict policy with allowlists",
+            },
+            TestCase {
+                policy: EnvironmentNetworkPolicy {
+                    allowed_environment_ids: vec!["".to_string()],
+                    ..strict_policy.clone()
+                },
+                expected_error: Some("allowed environment ids can't be empty"),
+                description: "empty environment id",
+            },
+            TestCase {
+                policy: EnvironmentNetworkPolicy {
+                    allowed_ingress_cidrs: vec!["10.10.0.0".to_string()],
+                    ..strict_policy.clone()
+                },
+                expected_error: Some("`10.10.0.0` is not a valid CIDR"),
+                description: "ingress CIDR without prefix length",
+            },
+            TestCase {
+                policy: EnvironmentNetworkPolicy {
+                    allowed_egress_cidrs: vec!["10.10.0.0/33".to_string()],
+                    ..strict_policy.clone()
+                },
+                expected_error: Some("`10.10.0.0/33` is not a valid CIDR"),
+                description: "egress CIDR with an invalid prefix length",
+            },
+            TestCase {
+                policy: EnvironmentNetworkPolicy {
+                    allowed_egress_cidrs: vec!["169.254.169.254/32".to_string()],
+                    ..strict_policy.clone()
+                },
+                expected_error: Some("the instance metadata server can't be allowed as egress destination"),
+                description: "metadata server as egress CIDR",
+            },
+            TestCase {
+                policy: EnvironmentNetworkPolicy {
+                    allowed_egress_cidrs: vec!["api.example.com/32".to_string()],
+                    ..strict_policy
+                },
+                expected_error: Some("`api.example.com/32` is not a valid CIDR"),
+                description: "hostname instead of an address",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = tc.policy.validate();
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => assert_eq!(Err(expected_error.to_string()), result, "{}", tc.description),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ip_block_without_metadata_server() {
+        // setup:
+        let test_cases = vec![
+            ("0.0.0.0/0", true),
+            ("169.254.0.0/16", true),
+            ("169.254.169.0/24", true),
+            ("10.0.0.0/8", false),
+            ("172.30.0.12/32", false),
+            ("2001:db8::/32", false),
+        ];
+
+        for (cidr, expected_except) in test_cases {
+            // execute:
+            let ip_block = IpBlockDataTemplate::without_metadata_server(cidr);
+
+            // verify:
+            assert_eq!(cidr, ip_block.cidr);
+            assert_eq!(
+                if expected_except {
+                    vec!["169.254.169.254/32".to_string()]
+                } else {
+                    vec![]
+                },
+                ip_block.except,
+                "cidr: {}",
+                cidr
+            );
+        }
+    }
+
+    #[test]
+    fn test_database_network_policies_rendering() {
+        // setup:
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "postgresql-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("publicly_accessible", &true);
+        context.insert("database_port", &5432);
+        EnvironmentNetworkPolicy {
+            mode: NetworkPolicyMode::Strict,
+            allowed_environment_ids: vec![],
+            allowed_ingress_cidrs: vec!["10.10.0.0/16".to_string()],
+            restrict_egress: false,
+            allowed_egress_cidrs: vec![],
+        }
+        .insert_into_tera_context(&mut context);
+
+        for database in ["mongodb", "mysql", "postgresql", "redis"] {
+            let template = std::fs::read_to_string(format!(
+                "{}/lib/common/services/{}/templates/qovery-networkpolicies.j2.yaml",
+                env!("CARGO_MANIFEST_DIR"),
+                database
+            ))
+            .unwrap();
+
+            // execute:
+            let rendered = tera::Tera::one_off(template.as_str(), &context, false).unwrap();
+            let documents = rendered
+                .split("\n---\n")
+                .filter_map(|document| serde_yaml::from_str::<serde_yaml::Value>(document).ok())
+                .filter(|document| !document.is_null())
+                .collect::<Vec<_>>();
+
+            // verify:
+            assert_eq!(1, documents.len(), "{}", database);
+            assert_eq!(
+                "abcdef",
+                documents[0]["spec"]["podSelector"]["matchLabels"]["databaseId"]
+                    .as_str()
+                    .unwrap(),
+                "{}",
+                database
+            );
+            let ingress = documents[0]["spec"]["ingress"].as_sequence().unwrap();
+            assert_eq!(3, ingress.len(), "{}", database);
+            assert_eq!(
+                "10.10.0.0/16",
+                ingress[1]["from"][0]["ipBlock"]["cidr"].as_str().unwrap(),
+                "{}",
+                database
+            );
+            assert_eq!(5432, ingress[2]["ports"][0]["port"].as_u64().unwrap(), "{}", database);
+        }
+    }
+}
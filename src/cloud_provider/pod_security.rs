@@ -0,0 +1,286 @@
+use serde::{Deserialize, Serialize};
+
+use crate::cloud_provider::models::{
+    CapabilitiesDataTemplate, ContainerSecurityContextDataTemplate, PodSecurityContextDataTemplate,
+    SeccompProfileDataTemplate,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SeccompProfile {
+    /// Container runtime default profile, blocking dangerous syscalls
+    RuntimeDefault,
+    Unconfined,
+}
+
+impl SeccompProfile {
+    pub fn to_k8s_format(&self) -> &str {
+        match self {
+            SeccompProfile::RuntimeDefault => "RuntimeDefault",
+            SeccompProfile::Unconfined => "Unconfined",
+        }
+    }
+}
+
+/// Hardening of an application or job containers, the image defaults apply when nothing is set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PodSecuritySettings {
+    pub run_as_non_root: bool,
+    /// UID running the containers processes, required to run as non root
+    pub run_as_user: Option<u32>,
+    /// GID running the containers processes, it also owns the mounted volumes
+    pub run_as_group: Option<u32>,
+    pub read_only_root_filesystem: bool,
+    /// Writable empty directories mounted in the containers, eq. `/tmp` with a read only root filesystem
+    pub writable_paths: Vec<String>,
+    /// Linux capabilities removed from the containers, eq. `["ALL"]`
+    pub drop_capabilities: Vec<String>,
+    pub seccomp_profile: Option<SeccompProfile>,
+    pub disable_privilege_escalation: bool,
+}
+
+impl PodSecuritySettings {
+    pub fn validate(&self) -> Result<(), String> {
+        // the kubelet refuses to start an image running as root without an explicit UID, check it now rather than
+        // letting the deployment time out
+        if self.run_as_non_root && self.run_as_user.unwrap_or_default() == 0 {
+            return Err("running as non root requires a non root user id".to_string());
+        }
+
+        for (idx, path) in self.writable_paths.iter().enumerate() {
+            if !path.starts_with('/') || path == "/" {
+                return Err(format!("writable path `{}` must be an absolute directory path", path));
+            }
+            if self.writable_paths[..idx].contains(path) {
+                return Err(format!("writable path `{}` is set more than once", path));
+            }
+        }
+
+        if self.drop_capabilities.iter().any(|capability| capability.is_empty()) {
+            return Err("dropped capabilities can't be empty".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn pod_security_context(&self) -> PodSecurityContextDataTemplate {
+        PodSecurityContextDataTemplate {
+            run_as_non_root: self.run_as_non_root.then_some(true),
+            run_as_user: self.run_as_user,
+            run_as_group: self.run_as_group,
+            fs_group: self.run_as_group,
+            seccomp_profile: self.seccomp_profile.map(|profile| SeccompProfileDataTemplate {
+                profile_type: profile.to_k8s_format().to_string(),
+            }),
+        }
+    }
+
+    pub fn container_security_context(&self) -> ContainerSecurityContextDataTemplate {
+        ContainerSecurityContextDataTemplate {
+            read_only_root_filesystem: self.read_only_root_filesystem.then_some(true),
+            allow_privilege_escalation: self.disable_privilege_escalation.then_some(false),
+            capabilities: match self.drop_capabilities.is_empty() {
+                true => None,
+                false => Some(CapabilitiesDataTemplate {
+                    drop: self.drop_capabilities.clone(),
+                }),
+            },
+        }
+    }
+}
+
+/// Cluster wide requirements on applications containers, checked before each deployment.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PodSecurityPolicy {
+    pub require_run_as_non_root: bool,
+    pub require_read_only_root_filesystem: bool,
+    /// Capabilities every container has to drop, dropping `ALL` satisfies any of them
+    pub required_drop_capabilities: Vec<String>,
+    /// Requires the `RuntimeDefault` seccomp profile
+    pub require_seccomp_profile: bool,
+    pub require_no_privilege_escalation: bool,
+}
+
+impl PodSecurityPolicy {
+    /// Reasons the settings don't comply with the policy, empty when they do.
+    pub fn violations(&self, settings: &PodSecuritySettings) -> Vec<String> {
+        let mut violations = vec![];
+
+        if self.require_run_as_non_root && !settings.run_as_non_root {
+            violations.push("containers must run as non root".to_string());
+        }
+        if self.require_read_only_root_filesystem && !settings.read_only_root_filesystem {
+            violations.push("containers must have a read only root filesystem".to_string());
+        }
+
+        let drops_all = settings
+            .drop_capabilities
+            .iter()
+            .any(|capability| capability.eq_ignore_ascii_case("ALL"));
+        for capability in &self.required_drop_capabilities {
+            if !drops_all
+                && !settings
+                    .drop_capabilities
+                    .iter()
+                    .any(|dropped| dropped.eq_ignore_ascii_case(capability))
+            {
+                violations.push(format!("containers must drop the {} capability", capability));
+            }
+        }
+
+        if self.require_seccomp_profile && settings.seccomp_profile != Some(SeccompProfile::RuntimeDefault) {
+            violations.push("containers must use the RuntimeDefault seccomp profile".to_string());
+        }
+        if self.require_no_privilege_escalation && !settings.disable_privilege_escalation {
+            violations.push("containers must disable privilege escalation".to_string());
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PodSecurityPolicy, PodSecuritySettings, SeccompProfile};
+
+    fn hardened_settings() -> PodSecuritySettings {
+        PodSecuritySettings {
+            run_as_non_root: true,
+            run_as_user: Some(1000),
+            run_as_group: Some(1000),
+            read_only_root_filesystem: true,
+            writable_paths: vec!["/tmp".to_string()],
+            drop_capabilities: vec!["ALL".to_string()],
+            seccomp_profile: Some(SeccompProfile::RuntimeDefault),
+            disable_privilege_escalation: true,
+        }
+    }
+
+    #[test]
+    fn test_validate_pod_security_settings() {
+        // setup:
+        struct TestCase<'a> {
+            settings: PodSecuritySettings,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                settings: PodSecuritySettings::default(),
+                expected_error: None,
+                description: "image defaults",
+            },
+            TestCase {
+                settings: hardened_settings(),
+                expected_error: None,
+                description: "hardened settings",
+            },
+            TestCase {
+                settings: PodSecuritySettings {
+                    run_as_user: None,
+                    ..hardened_settings()
+                },
+                expected_error: Some("running as non root requires a non root user id"),
+                description: "non root without user id",
+            },
+            TestCase {
+                settings: PodSecuritySettings {
+                    run_as_user: Some(0),
+                    ..hardened_settings()
+                },
+                expected_error: Some("running as non root requires a non root user id"),
+                description: "non root with root user id",
+            },
+            TestCase {
+                settings: PodSecuritySettings {
+                    writable_paths: vec!["tmp".to_string()],
+                    ..hardened_settings()
+                },
+                expected_error: Some("writable path `tmp` must be an absolute directory path"),
+                description: "relative writable path",
+            },
+            TestCase {
+                settings: PodSecuritySettings {
+                    writable_paths: vec!["/tmp".to_string(), "/tmp".to_string()],
+                    ..hardened_settings()
+                },
+                expected_error: Some("writable path `/tmp` is set more than once"),
+                description: "duplicated writable path",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = tc.settings.validate();
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => assert_eq!(Err(expected_error.to_string()), result, "{}", tc.description),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pod_security_policy_violations() {
+        // setup:
+        let policy = PodSecurityPolicy {
+            require_run_as_non_root: true,
+            require_read_only_root_filesystem: false,
+            required_drop_capabilities: vec!["NET_RAW".to_string()],
+            require_seccomp_profile: true,
+            require_no_privilege_escalation: true,
+        };
+
+        // execute & verify:
+        assert!(policy.violations(&hardened_settings()).is_empty());
+        assert!(policy
+            .violations(&PodSecuritySettings {
+                drop_capabilities: vec!["net_raw".to_string()],
+                ..hardened_settings()
+            })
+            .is_empty());
+        assert_eq!(
+            vec![
+                "containers must run as non root".to_string(),
+                "containers must drop the NET_RAW capability".to_string(),
+                "containers must use the RuntimeDefault seccomp profile".to_string(),
+                "containers must disable privilege escalation".to_string(),
+            ],
+            policy.violations(&PodSecuritySettings::default())
+        );
+    }
+
+    #[test]
+    fn test_security_contexts() {
+        // execute:
+        let pod_security_context = serde_json::to_value(hardened_settings().pod_security_context()).unwrap();
+        let container_security_context =
+            serde_json::to_value(hardened_settings().container_security_context()).unwrap();
+        let default_container_security_context =
+            serde_json::to_value(PodSecuritySettings::default().container_security_context()).unwrap();
+
+        // verify:
+        assert_eq!(
+            serde_json::json!({
+                "runAsNonRoot": true,
+                "runAsUser": 1000,
+                "runAsGroup": 1000,
+                "fsGroup": 1000,
+                "seccompProfile": {"type": "RuntimeDefault"}
+            }),
+            pod_security_context
+        );
+        assert_eq!(
+            serde_json::json!({
+                "readOnlyRootFilesystem": true,
+                "allowPrivilegeEscalation": false,
+                "capabilities": {"drop": ["ALL"]}
+            }),
+            container_security_context
+        );
+        assert_eq!(serde_json::json!({}), default_container_security_context);
+    }
+}
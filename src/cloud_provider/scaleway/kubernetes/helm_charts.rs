@@ -1,10 +1,13 @@
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::helm::{
-    get_chart_for_cluster_agent, get_chart_for_shell_agent, get_engine_helm_action_from_location, ChartInfo,
-    ChartSetValue, ChartValuesGenerated, ClusterAgentContext, CommonChart, CoreDNSConfigChart, HelmChart,
-    HelmChartNamespaces, PrometheusOperatorConfigChart, ShellAgentContext,
+    get_cert_manager_dns_provider_values, get_chart_for_cluster_agent, get_chart_for_shell_agent,
+    get_engine_helm_action_from_location, ChartInfo, ChartSetValue, ChartValuesGenerated, ClusterAgentContext,
+    CommonChart, CoreDNSConfigChart, HelmChart, HelmChartNamespaces, NginxIngressChart, PrometheusOperatorConfigChart,
+    ShellAgentContext,
 };
 use crate::cloud_provider::qovery::{get_qovery_app_version, EngineLocation, QoveryAgent, QoveryAppName, QoveryEngine};
 use crate::cloud_provider::scaleway::kubernetes::KapsuleOptions;
+use crate::dns_provider::DnsProviderConfiguration;
 use crate::errors::CommandError;
 use crate::models::scaleway::{ScwRegion, ScwZone};
 use semver::Version;
@@ -38,10 +41,8 @@ pub struct ChartsConfigPrerequisites {
     pub managed_dns_helm_format: String,
     pub managed_dns_resolvers_terraform_format: String,
     pub external_dns_provider: String,
-    pub dns_email_report: String,
-    pub acme_url: String,
-    pub cloudflare_email: String,
-    pub cloudflare_api_token: String,
+    pub acme_settings: AcmeSettings,
+    pub dns_provider_configuration: DnsProviderConfiguration,
     pub disable_pleco: bool,
     // qovery options form json input
     pub infra_options: KapsuleOptions,
@@ -67,10 +68,8 @@ impl ChartsConfigPrerequisites {
         managed_dns_helm_format: String,
         managed_dns_resolvers_terraform_format: String,
         external_dns_provider: String,
-        dns_email_report: String,
-        acme_url: String,
-        cloudflare_email: String,
-        cloudflare_api_token: String,
+        acme_settings: AcmeSettings,
+        dns_provider_configuration: DnsProviderConfiguration,
         disable_pleco: bool,
         infra_options: KapsuleOptions,
     ) -> Self {
@@ -94,10 +93,8 @@ impl ChartsConfigPrerequisites {
             managed_dns_helm_format,
             managed_dns_resolvers_terraform_format,
             external_dns_provider,
-            dns_email_report,
-            acme_url,
-            cloudflare_email,
-            cloudflare_api_token,
+            acme_settings,
+            dns_provider_configuration,
             disable_pleco,
             infra_options,
         }
@@ -566,14 +563,6 @@ datasources:
                     key: "externalDnsProvider".to_string(),
                     value: chart_config_prerequisites.external_dns_provider.clone(),
                 },
-                ChartSetValue {
-                    key: "acme.letsEncrypt.emailReport".to_string(),
-                    value: chart_config_prerequisites.dns_email_report.clone(),
-                },
-                ChartSetValue {
-                    key: "acme.letsEncrypt.acmeUrl".to_string(),
-                    value: chart_config_prerequisites.acme_url.clone(),
-                },
                 ChartSetValue {
                     key: "managedDns".to_string(),
                     value: chart_config_prerequisites.managed_dns_helm_format.clone(),
@@ -582,18 +571,18 @@ datasources:
             ..Default::default()
         },
     };
-    if chart_config_prerequisites.external_dns_provider == "cloudflare" {
-        cert_manager_config.chart_info.values.push(ChartSetValue {
-            key: "provider.cloudflare.apiToken".to_string(),
-            value: chart_config_prerequisites.cloudflare_api_token.clone(),
-        });
-        cert_manager_config.chart_info.values.push(ChartSetValue {
-            key: "provider.cloudflare.email".to_string(),
-            value: chart_config_prerequisites.cloudflare_email.clone(),
-        })
-    }
-
-    let nginx_ingress = CommonChart {
+    cert_manager_config
+        .chart_info
+        .values
+        .extend(get_cert_manager_dns_provider_values(
+            &chart_config_prerequisites.dns_provider_configuration,
+        ));
+    cert_manager_config
+        .chart_info
+        .values
+        .extend(chart_config_prerequisites.acme_settings.cert_manager_configs_values());
+
+    let nginx_ingress = NginxIngressChart {
         chart_info: ChartInfo {
             name: "nginx-ingress".to_string(),
             path: chart_path("common/charts/ingress-nginx"),
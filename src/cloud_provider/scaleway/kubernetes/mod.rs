@@ -1,19 +1,23 @@
 mod helm_charts;
 pub mod node;
 
+use crate::cloud_provider::acme::AcmeSettings;
 use crate::cloud_provider::aws::regions::AwsZones;
 use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::helm::{deploy_charts_levels, ChartInfo};
 use crate::cloud_provider::kubernetes::{
-    is_kubernetes_upgrade_required, send_progress_on_long_task, uninstall_cert_manager, Kind, Kubernetes,
-    KubernetesUpgradeStatus, ProviderOptions,
+    check_ingress_controller_supports_kubernetes_version, custom_config_file_store, is_kubernetes_upgrade_required,
+    put_kubeconfig_into_config_file_store, send_progress_on_long_task, uninstall_cert_manager, Kind, Kubernetes,
+    KubernetesApiCapabilities, KubernetesUpgradeStatus, ProviderOptions, VOLUME_SNAPSHOT_CLASS,
 };
 use crate::cloud_provider::models::{NodeGroups, NodeGroupsFormat};
+use crate::cloud_provider::pod_security::PodSecurityPolicy;
 use crate::cloud_provider::qovery::EngineLocation;
 use crate::cloud_provider::scaleway::kubernetes::helm_charts::{scw_helm_charts, ChartsConfigPrerequisites};
 use crate::cloud_provider::scaleway::kubernetes::node::{ScwInstancesType, ScwNodeGroup};
 use crate::cloud_provider::utilities::print_action;
 use crate::cloud_provider::{kubernetes, CloudProvider};
+use crate::cmd;
 use crate::cmd::helm::{to_engine_error, Helm};
 use crate::cmd::kubectl::{kubectl_exec_api_custom_metrics, kubectl_exec_get_all_namespaces, kubectl_exec_get_events};
 use crate::cmd::terraform::{terraform_exec, terraform_init_validate_plan_apply, terraform_init_validate_state_list};
@@ -27,11 +31,11 @@ use crate::io_models::{
 };
 use crate::logger::Logger;
 use crate::models::scaleway::ScwZone;
+use crate::object_storage::s3_compatible::{S3Compatible, S3CompatibleSettings};
 use crate::object_storage::scaleway_object_storage::{BucketDeleteStrategy, ScalewayOS};
 use crate::object_storage::ObjectStorage;
 use crate::runtime::block_on;
 use crate::string::terraform_list_format;
-use crate::{cmd, dns_provider};
 use ::function_name::named;
 use reqwest::StatusCode;
 use retry::delay::{Fibonacci, Fixed};
@@ -80,6 +84,12 @@ pub struct KapsuleOptions {
 
     // Other
     pub tls_email_report: String,
+    #[serde(default)]
+    pub acme: Option<AcmeSettings>,
+    #[serde(default)]
+    pub pod_security_policy: PodSecurityPolicy,
+    #[serde(default)]
+    pub config_file_store: Option<S3CompatibleSettings>,
 }
 
 impl ProviderOptions for KapsuleOptions {}
@@ -120,6 +130,9 @@ impl KapsuleOptions {
             scaleway_access_key,
             scaleway_secret_key,
             tls_email_report,
+            acme: None,
+            pod_security_policy: PodSecurityPolicy::default(),
+            config_file_store: None,
         }
     }
 }
@@ -134,6 +147,7 @@ pub struct Kapsule {
     cloud_provider: Arc<Box<dyn CloudProvider>>,
     dns_provider: Arc<Box<dyn DnsProvider>>,
     object_storage: ScalewayOS,
+    config_file_store: Option<S3Compatible>,
     nodes_groups: Vec<NodeGroups>,
     template_directory: String,
     options: KapsuleOptions,
@@ -191,6 +205,8 @@ impl Kapsule {
             context.resource_expiration_in_seconds(),
         );
 
+        let config_file_store = custom_config_file_store(&context, options.config_file_store.as_ref());
+
         let listeners = cloud_provider.listeners().clone();
         Ok(Kapsule {
             context,
@@ -202,6 +218,7 @@ impl Kapsule {
             cloud_provider,
             dns_provider,
             object_storage,
+            config_file_store,
             nodes_groups,
             template_directory,
             options,
@@ -457,13 +474,7 @@ impl Kapsule {
             &managed_dns_resolvers_terraform_format,
         );
         context.insert("wildcard_managed_dns", &self.dns_provider().domain().wildcarded().to_string());
-        match self.dns_provider.kind() {
-            dns_provider::Kind::Cloudflare => {
-                context.insert("external_dns_provider", self.dns_provider.provider_name());
-                context.insert("cloudflare_api_token", self.dns_provider.token());
-                context.insert("cloudflare_email", self.dns_provider.account());
-            }
-        };
+        self.dns_provider.configuration().insert_into_tera_context(&mut context);
 
         context.insert("dns_email_report", &self.options.tls_email_report);
 
@@ -519,7 +530,7 @@ impl Kapsule {
         context.insert("aws_terraform_backend_bucket", "qovery-terrafom-tfstates");
 
         // TLS
-        context.insert("acme_server_url", &self.lets_encrypt_url());
+        context.insert("acme_server_url", &self.acme_settings().directory_url);
 
         // Vault
         context.insert("vault_auth_method", "none");
@@ -572,14 +583,6 @@ impl Kapsule {
         terraform_list_format(managed_dns_resolvers)
     }
 
-    fn lets_encrypt_url(&self) -> String {
-        match &self.context.is_test_cluster() {
-            true => "https://acme-staging-v02.api.letsencrypt.org/directory",
-            false => "https://acme-v02.api.letsencrypt.org/directory",
-        }
-        .to_string()
-    }
-
     fn create(&self) -> Result<(), EngineError> {
         let listeners_helper = ListenersHelper::new(&self.listeners);
         let event_details = self.get_event_details(Stage::Infrastructure(InfrastructureStep::Create));
@@ -594,6 +597,12 @@ impl Kapsule {
             EventMessage::new_from_safe("Preparing SCW cluster deployment.".to_string()),
         ));
 
+        check_ingress_controller_supports_kubernetes_version(
+            self.context().lib_root_dir(),
+            self.version(),
+            event_details.clone(),
+        )?;
+
         // upgrade cluster instead if required
         match self.get_kubeconfig_file() {
             Ok((path, _)) => match is_kubernetes_upgrade_required(
@@ -745,6 +754,13 @@ impl Kapsule {
             self.logger().log(EngineEvent::Error(error.clone(), None));
             return Err(error);
         }
+        if self.config_file_store.is_some() {
+            put_kubeconfig_into_config_file_store(
+                self,
+                kubeconfig_path.to_str().expect("No path for Kubeconfig"),
+                event_details.clone(),
+            )?;
+        }
 
         let cluster_info = self.get_scw_cluster_info()?;
         if cluster_info.is_none() {
@@ -958,10 +974,8 @@ impl Kapsule {
             self.dns_provider.domain().to_helm_format_string(),
             self.managed_dns_resolvers_terraform_format(),
             self.dns_provider.provider_name().to_string(),
-            self.options.tls_email_report.clone(),
-            self.lets_encrypt_url(),
-            self.dns_provider.account().to_string(),
-            self.dns_provider.token().to_string(),
+            self.acme_settings(),
+            self.dns_provider.configuration(),
             self.context.disable_pleco(),
             self.options.clone(),
         );
@@ -1547,6 +1561,19 @@ impl Kubernetes for Kapsule {
         None
     }
 
+    fn node_groups(&self) -> &[NodeGroups] {
+        &self.nodes_groups
+    }
+
+    fn volume_snapshot_class(&self, api_capabilities: &KubernetesApiCapabilities) -> Option<&str> {
+        // the snapshot class is only installed when the cluster serves the v1 snapshot api
+        if api_capabilities.volume_snapshot_api {
+            Some(VOLUME_SNAPSHOT_CLASS)
+        } else {
+            None
+        }
+    }
+
     fn cloud_provider(&self) -> &dyn CloudProvider {
         self.cloud_provider.as_ref().borrow()
     }
@@ -1555,12 +1582,23 @@ impl Kubernetes for Kapsule {
         self.dns_provider.as_ref().borrow()
     }
 
+    fn acme_settings(&self) -> AcmeSettings {
+        AcmeSettings::from_options(&self.options.acme, &self.context, &self.options.tls_email_report)
+    }
+
+    fn pod_security_policy(&self) -> PodSecurityPolicy {
+        self.options.pod_security_policy.clone()
+    }
+
     fn logger(&self) -> &dyn Logger {
         self.logger.borrow()
     }
 
     fn config_file_store(&self) -> &dyn ObjectStorage {
-        &self.object_storage
+        match &self.config_file_store {
+            Some(config_file_store) => config_file_store,
+            None => &self.object_storage,
+        }
     }
 
     fn is_valid(&self) -> Result<(), EngineError> {
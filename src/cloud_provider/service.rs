@@ -1,4 +1,4 @@
-use std::net::TcpStream;
+use std::net::{SocketAddr, TcpStream};
 use std::str::FromStr;
 use std::sync::mpsc;
 use std::sync::mpsc::TryRecvError;
@@ -18,12 +18,12 @@ use crate::cmd::helm;
 use crate::cmd::kubectl::ScalingKind::Statefulset;
 use crate::cmd::kubectl::{kubectl_exec_delete_secret, kubectl_exec_scale_replicas_by_selector, ScalingKind};
 use crate::cmd::structs::LabelsContent;
-use crate::errors::{CommandError, EngineError};
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
 use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage, ToTransmitter};
 use crate::io_models::ProgressLevel::Info;
 use crate::io_models::{
     Context, DatabaseMode, Listen, Listeners, ListenersHelper, ProgressInfo, ProgressLevel, ProgressScope,
-    QoveryIdentifier,
+    QoveryIdentifier, Scheduling,
 };
 use crate::logger::Logger;
 use crate::models::types::VersionsNumber;
@@ -46,6 +46,7 @@ pub trait Service: ToTransmitter {
             ServiceType::Application => "applications",
             ServiceType::Database(_) => "databases",
             ServiceType::Router => "routers",
+            ServiceType::Job => "jobs",
         };
 
         crate::fs::workspace_directory(
@@ -113,6 +114,7 @@ pub trait Service: ToTransmitter {
             ServiceType::Application => ProgressScope::Application { id },
             ServiceType::Database(_) => ProgressScope::Database { id },
             ServiceType::Router => ProgressScope::Router { id },
+            ServiceType::Job => ProgressScope::Job { id },
         }
     }
 }
@@ -128,9 +130,9 @@ pub trait StatelessService: Service + Create + Pause + Delete {
         }
     }
 
-    fn exec_check_action(&self) -> Result<(), EngineError> {
+    fn exec_check_action(&self, deployment_target: &DeploymentTarget) -> Result<(), EngineError> {
         match self.action() {
-            crate::cloud_provider::service::Action::Create => self.on_create_check(),
+            crate::cloud_provider::service::Action::Create => self.on_create_check(deployment_target),
             crate::cloud_provider::service::Action::Delete => self.on_delete_check(),
             crate::cloud_provider::service::Action::Pause => self.on_pause_check(),
             crate::cloud_provider::service::Action::Nothing => Ok(()),
@@ -149,9 +151,9 @@ pub trait StatefulService: Service + Create + Pause + Delete {
         }
     }
 
-    fn exec_check_action(&self) -> Result<(), EngineError> {
+    fn exec_check_action(&self, deployment_target: &DeploymentTarget) -> Result<(), EngineError> {
         match self.action() {
-            crate::cloud_provider::service::Action::Create => self.on_create_check(),
+            crate::cloud_provider::service::Action::Create => self.on_create_check(deployment_target),
             crate::cloud_provider::service::Action::Delete => self.on_delete_check(),
             crate::cloud_provider::service::Action::Pause => self.on_pause_check(),
             crate::cloud_provider::service::Action::Nothing => Ok(()),
@@ -164,10 +166,16 @@ pub trait StatefulService: Service + Create + Pause + Delete {
 pub trait RouterService: StatelessService + Listen + Helm {
     fn domains(&self) -> Vec<&str>;
     fn has_custom_domains(&self) -> bool;
-    fn check_domains(&self, event_details: EventDetails, logger: &dyn Logger) -> Result<(), EngineError> {
+    fn check_domains(
+        &self,
+        nameservers: &[SocketAddr],
+        event_details: EventDetails,
+        logger: &dyn Logger,
+    ) -> Result<(), EngineError> {
         check_domain_for(
             ListenersHelper::new(self.listeners()),
             self.domains(),
+            nameservers,
             self.id(),
             self.context().execution_id(),
             event_details,
@@ -182,6 +190,7 @@ pub trait DatabaseService: StatefulService {
         &self,
         listeners: Listeners,
         domains: Vec<&str>,
+        nameservers: &[SocketAddr],
         event_details: EventDetails,
         logger: &dyn Logger,
     ) -> Result<(), EngineError> {
@@ -189,6 +198,7 @@ pub trait DatabaseService: StatefulService {
             check_domain_for(
                 ListenersHelper::new(&listeners),
                 domains,
+                nameservers,
                 self.id(),
                 self.context().execution_id(),
                 event_details,
@@ -201,7 +211,7 @@ pub trait DatabaseService: StatefulService {
 
 pub trait Create {
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError>;
-    fn on_create_check(&self) -> Result<(), EngineError>;
+    fn on_create_check(&self, target: &DeploymentTarget) -> Result<(), EngineError>;
     fn on_create_error(&self, target: &DeploymentTarget) -> Result<(), EngineError>;
 }
 
@@ -251,6 +261,7 @@ pub struct DatabaseOptions {
     pub activate_high_availability: bool,
     pub activate_backups: bool,
     pub publicly_accessible: bool,
+    pub scheduling: Scheduling,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -277,6 +288,7 @@ pub enum ServiceType {
     Application,
     Database(DatabaseType),
     Router,
+    Job,
 }
 
 impl ServiceType {
@@ -285,6 +297,7 @@ impl ServiceType {
             ServiceType::Application => "Application".to_string(),
             ServiceType::Database(db_type) => format!("{} database", db_type.to_string()),
             ServiceType::Router => "Router".to_string(),
+            ServiceType::Job => "Job".to_string(),
         }
     }
 }
@@ -356,17 +369,198 @@ pub fn default_tera_context(
     context
 }
 
+/// Jobs run to completion around a user service deployment, a failing hook aborts the deployment
+/// and a failing post-deploy hook rolls the service back to its previous version.
+#[derive(Default)]
+pub struct DeploymentHooks<'a> {
+    pub pre_deploy: Option<&'a dyn StatelessService>,
+    pub post_deploy: Option<&'a dyn StatelessService>,
+}
+
 /// deploy a stateless service created by the user (E.g: App or External Service)
 /// the difference with `deploy_service(..)` is that this function provides the thrown error in case of failure
-pub fn deploy_user_stateless_service<T>(target: &DeploymentTarget, service: &T) -> Result<(), EngineError>
+pub fn deploy_user_stateless_service<T>(
+    target: &DeploymentTarget,
+    service: &T,
+    hooks: &DeploymentHooks,
+) -> Result<(), EngineError>
+where
+    T: Service + Helm + Listen,
+{
+    if let Some(pre_deploy) = hooks.pre_deploy {
+        run_deployment_hook(target, service, pre_deploy, "pre-deploy")?;
+    }
+
+    deploy_stateless_service(target, service)?;
+
+    if let Some(post_deploy) = hooks.post_deploy {
+        if let Err(err) = run_deployment_hook(target, service, post_deploy, "post-deploy") {
+            // the new version is already live, put the previous one back
+            rollback_stateless_service_chart(target, service)?;
+            return Err(err);
+        }
+    }
+
+    Ok(())
+}
+
+/// rollback the helm release of a stateless service to its previous revision, a first deployment has none
+fn rollback_stateless_service_chart<T>(target: &DeploymentTarget, service: &T) -> Result<(), EngineError>
 where
     T: Service + Helm,
 {
-    deploy_stateless_service(target, service)
+    let kubernetes = target.kubernetes;
+    let event_details = service.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+    let kubernetes_config_file_path = kubernetes.get_kubeconfig_file_path()?;
+
+    let helm = helm::Helm::new(
+        &kubernetes_config_file_path,
+        &kubernetes.cloud_provider().credentials_environment_variables(),
+    )
+    .map_err(|e| helm::to_engine_error(&event_details, e))?;
+    let chart = ChartInfo::new_from_custom_namespace(
+        service.helm_release_name(),
+        service.workspace_directory(),
+        target.environment.namespace().to_string(),
+        600_i64,
+        vec![],
+        false,
+        service.selector(),
+    );
+
+    match helm.rollback(&chart, &[]) {
+        Ok(_) => {
+            service.logger().log(EngineEvent::Warning(
+                event_details,
+                EventMessage::new_from_safe(format!(
+                    "{} has been rolled back to its previous version",
+                    service.name_with_id()
+                )),
+            ));
+            Ok(())
+        }
+        Err(helm::HelmError::CannotRollback(_)) => {
+            service.logger().log(EngineEvent::Warning(
+                event_details,
+                EventMessage::new_from_safe(format!(
+                    "{} has no previous version to roll back to",
+                    service.name_with_id()
+                )),
+            ));
+            Ok(())
+        }
+        Err(e) => Err(helm::to_engine_error(&event_details, e)),
+    }
+}
+
+/// run a deployment hook to completion and send its logs to the service listeners
+fn run_deployment_hook<T>(
+    target: &DeploymentTarget,
+    service: &T,
+    hook: &dyn StatelessService,
+    hook_name: &str,
+) -> Result<(), EngineError>
+where
+    T: Service + Listen,
+{
+    let kubernetes = target.kubernetes;
+    let event_details = service.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+    let listeners_helper = ListenersHelper::new(service.listeners());
+    let progress_info = |level: ProgressLevel, message: String| {
+        ProgressInfo::new(
+            service.progress_scope(),
+            level,
+            Some(message),
+            kubernetes.context().execution_id(),
+        )
+    };
+
+    let message = format!("Running {} hook of {}", hook_name, service.name_with_id_and_version());
+    listeners_helper.deployment_in_progress(progress_info(Info, message.to_string()));
+    service
+        .logger()
+        .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+
+    // a failed hook job already sent its logs to the listeners
+    if let Err(err) = hook.on_create(target).and_then(|_| hook.on_create_check(target)) {
+        return Err(EngineError::new_deployment_hook_failed(
+            event_details,
+            service.name().to_string(),
+            hook_name.to_string(),
+            err.underlying_error(),
+        ));
+    }
+
+    let logs = kubernetes
+        .get_kubeconfig_file_path()
+        .and_then(|kubernetes_config_file_path| {
+            let selector = hook.selector().unwrap_or_default();
+            crate::cmd::kubectl::kubectl_exec_logs(
+                kubernetes_config_file_path.as_str(),
+                target.environment.namespace(),
+                selector.as_str(),
+                kubernetes.cloud_provider().credentials_environment_variables(),
+            )
+            .map_err(|e| {
+                EngineError::new_k8s_get_logs_error(
+                    event_details.clone(),
+                    selector.to_string(),
+                    target.environment.namespace().to_string(),
+                    e,
+                )
+            })
+        });
+    let logs = match logs {
+        Ok(logs) if !logs.is_empty() => format!("{} hook logs:\n{}", hook_name, logs.join("\n")),
+        Ok(_) => format!("{} hook didn't output any logs", hook_name),
+        Err(err) => {
+            service.logger().log(EngineEvent::Warning(
+                event_details,
+                EventMessage::new(
+                    format!("Cannot retrieve {} hook logs", hook_name),
+                    Some(err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                ),
+            ));
+            format!("{} hook logs are not available", hook_name)
+        }
+    };
+    listeners_helper.deployment_in_progress(progress_info(Info, logs));
+
+    Ok(())
 }
 
 /// deploy a stateless service (app, router, database...) on Kubernetes
 pub fn deploy_stateless_service<T>(target: &DeploymentTarget, service: &T) -> Result<(), EngineError>
+where
+    T: Service + Helm,
+{
+    deploy_stateless_service_chart(target, service)?;
+
+    let kubernetes = target.kubernetes;
+    let environment = target.environment;
+    let kubernetes_config_file_path = kubernetes.get_kubeconfig_file_path()?;
+    let event_details = service.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+
+    crate::cmd::kubectl::kubectl_exec_is_pod_ready_with_retry(
+        kubernetes_config_file_path.as_str(),
+        environment.namespace(),
+        service.selector().unwrap_or_default().as_str(),
+        kubernetes.cloud_provider().credentials_environment_variables(),
+    )
+    .map_err(|e| {
+        EngineError::new_k8s_pod_not_ready(
+            event_details.clone(),
+            service.selector().unwrap_or_default(),
+            environment.namespace().to_string(),
+            e,
+        )
+    })?;
+
+    Ok(())
+}
+
+/// render the service chart and install it with helm, without waiting for its pods to be ready
+pub fn deploy_stateless_service_chart<T>(target: &DeploymentTarget, service: &T) -> Result<(), EngineError>
 where
     T: Service + Helm,
 {
@@ -433,24 +627,7 @@ where
     );
 
     helm.upgrade(&chart, &[])
-        .map_err(|e| helm::to_engine_error(&event_details, e))?;
-
-    crate::cmd::kubectl::kubectl_exec_is_pod_ready_with_retry(
-        kubernetes_config_file_path.as_str(),
-        environment.namespace(),
-        service.selector().unwrap_or_default().as_str(),
-        kubernetes.cloud_provider().credentials_environment_variables(),
-    )
-    .map_err(|e| {
-        EngineError::new_k8s_pod_not_ready(
-            event_details.clone(),
-            service.selector().unwrap_or_default(),
-            environment.namespace().to_string(),
-            e,
-        )
-    })?;
-
-    Ok(())
+        .map_err(|e| helm::to_engine_error(&event_details, e))
 }
 
 /// do specific operations on a stateless service deployment error
@@ -1,8 +1,12 @@
 #![allow(clippy::field_reassign_with_default)]
 
+use crate::cloud_provider::models::CustomDomain;
 use crate::errors::EngineError;
 use crate::events::{EngineEvent, EventDetails, EventMessage};
-use crate::io_models::{Listeners, ListenersHelper, ProgressInfo, ProgressLevel, ProgressScope};
+use crate::io_models::{
+    DomainVerification, DomainVerificationStatus, Listeners, ListenersHelper, ProgressInfo, ProgressLevel,
+    ProgressScope,
+};
 use crate::logger::Logger;
 use chrono::Duration;
 use core::option::Option::{None, Some};
@@ -11,13 +15,16 @@ use core::result::Result::{Err, Ok};
 use retry::delay::Fixed;
 use retry::{Error, OperationResult};
 use std::fmt;
+use std::net::IpAddr;
 use std::net::ToSocketAddrs;
 use std::net::{SocketAddr, TcpStream as NetTcpStream};
 use trust_dns_resolver::config::*;
+use trust_dns_resolver::error::{ResolveError, ResolveErrorKind};
 use trust_dns_resolver::proto::rr::{RData, RecordType};
 use trust_dns_resolver::Resolver;
 
-fn dns_resolvers() -> Vec<Resolver> {
+/// One resolver per nameserver in `nameservers`, or public resolvers when no nameserver is given.
+fn dns_resolvers(nameservers: &[SocketAddr]) -> Vec<Resolver> {
     let mut resolver_options = ResolverOpts::default();
 
     //  We want to avoid cache and using host file of the host, as some provider force caching
@@ -33,6 +40,21 @@ fn dns_resolvers() -> Vec<Resolver> {
     //);
 
     //Resolver::new(resolver, resolver_options).unwrap()
+    if !nameservers.is_empty() {
+        return nameservers
+            .iter()
+            .map(|nameserver| {
+                let resolver_config = ResolverConfig::from_parts(
+                    None,
+                    vec![],
+                    NameServerConfigGroup::from_ips_clear(&[nameserver.ip()], nameserver.port(), true),
+                );
+
+                Resolver::new(resolver_config, resolver_options).expect("Invalid DNS resolver configuration")
+            })
+            .collect();
+    }
+
     vec![
         Resolver::new(ResolverConfig::google(), resolver_options).expect("Invalid google DNS resolver configuration"),
         Resolver::new(ResolverConfig::cloudflare(), resolver_options)
@@ -42,101 +64,240 @@ fn dns_resolvers() -> Vec<Resolver> {
     ]
 }
 
-fn get_cname_record_value(resolver: &Resolver, cname: &str) -> Option<String> {
-    resolver
-        .lookup(cname, RecordType::CNAME)
-        .iter()
-        .flat_map(|lookup| lookup.record_iter())
-        .filter_map(|record| {
-            if let RData::CNAME(cname) = record.rdata() {
-                Some(cname.to_utf8())
-            } else {
-                None
+// a missing record is an answer, other errors (timeout, unreachable nameserver, ...) are not
+fn lookup_records<T>(lookup: Result<Vec<T>, ResolveError>) -> Result<Vec<T>, String> {
+    match lookup {
+        Ok(records) => Ok(records),
+        Err(e) => match e.kind() {
+            ResolveErrorKind::NoRecordsFound { .. } => Ok(vec![]),
+            _ => Err(e.to_string()),
+        },
+    }
+}
+
+fn get_cname_record_value(resolver: &Resolver, cname: &str) -> Result<Option<String>, String> {
+    let cnames = lookup_records(resolver.lookup(cname, RecordType::CNAME).map(|lookup| {
+        lookup
+            .record_iter()
+            .filter_map(|record| {
+                if let RData::CNAME(cname) = record.rdata() {
+                    Some(cname.to_utf8())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }))?;
+
+    Ok(cnames.into_iter().next()) // Can only have one domain behind a CNAME
+}
+
+/// Records of a custom domain, as returned by a single resolver.
+#[derive(Default)]
+struct CustomDomainRecords {
+    txt_values: Vec<String>,
+    cname: Option<String>,
+    // apex domains can't have a CNAME, they point to the target with A or ALIAS (flattened) records
+    ips: Vec<IpAddr>,
+    target_ips: Vec<IpAddr>,
+}
+
+fn get_custom_domain_records(
+    resolver: &Resolver,
+    custom_domain: &CustomDomain,
+    txt_record_name: &str,
+) -> Result<CustomDomainRecords, String> {
+    let txt_values = lookup_records(resolver.txt_lookup(txt_record_name).map(|lookup| {
+        lookup
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data).to_string())
+                    .collect::<String>()
+            })
+            .collect()
+    }))?;
+
+    let cname = get_cname_record_value(resolver, custom_domain.domain.as_str())?;
+
+    if cname.is_some() {
+        return Ok(CustomDomainRecords {
+            txt_values,
+            cname,
+            ..Default::default()
+        });
+    }
+
+    let lookup_ips = |domain: &str| lookup_records(resolver.lookup_ip(domain).map(|lookup| lookup.iter().collect()));
+
+    Ok(CustomDomainRecords {
+        txt_values,
+        cname,
+        ips: lookup_ips(custom_domain.domain.as_str())?,
+        target_ips: lookup_ips(custom_domain.target_domain.as_str())?,
+    })
+}
+
+/// Verification status of a domain, from records returned by a single resolver.
+fn domain_verification_status(
+    records: &CustomDomainRecords,
+    expected_txt_value: &str,
+    expected_cname_target: &str,
+) -> DomainVerificationStatus {
+    if records.txt_values.is_empty() {
+        return DomainVerificationStatus::TxtRecordNotFound;
+    }
+
+    if !records.txt_values.iter().any(|value| value == expected_txt_value) {
+        return DomainVerificationStatus::TxtRecordMismatch {
+            found_values: records.txt_values.to_vec(),
+        };
+    }
+
+    match records.cname.as_deref().map(|cname| cname.trim_end_matches('.')) {
+        // without a CNAME, the domain has to resolve to the target addresses
+        None if !records.ips.is_empty() && records.ips.iter().all(|ip| records.target_ips.contains(ip)) => {
+            DomainVerificationStatus::Verified
+        }
+        None => DomainVerificationStatus::CnameNotFound,
+        Some(cname) if cname != expected_cname_target.trim_end_matches('.') => {
+            DomainVerificationStatus::CnameMismatch {
+                found_target: cname.to_string(),
             }
-        })
-        .next() // Can only have one domain behind a CNAME
+        }
+        Some(_) => DomainVerificationStatus::Verified,
+    }
 }
 
-pub fn check_cname_for(
+/// Verifies the custom domain `_qovery-challenge` TXT record and CNAME, the domain is verified
+/// only when all resolvers agree, to not route traffic based on a single stale answer.
+/// `is_already_routed` domains were routed before the verification existed, they are kept routed whatever the result.
+pub fn verify_custom_domain(
     scope: ProgressScope,
     listeners: &Listeners,
-    cname_to_check: &str,
+    custom_domain: &CustomDomain,
+    is_already_routed: bool,
+    nameservers: &[SocketAddr],
     execution_id: &str,
-) -> Result<String, String> {
-    let resolvers = dns_resolvers();
+) -> DomainVerification {
+    let resolvers = dns_resolvers(nameservers);
     let listener_helper = ListenersHelper::new(listeners);
+    let txt_record_name = custom_domain.verification_record_name();
 
-    let send_deployment_progress = |msg: &str| {
-        listener_helper.deployment_in_progress(ProgressInfo::new(
-            scope.clone(),
-            ProgressLevel::Info,
-            Some(msg.to_string()),
-            execution_id,
-        ));
-    };
-
-    let send_deployment_progress_warn = |msg: &str| {
+    let send_deployment_progress = |level: ProgressLevel, msg: &str| {
         listener_helper.deployment_in_progress(ProgressInfo::new(
             scope.clone(),
-            ProgressLevel::Warn,
+            level,
             Some(msg.to_string()),
             execution_id,
         ));
     };
 
     send_deployment_progress(
+        ProgressLevel::Info,
         format!(
-            "Checking CNAME resolution of '{}'. Please wait, it can take some time...",
-            cname_to_check
+            "Checking ownership of '{}' with TXT record '{}'. Please wait, it can take some time...",
+            custom_domain.domain, txt_record_name
         )
         .as_str(),
     );
 
-    // Trying for 5 min to resolve CNAME
-    let mut ix: usize = 0;
-    let mut next_resolver = || {
-        let resolver = &resolvers[ix % resolvers.len()];
-        ix += 1;
-        resolver
-    };
-    let fixed_iterable = Fixed::from_millis(Duration::seconds(5).num_milliseconds() as u64).take(6 * 5);
+    // Trying for 1 min to verify the domain
+    let fixed_iterable = Fixed::from_millis(Duration::seconds(5).num_milliseconds() as u64).take(12);
     let check_result = retry::retry(fixed_iterable, || {
-        match get_cname_record_value(next_resolver(), cname_to_check) {
-            Some(domain) => OperationResult::Ok(domain),
-            None => {
-                let msg = format!("Cannot find domain under CNAME {}. Retrying in 5 seconds...", cname_to_check);
-                send_deployment_progress(msg.as_str());
-                OperationResult::Retry(msg)
+        let status = resolvers
+            .iter()
+            .map(
+                |resolver| match get_custom_domain_records(resolver, custom_domain, txt_record_name.as_str()) {
+                    Ok(records) => domain_verification_status(
+                        &records,
+                        custom_domain.verification_token.as_str(),
+                        custom_domain.target_domain.as_str(),
+                    ),
+                    Err(raw_error_message) => DomainVerificationStatus::ResolutionFailed { raw_error_message },
+                },
+            )
+            .find(|status| *status != DomainVerificationStatus::Verified)
+            .unwrap_or(DomainVerificationStatus::Verified);
+
+        match status {
+            DomainVerificationStatus::Verified => OperationResult::Ok(status),
+            _ => {
+                send_deployment_progress(
+                    ProgressLevel::Info,
+                    format!(
+                        "Domain {} is not verified yet ({:?}). Retrying in 5 seconds...",
+                        custom_domain.domain, status
+                    )
+                    .as_str(),
+                );
+                OperationResult::Retry(status)
             }
         }
     });
 
-    match check_result {
-        Ok(domain) => {
-            send_deployment_progress(format!("Resolution of CNAME {} found to {}", cname_to_check, domain).as_str());
-        }
-        Err(_) => {
-            let msg = format!(
-                "Resolution of CNAME {} failed. Please check that you have correctly configured your CNAME. If you are using a CDN you can forget this message",
-                cname_to_check
-            );
-            send_deployment_progress_warn(msg.as_str());
-        }
-    }
+    let status = match check_result {
+        Ok(status) => status,
+        Err(Error::Operation { error, .. }) => error,
+        Err(Error::Internal(raw_error_message)) => DomainVerificationStatus::ResolutionFailed { raw_error_message },
+    };
+
+    let verification = DomainVerification {
+        domain: custom_domain.domain.to_string(),
+        txt_record_name,
+        expected_txt_value: custom_domain.verification_token.to_string(),
+        expected_cname_target: custom_domain.target_domain.to_string(),
+        status,
+    };
+
+    let routing = match is_already_routed {
+        true => "it stays routed as it already was, but it will have to be verified once removed",
+        false => "it will not be routed",
+    };
+    let (level, msg) = match &verification.status {
+        DomainVerificationStatus::Verified => (
+            ProgressLevel::Info,
+            format!("Domain {} is verified", verification.domain),
+        ),
+        DomainVerificationStatus::TxtRecordNotFound | DomainVerificationStatus::TxtRecordMismatch { .. } => (
+            ProgressLevel::Warn,
+            format!(
+                "Ownership of domain {} cannot be verified, {}. Please create a TXT record '{}' with value '{}'",
+                verification.domain, routing, verification.txt_record_name, verification.expected_txt_value
+            ),
+        ),
+        DomainVerificationStatus::CnameNotFound | DomainVerificationStatus::CnameMismatch { .. } => (
+            ProgressLevel::Warn,
+            format!(
+                "Domain {} is not verified, {}. Please check that you have correctly configured your CNAME targeting {} (or an ALIAS record for an apex domain)",
+                verification.domain, routing, verification.expected_cname_target
+            ),
+        ),
+        DomainVerificationStatus::ResolutionFailed { raw_error_message } => (
+            ProgressLevel::Warn,
+            format!(
+                "Domain {} records cannot be resolved ({}), {}. Please retry the deployment",
+                verification.domain, raw_error_message, routing
+            ),
+        ),
+    };
 
-    // do not exit / rollback if domain is not ready, simply warn the user about it
-    Ok(cname_to_check.to_string())
+    listener_helper.domain_verification(ProgressInfo::new(scope, level, Some(msg), execution_id), verification.clone());
+
+    verification
 }
 
 pub fn check_domain_for(
     listener_helper: ListenersHelper,
     domains_to_check: Vec<&str>,
+    nameservers: &[SocketAddr],
     execution_id: &str,
     context_id: &str,
     event_details: EventDetails,
     logger: &dyn Logger,
 ) -> Result<(), EngineError> {
-    let resolvers = dns_resolvers();
+    let resolvers = dns_resolvers(nameservers);
 
     for domain in domains_to_check {
         let message = format!(
@@ -337,10 +498,13 @@ pub fn print_action(
 #[cfg(test)]
 mod tests {
     use crate::cloud_provider::utilities::{
-        check_tcp_port_is_open, dns_resolvers, get_cname_record_value, TcpCheckErrors, TcpCheckSource,
+        check_tcp_port_is_open, dns_resolvers, domain_verification_status, get_cname_record_value, CustomDomainRecords,
+        TcpCheckErrors, TcpCheckSource,
     };
     use crate::errors::CommandError;
+    use crate::io_models::DomainVerificationStatus;
     use crate::models::types::VersionsNumber;
+    use std::net::IpAddr;
     use std::str::FromStr;
 
     #[test]
@@ -363,10 +527,82 @@ mod tests {
 
     #[test]
     pub fn test_cname_resolution() {
-        let resolvers = dns_resolvers();
+        let resolvers = dns_resolvers(&[]);
         let cname = get_cname_record_value(&resolvers[0], "ci-test-no-delete.qovery.io");
 
-        assert_eq!(cname, Some(String::from("qovery.io.")));
+        assert_eq!(cname, Ok(Some(String::from("qovery.io."))));
+    }
+
+    #[test]
+    pub fn test_domain_verification_status() {
+        // setup:
+        struct TestCase<'a> {
+            records: CustomDomainRecords,
+            expected_output: DomainVerificationStatus,
+            description: &'a str,
+        }
+
+        let token = "qovery-verification=token";
+        let target = "app.qovery.io";
+        let records = |txt_values: Vec<&str>, cname: Option<&str>| CustomDomainRecords {
+            txt_values: txt_values.into_iter().map(|value| value.to_string()).collect(),
+            cname: cname.map(|cname| cname.to_string()),
+            ..Default::default()
+        };
+        let apex_records = |ips: Vec<[u8; 4]>| CustomDomainRecords {
+            ips: ips.into_iter().map(IpAddr::from).collect(),
+            target_ips: vec![IpAddr::from([1, 2, 3, 4]), IpAddr::from([1, 2, 3, 5])],
+            ..records(vec![token], None)
+        };
+        let test_cases = vec![
+            TestCase {
+                records: records(vec![], Some("app.qovery.io.")),
+                expected_output: DomainVerificationStatus::TxtRecordNotFound,
+                description: "missing TXT record",
+            },
+            TestCase {
+                records: records(vec!["other-token"], Some("app.qovery.io.")),
+                expected_output: DomainVerificationStatus::TxtRecordMismatch {
+                    found_values: vec!["other-token".to_string()],
+                },
+                description: "TXT record with another token",
+            },
+            TestCase {
+                records: records(vec![token], None),
+                expected_output: DomainVerificationStatus::CnameNotFound,
+                description: "missing CNAME",
+            },
+            TestCase {
+                records: records(vec![token], Some("cdn.example.com.")),
+                expected_output: DomainVerificationStatus::CnameMismatch {
+                    found_target: "cdn.example.com".to_string(),
+                },
+                description: "CNAME targeting another domain",
+            },
+            TestCase {
+                records: records(vec!["v=spf1 -all", token], Some("app.qovery.io.")),
+                expected_output: DomainVerificationStatus::Verified,
+                description: "verified domain having several TXT records",
+            },
+            TestCase {
+                records: apex_records(vec![[1, 2, 3, 5], [1, 2, 3, 4]]),
+                expected_output: DomainVerificationStatus::Verified,
+                description: "apex domain with an ALIAS record to the target",
+            },
+            TestCase {
+                records: apex_records(vec![[1, 2, 3, 4], [5, 6, 7, 8]]),
+                expected_output: DomainVerificationStatus::CnameNotFound,
+                description: "apex domain resolving to another address",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = domain_verification_status(&tc.records, token, target);
+
+            // verify:
+            assert_eq!(tc.expected_output, result, "case: {}", tc.description);
+        }
     }
 
     #[test]
@@ -3,7 +3,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use retry::delay::Fibonacci;
+use retry::delay::{Fibonacci, Fixed};
 use retry::OperationResult;
 use serde::de::DeserializeOwned;
 
@@ -11,12 +11,12 @@ use crate::cloud_provider::digitalocean::models::svc::DoLoadBalancer;
 use crate::cloud_provider::metrics::KubernetesApiMetrics;
 use crate::cmd::command::QoveryCommand;
 use crate::cmd::structs::{
-    Configmap, Daemonset, Item, KubernetesEvent, KubernetesJob, KubernetesKind, KubernetesList, KubernetesNode,
-    KubernetesPod, KubernetesPodStatusPhase, KubernetesPodStatusReason, KubernetesService, KubernetesVersion,
-    LabelsContent, Namespace, Secrets, HPA, PDB, PVC, SVC,
+    Configmap, Daemonset, Ingress, Item, KubernetesEvent, KubernetesJob, KubernetesKind, KubernetesList,
+    KubernetesNode, KubernetesPod, KubernetesPodStatusPhase, KubernetesPodStatusReason, KubernetesService,
+    KubernetesVersion, LabelsContent, Namespace, Secrets, StorageClass, VolumeSnapshot, VolumeSnapshotClass, HPA, PDB,
+    PVC, SVC,
 };
 use crate::constants::KUBECONFIG;
-use crate::error::{SimpleError, SimpleErrorKind};
 use crate::errors::{CommandError, ErrorMessageVerbosity};
 
 pub enum ScalingKind {
@@ -186,11 +186,44 @@ where
         envs,
     )?;
 
-    if result.status.load_balancer.ingress.is_empty() {
-        return Ok(None);
-    }
+    Ok(result
+        .status
+        .load_balancer
+        .ingress
+        .first()
+        .map(|ingress| ingress.hostname.clone())
+        .filter(|hostname| !hostname.is_empty()))
+}
+
+/// Hostname or IP address of a LoadBalancer service, `None` while the load balancer isn't provisioned yet.
+pub fn kubectl_exec_get_load_balancer_address<P>(
+    kubernetes_config: P,
+    namespace: &str,
+    name: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<Option<String>, CommandError>
+where
+    P: AsRef<Path>,
+{
+    let result = kubectl_exec::<P, KubernetesService>(
+        vec!["get", "-n", namespace, "svc", name, "-o", "json"],
+        kubernetes_config,
+        envs,
+    )?;
 
-    Ok(Some(result.status.load_balancer.ingress.first().unwrap().hostname.clone()))
+    Ok(result
+        .status
+        .load_balancer
+        .ingress
+        .into_iter()
+        .map(|ingress| {
+            if ingress.hostname.is_empty() {
+                ingress.ip
+            } else {
+                ingress.hostname
+            }
+        })
+        .find(|address| !address.is_empty()))
 }
 
 pub fn kubectl_exec_is_pod_ready_with_retry<P>(
@@ -283,16 +316,18 @@ where
     Ok(Some(is_ready))
 }
 
+/// Wait for a job to complete, returns `Some(false)` if the job failed or is still running after `timeout_in_seconds`.
 pub fn kubectl_exec_is_job_ready_with_retry<P>(
     kubernetes_config: P,
     namespace: &str,
     job_name: &str,
+    timeout_in_seconds: u32,
     envs: Vec<(&str, &str)>,
-) -> Result<Option<bool>, SimpleError>
+) -> Result<Option<bool>, CommandError>
 where
     P: AsRef<Path>,
 {
-    let result = retry::retry(Fibonacci::from_millis(3000).take(10), || {
+    let result = retry::retry(Fixed::from_millis(5000).take((timeout_in_seconds / 5) as usize + 1), || {
         let r = crate::cmd::kubectl::kubectl_exec_is_job_ready(
             kubernetes_config.as_ref(),
             namespace,
@@ -302,8 +337,8 @@ where
 
         match r {
             Ok(is_ready) => match is_ready {
-                Some(true) => OperationResult::Ok(true),
-                _ => {
+                Some(is_ready) => OperationResult::Ok(is_ready),
+                None => {
                     let t = format!("job {} is not ready yet", job_name);
                     info!("{}", t.as_str());
                     OperationResult::Retry(t)
@@ -320,12 +355,13 @@ where
                 total_delay: _,
                 tries: _,
             } => Ok(Some(false)),
-            retry::Error::Internal(err) => Err(SimpleError::new(SimpleErrorKind::Other, Some(err))),
+            retry::Error::Internal(err) => Err(CommandError::new_from_safe_message(err)),
         },
-        Ok(_) => Ok(Some(true)),
+        Ok(is_ready) => Ok(Some(is_ready)),
     }
 }
 
+/// Returns `Some(true)` once the job succeeded, `Some(false)` if it failed and `None` while it is still running.
 pub fn kubectl_exec_is_job_ready<P>(
     kubernetes_config: P,
     namespace: &str,
@@ -345,7 +381,16 @@ where
         return Ok(Some(true));
     }
 
-    Ok(Some(false))
+    if job_result
+        .status
+        .conditions
+        .iter()
+        .any(|condition| condition.condition_type == "Failed" && condition.status == "True")
+    {
+        return Ok(Some(false));
+    }
+
+    Ok(None)
 }
 
 pub fn kubectl_exec_is_namespace_present<P>(kubernetes_config: P, namespace: &str, envs: Vec<(&str, &str)>) -> bool
@@ -639,6 +684,20 @@ where
     kubectl_exec::<P, KubernetesVersion>(vec!["version", "-o", "json"], kubernetes_config, envs)
 }
 
+/// API group versions served by the cluster, eq. `snapshot.storage.k8s.io/v1`.
+pub fn kubectl_exec_api_versions<P>(kubernetes_config: P, envs: Vec<(&str, &str)>) -> Result<Vec<String>, CommandError>
+where
+    P: AsRef<Path>,
+{
+    let output = kubectl_exec_raw_output(vec!["api-versions"], kubernetes_config, envs, true)?;
+
+    Ok(output
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
 pub fn kubectl_exec_get_daemonset<P>(
     kubernetes_config: P,
     name: &str,
@@ -924,6 +983,45 @@ where
     )
 }
 
+/// suspend a cron job, its future runs won't be scheduled until it is deployed again
+///
+/// # Arguments
+///
+/// * `kubernetes_config` - kubernetes config path
+/// * `envs` - environment variables required for kubernetes connection
+/// * `namespace` - kubernetes namespace
+/// * `name` - name of the cron job to suspend
+pub fn kubectl_exec_suspend_cronjob<P>(
+    kubernetes_config: P,
+    envs: Vec<(&str, &str)>,
+    namespace: &str,
+    name: &str,
+) -> Result<(), CommandError>
+where
+    P: AsRef<Path>,
+{
+    let mut _envs = Vec::with_capacity(envs.len() + 1);
+    _envs.push((KUBECONFIG, kubernetes_config.as_ref().to_str().unwrap()));
+    _envs.extend(envs);
+
+    kubectl_exec_with_output(
+        vec![
+            "-n",
+            namespace,
+            "patch",
+            "cronjob",
+            name,
+            "--type",
+            "merge",
+            "-p",
+            r#"{"spec":{"suspend":true}}"#,
+        ],
+        _envs,
+        &mut |_| {},
+        &mut |_| {},
+    )
+}
+
 /// scale down replicas by selector
 ///
 /// # Arguments
@@ -1031,6 +1129,124 @@ where
     kubectl_exec::<P, PVC>(vec!["get", "pvc", "-o", "json", "-n", namespace], kubernetes_config, envs)
 }
 
+/// delete a persistent volume claim, waiting for the pods using it to be gone
+///
+/// # Arguments
+///
+/// * `kubernetes_config` - kubernetes config path
+/// * `namespace` - kubernetes namespace
+/// * `name` - name of the persistent volume claim to delete
+/// * `envs` - environment variables required for kubernetes connection
+pub fn kubectl_exec_delete_pvc<P>(
+    kubernetes_config: P,
+    namespace: &str,
+    name: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<(), CommandError>
+where
+    P: AsRef<Path>,
+{
+    let mut complete_envs = Vec::with_capacity(envs.len() + 1);
+    complete_envs.push((KUBECONFIG, kubernetes_config.as_ref().to_str().unwrap()));
+    complete_envs.extend(envs);
+
+    kubectl_exec_with_output(
+        vec![
+            "delete",
+            "pvc",
+            name,
+            "-n",
+            namespace,
+            "--ignore-not-found",
+            "--timeout",
+            "300s",
+        ],
+        complete_envs,
+        &mut |_| {},
+        &mut |_| {},
+    )
+}
+
+pub fn kubectl_exec_get_volume_snapshots<P>(
+    kubernetes_config: P,
+    namespace: &str,
+    selector: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<KubernetesList<VolumeSnapshot>, CommandError>
+where
+    P: AsRef<Path>,
+{
+    kubectl_exec::<P, KubernetesList<VolumeSnapshot>>(
+        vec!["get", "volumesnapshots", "-o", "json", "-n", namespace, "-l", selector],
+        kubernetes_config,
+        envs,
+    )
+}
+
+pub fn kubectl_exec_get_ingresses<P>(
+    kubernetes_config: P,
+    namespace: &str,
+    selector: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<KubernetesList<Ingress>, CommandError>
+where
+    P: AsRef<Path>,
+{
+    kubectl_exec::<P, KubernetesList<Ingress>>(
+        vec!["get", "ingresses", "-o", "json", "-n", namespace, "-l", selector],
+        kubernetes_config,
+        envs,
+    )
+}
+
+pub fn kubectl_exec_get_storage_class<P>(
+    kubernetes_config: P,
+    name: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<StorageClass, CommandError>
+where
+    P: AsRef<Path>,
+{
+    kubectl_exec::<P, StorageClass>(vec!["get", "storageclass", name, "-o", "json"], kubernetes_config, envs)
+}
+
+pub fn kubectl_exec_delete_storage_class<P>(
+    kubernetes_config: P,
+    name: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<(), CommandError>
+where
+    P: AsRef<Path>,
+{
+    let mut _envs = Vec::with_capacity(envs.len() + 1);
+    _envs.push((KUBECONFIG, kubernetes_config.as_ref().to_str().unwrap()));
+    _envs.extend(envs);
+
+    kubectl_exec_with_output(
+        vec!["delete", "storageclass", name],
+        _envs,
+        &mut |line| info!("{}", line),
+        &mut |line| error!("{}", line),
+    )?;
+
+    Ok(())
+}
+
+pub fn kubectl_exec_get_volume_snapshot_class<P>(
+    kubernetes_config: P,
+    name: &str,
+    envs: Vec<(&str, &str)>,
+) -> Result<VolumeSnapshotClass, CommandError>
+where
+    P: AsRef<Path>,
+{
+    kubectl_exec::<P, VolumeSnapshotClass>(
+        vec!["get", "volumesnapshotclass", name, "-o", "json"],
+        kubernetes_config,
+        envs,
+    )
+}
+
 pub fn kubectl_get_svc<P>(kubernetes_config: P, namespace: &str, envs: Vec<(&str, &str)>) -> Result<SVC, CommandError>
 where
     P: AsRef<Path>,
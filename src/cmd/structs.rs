@@ -144,7 +144,10 @@ pub struct KubernetesServiceStatusLoadBalancer {
 #[derive(Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct KubernetesServiceStatusLoadBalancerIngress {
+    #[serde(default)]
     pub hostname: String,
+    #[serde(default)]
+    pub ip: String,
 }
 
 #[derive(Deserialize, Clone, Eq, PartialEq, Debug)]
@@ -272,7 +275,20 @@ pub struct KubernetesJob {
 #[derive(Deserialize, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct KubernetesJobStatus {
+    #[serde(default)]
     pub succeeded: u32,
+    #[serde(default)]
+    pub failed: u32,
+    #[serde(default)]
+    pub conditions: Vec<KubernetesJobCondition>,
+}
+
+#[derive(Deserialize, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KubernetesJobCondition {
+    #[serde(rename = "type")]
+    pub condition_type: String,
+    pub status: String,
 }
 
 #[derive(Deserialize, Clone, Eq, PartialEq)]
@@ -416,7 +432,10 @@ pub struct PVCItem {
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PVCMetadata {
+    pub name: String,
     pub resource_version: String,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
@@ -427,6 +446,14 @@ pub struct PVCSpec {
     pub storage_class_name: String,
     pub volume_mode: String,
     pub volume_name: String,
+    pub data_source: Option<PVCDataSource>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PVCDataSource {
+    pub kind: String,
+    pub name: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Deserialize)]
@@ -535,9 +562,77 @@ pub struct HPAAnnotationCondition {
     pub conditions: Option<String>,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshot {
+    pub metadata: VolumeSnapshotMetadata,
+    pub status: Option<VolumeSnapshotStatus>,
+}
+
+impl VolumeSnapshot {
+    pub fn is_ready_to_use(&self) -> bool {
+        matches!(
+            self.status,
+            Some(VolumeSnapshotStatus {
+                ready_to_use: Some(true),
+                ..
+            })
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshotMetadata {
+    pub creation_timestamp: String,
+    pub name: String,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshotStatus {
+    pub ready_to_use: Option<bool>,
+    pub restore_size: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ingress {
+    pub spec: IngressSpec,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngressSpec {
+    #[serde(default)]
+    pub rules: Vec<IngressRule>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IngressRule {
+    pub host: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageClass {
+    pub provisioner: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshotClass {
+    pub driver: String,
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::cmd::structs::{KubernetesList, KubernetesPod, KubernetesPodStatusReason, PDB, PVC, SVC};
+    use crate::cmd::structs::{
+        Ingress, KubernetesList, KubernetesPod, KubernetesPodStatusReason, VolumeSnapshot, PDB, PVC, SVC,
+    };
 
     #[test]
     fn test_svc_deserialize() {
@@ -810,6 +905,11 @@ mod tests {
         "accessModes": [
           "ReadWriteOnce"
         ],
+        "dataSource": {
+          "apiGroup": "snapshot.storage.k8s.io",
+          "kind": "VolumeSnapshot",
+          "name": "wx3s3f67pruykgz-app-simple-app-vsxgtriudbloeaa-1-27823740"
+        },
         "resources": {
           "requests": {
             "storage": "10Gi"
@@ -842,7 +942,11 @@ mod tests {
 
         // verify:
         match pvc {
-            Ok(_) => assert!(true),
+            Ok(pvc) => {
+                let items = pvc.items.unwrap();
+                assert_eq!(None, items[0].spec.data_source);
+                assert_eq!("VolumeSnapshot", items[1].spec.data_source.as_ref().unwrap().kind);
+            }
             Err(e) => {
                 return assert!(false, "{}", e);
             }
@@ -1778,4 +1882,159 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_volume_snapshot_deserialize() {
+        // setup:
+        let payload = r#"{
+    "apiVersion": "v1",
+    "items": [
+        {
+            "apiVersion": "snapshot.storage.k8s.io/v1",
+            "kind": "VolumeSnapshot",
+            "metadata": {
+                "creationTimestamp": "2022-06-20T03:00:12Z",
+                "generation": 1,
+                "labels": {
+                    "appId": "z584b6585",
+                    "diskId": "z1a2b3c4d"
+                },
+                "name": "z1a2b3c4d-app-z584b6585-0-20220620030010",
+                "namespace": "za2730025-z18650490",
+                "resourceVersion": "892065755",
+                "uid": "ec7c8f98-3cf2-4b77-b5c1-4e449a12be51"
+            },
+            "spec": {
+                "source": {
+                    "persistentVolumeClaimName": "z1a2b3c4d-app-z584b6585-0"
+                },
+                "volumeSnapshotClassName": "qovery-volume-snapshots"
+            },
+            "status": {
+                "boundVolumeSnapshotContentName": "snapcontent-ec7c8f98-3cf2-4b77-b5c1-4e449a12be51",
+                "creationTime": "2022-06-20T03:00:13Z",
+                "readyToUse": true,
+                "restoreSize": "10Gi"
+            }
+        },
+        {
+            "apiVersion": "snapshot.storage.k8s.io/v1",
+            "kind": "VolumeSnapshot",
+            "metadata": {
+                "creationTimestamp": "2022-06-21T03:00:11Z",
+                "generation": 1,
+                "labels": {
+                    "appId": "z584b6585",
+                    "diskId": "z1a2b3c4d"
+                },
+                "name": "z1a2b3c4d-app-z584b6585-0-20220621030009",
+                "namespace": "za2730025-z18650490",
+                "resourceVersion": "892075755",
+                "uid": "7ea75ab3-4a1f-401e-a0e8-11203ae621e9"
+            },
+            "spec": {
+                "source": {
+                    "persistentVolumeClaimName": "z1a2b3c4d-app-z584b6585-0"
+                },
+                "volumeSnapshotClassName": "qovery-volume-snapshots"
+            }
+        }
+    ],
+    "kind": "List",
+    "metadata": {
+        "resourceVersion": "",
+        "selfLink": ""
+    }
+}"#;
+
+        // execute:
+        let snapshots = serde_json::from_str::<KubernetesList<VolumeSnapshot>>(payload).unwrap();
+
+        // verify:
+        assert_eq!(2, snapshots.items.len());
+        assert_eq!("z1a2b3c4d", snapshots.items[0].metadata.labels["diskId"]);
+        assert!(snapshots.items[0].is_ready_to_use());
+        assert!(!snapshots.items[1].is_ready_to_use());
+    }
+
+    #[test]
+    fn test_ingress_deserialize() {
+        // setup:
+        let payload = r#"{
+    "apiVersion": "v1",
+    "items": [
+        {
+            "apiVersion": "networking.k8s.io/v1",
+            "kind": "Ingress",
+            "metadata": {
+                "labels": {
+                    "routerId": "z8d2f4c1a"
+                },
+                "name": "router-z8d2f4c1a",
+                "namespace": "za2730025-z18650490"
+            },
+            "spec": {
+                "rules": [
+                    {
+                        "host": "z8d2f4c1a-main.za2730025.qovery.io",
+                        "http": {
+                            "paths": []
+                        }
+                    },
+                    {
+                        "host": "www.example.com",
+                        "http": {
+                            "paths": []
+                        }
+                    },
+                    {
+                        "http": {
+                            "paths": []
+                        }
+                    }
+                ]
+            }
+        },
+        {
+            "apiVersion": "networking.k8s.io/v1",
+            "kind": "Ingress",
+            "metadata": {
+                "name": "router-z8d2f4c1a-default-backend",
+                "namespace": "za2730025-z18650490"
+            },
+            "spec": {
+                "defaultBackend": {
+                    "service": {
+                        "name": "app-z584b6585",
+                        "port": {
+                            "number": 80
+                        }
+                    }
+                }
+            }
+        }
+    ],
+    "kind": "List"
+}"#;
+
+        // execute:
+        let ingresses = serde_json::from_str::<KubernetesList<Ingress>>(payload).unwrap();
+
+        // verify:
+        assert_eq!(2, ingresses.items.len());
+        assert_eq!(
+            vec![
+                Some("z8d2f4c1a-main.za2730025.qovery.io".to_string()),
+                Some("www.example.com".to_string()),
+                None
+            ],
+            ingresses.items[0]
+                .spec
+                .rules
+                .iter()
+                .map(|rule| rule.host.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(ingresses.items[1].spec.rules.is_empty());
+    }
 }
@@ -1,8 +1,22 @@
 use std::net::Ipv4Addr;
 
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
 use crate::dns_provider::errors::DnsProviderError;
-use crate::dns_provider::{DnsProvider, Kind};
+use crate::dns_provider::{DnsProvider, DnsProviderConfiguration, DnsRecord, DnsRecordType, Kind};
 use crate::io_models::{Context, Domain};
+use crate::utilities::get_header_with_bearer;
+
+pub const CLOUDFLARE_API_URL: &str = "https://api.cloudflare.com/client/v4";
+// Cloudflare maximum page size for DNS records listing
+const CLOUDFLARE_RECORDS_PAGE_SIZE: u32 = 100;
+// Cloudflare uses a ttl of 1 for "automatic", otherwise it has to be between 60 and 86400 seconds
+const CLOUDFLARE_AUTOMATIC_TTL: u32 = 1;
+const CLOUDFLARE_MIN_TTL: u32 = 60;
+const CLOUDFLARE_MAX_TTL: u32 = 86400;
 
 pub struct Cloudflare {
     context: Context,
@@ -11,6 +25,81 @@ pub struct Cloudflare {
     domain: Domain,
     cloudflare_api_token: String,
     cloudflare_email: String,
+    api_url: String,
+}
+
+#[derive(Deserialize)]
+struct CloudflareResponse<T> {
+    success: bool,
+    #[serde(default)]
+    errors: Vec<CloudflareError>,
+    result: Option<T>,
+    result_info: Option<CloudflareResultInfo>,
+}
+
+#[derive(Deserialize)]
+struct CloudflareError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct CloudflareResultInfo {
+    page: u32,
+    total_pages: u32,
+}
+
+#[derive(Deserialize)]
+struct CloudflareZone {
+    id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CloudflareDnsRecord {
+    #[serde(skip_serializing)]
+    id: Option<String>,
+    // zones can contain any record type, only the ones managed by the engine are converted
+    #[serde(rename = "type")]
+    record_type: String,
+    name: String,
+    content: String,
+    ttl: u32,
+    #[serde(default)]
+    proxied: bool,
+}
+
+impl CloudflareDnsRecord {
+    fn into_dns_record(self) -> Option<DnsRecord> {
+        let record_type = match self.record_type.as_str() {
+            "A" => DnsRecordType::A,
+            "AAAA" => DnsRecordType::Aaaa,
+            "CNAME" => DnsRecordType::Cname,
+            "TXT" => DnsRecordType::Txt,
+            // other record types are not managed by the engine
+            _ => return None,
+        };
+
+        Some(DnsRecord {
+            name: self.name,
+            record_type,
+            content: self.content,
+            ttl: self.ttl,
+            proxied: self.proxied,
+        })
+    }
+}
+
+impl From<&DnsRecord> for CloudflareDnsRecord {
+    fn from(record: &DnsRecord) -> Self {
+        CloudflareDnsRecord {
+            id: None,
+            record_type: record.record_type.to_string(),
+            name: record.name.trim_end_matches('.').to_string(),
+            content: record.content.to_string(),
+            ttl: record.ttl,
+            proxied: record.proxied,
+        }
+    }
 }
 
 impl Cloudflare {
@@ -29,8 +118,114 @@ impl Cloudflare {
             domain,
             cloudflare_api_token: cloudflare_api_token.to_string(),
             cloudflare_email: cloudflare_email.to_string(),
+            api_url: CLOUDFLARE_API_URL.to_string(),
         }
     }
+
+    fn zone_name(&self) -> String {
+        self.domain.root_domain().to_string()
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        reqwest::blocking::Client::new()
+            .request(method, format!("{}{}", self.api_url, path))
+            .headers(get_header_with_bearer(self.cloudflare_api_token.as_str()))
+    }
+
+    fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<CloudflareResponse<T>, String> {
+        let response = request.send().map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = response.text().map_err(|e| e.to_string())?;
+
+        let response: CloudflareResponse<T> = serde_json::from_str(body.as_str())
+            .map_err(|e| format!("unexpected Cloudflare API response (status {}): {}", status, e))?;
+
+        match response.success {
+            true => Ok(response),
+            false => Err(format!(
+                "Cloudflare API error (status {}): {}",
+                status,
+                response
+                    .errors
+                    .iter()
+                    .map(|e| format!("{} ({})", e.message, e.code))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    fn zone_id(&self) -> Result<String, DnsProviderError> {
+        let zone_name = self.zone_name();
+        let cannot_find_zone = |raw_error_message: String| DnsProviderError::CannotFindZone {
+            zone_name: zone_name.to_string(),
+            raw_error_message,
+        };
+
+        let response: CloudflareResponse<Vec<CloudflareZone>> = Cloudflare::send(
+            self.request(Method::GET, "/zones")
+                .query(&[("name", zone_name.as_str())]),
+        )
+        .map_err(cannot_find_zone)?;
+
+        match response.result.unwrap_or_default().into_iter().next() {
+            Some(zone) => Ok(zone.id),
+            None => Err(cannot_find_zone("zone doesn't exist or token can't access it".to_string())),
+        }
+    }
+
+    fn find_records(
+        &self,
+        zone_id: &str,
+        record_name: Option<&str>,
+        record_type: Option<DnsRecordType>,
+    ) -> Result<Vec<CloudflareDnsRecord>, String> {
+        let mut records = vec![];
+        let mut page = 1;
+
+        loop {
+            let mut query = vec![
+                ("page", page.to_string()),
+                ("per_page", CLOUDFLARE_RECORDS_PAGE_SIZE.to_string()),
+            ];
+            if let Some(record_name) = record_name {
+                query.push(("name", record_name.trim_end_matches('.').to_string()));
+            }
+            if let Some(record_type) = record_type {
+                query.push(("type", record_type.to_string()));
+            }
+
+            let response: CloudflareResponse<Vec<CloudflareDnsRecord>> = Cloudflare::send(
+                self.request(Method::GET, format!("/zones/{}/dns_records", zone_id).as_str())
+                    .query(&query),
+            )?;
+
+            records.extend(response.result.unwrap_or_default());
+
+            match response.result_info {
+                Some(info) if info.page < info.total_pages => page = info.page + 1,
+                _ => break,
+            }
+        }
+
+        Ok(records)
+    }
+
+    fn is_record_valid(&self, record: &DnsRecord) -> Result<(), DnsProviderError> {
+        record.is_valid(&self.domain.root_domain())?;
+
+        if record.ttl != CLOUDFLARE_AUTOMATIC_TTL && !(CLOUDFLARE_MIN_TTL..=CLOUDFLARE_MAX_TTL).contains(&record.ttl) {
+            return Err(DnsProviderError::InvalidRecord {
+                record_name: record.name.to_string(),
+                raw_error_message: format!(
+                    "ttl should be {} (automatic) or between {} and {} seconds",
+                    CLOUDFLARE_AUTOMATIC_TTL, CLOUDFLARE_MIN_TTL, CLOUDFLARE_MAX_TTL
+                ),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 impl DnsProvider for Cloudflare {
@@ -77,4 +272,352 @@ impl DnsProvider for Cloudflare {
             Ok(())
         }
     }
+
+    fn configuration(&self) -> DnsProviderConfiguration {
+        DnsProviderConfiguration::Cloudflare {
+            api_token: self.cloudflare_api_token.to_string(),
+            email: self.cloudflare_email.to_string(),
+        }
+    }
+
+    fn upsert_record(&self, record: &DnsRecord) -> Result<DnsRecord, DnsProviderError> {
+        self.is_record_valid(record)?;
+
+        let cannot_upsert_record = |raw_error_message: String| DnsProviderError::CannotUpsertRecord {
+            record_name: record.name.to_string(),
+            raw_error_message,
+        };
+
+        let zone_id = self.zone_id()?;
+        let existing_record = self
+            .find_records(zone_id.as_str(), Some(record.name.as_str()), Some(record.record_type))
+            .map_err(cannot_upsert_record)?
+            .into_iter()
+            .find_map(|r| r.id);
+
+        let request = match existing_record {
+            Some(record_id) => {
+                self.request(Method::PUT, format!("/zones/{}/dns_records/{}", zone_id, record_id).as_str())
+            }
+            None => self.request(Method::POST, format!("/zones/{}/dns_records", zone_id).as_str()),
+        };
+
+        let response: CloudflareResponse<CloudflareDnsRecord> =
+            Cloudflare::send(request.json(&CloudflareDnsRecord::from(record))).map_err(cannot_upsert_record)?;
+
+        match response.result.and_then(CloudflareDnsRecord::into_dns_record) {
+            Some(record) => Ok(record),
+            None => Err(cannot_upsert_record("record is missing from response".to_string())),
+        }
+    }
+
+    fn delete_record(&self, record_name: &str, record_type: DnsRecordType) -> Result<(), DnsProviderError> {
+        let cannot_delete_record = |raw_error_message: String| DnsProviderError::CannotDeleteRecord {
+            record_name: record_name.to_string(),
+            raw_error_message,
+        };
+
+        let zone_id = self.zone_id()?;
+        let records = self
+            .find_records(zone_id.as_str(), Some(record_name), Some(record_type))
+            .map_err(cannot_delete_record)?;
+
+        for record_id in records.into_iter().filter_map(|r| r.id) {
+            let _: CloudflareResponse<serde_json::Value> = Cloudflare::send(
+                self.request(Method::DELETE, format!("/zones/{}/dns_records/{}", zone_id, record_id).as_str()),
+            )
+            .map_err(cannot_delete_record)?;
+        }
+
+        Ok(())
+    }
+
+    fn list_records(&self) -> Result<Vec<DnsRecord>, DnsProviderError> {
+        let zone_id = self.zone_id()?;
+
+        match self.find_records(zone_id.as_str(), None, None) {
+            Ok(records) => Ok(records
+                .into_iter()
+                .filter_map(CloudflareDnsRecord::into_dns_record)
+                .collect()),
+            Err(raw_error_message) => Err(DnsProviderError::CannotListRecords {
+                zone_name: self.zone_name(),
+                raw_error_message,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::docker::Docker;
+    use mockito::{mock, Matcher, Mock};
+    use serde_json::json;
+
+    fn cloudflare() -> Cloudflare {
+        let mut cloudflare = Cloudflare::new(
+            Context::new(
+                "organization_id".to_string(),
+                "cluster_id".to_string(),
+                "execution_id".to_string(),
+                "/tmp".to_string(),
+                "/tmp".to_string(),
+                true,
+                None,
+                vec![],
+                None,
+                Docker::new_with_options(false, None).unwrap(),
+            ),
+            "id",
+            "cloudflare",
+            Domain::new("cluster.example.com".to_string()),
+            "token",
+            "user@example.com",
+        );
+        cloudflare.api_url = mockito::server_url();
+        cloudflare
+    }
+
+    fn mock_zone() -> Mock {
+        mock("GET", "/zones")
+            .match_header("authorization", "Bearer token")
+            .match_query(Matcher::UrlEncoded("name".to_string(), "example.com".to_string()))
+            .with_body(json!({"success": true, "errors": [], "result": [{"id": "zone-id"}]}).to_string())
+            .create()
+    }
+
+    fn mock_find_records(record_name: &str, record_type: &str, records: serde_json::Value) -> Mock {
+        mock("GET", "/zones/zone-id/dns_records")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("name".to_string(), record_name.to_string()),
+                Matcher::UrlEncoded("type".to_string(), record_type.to_string()),
+            ]))
+            .with_body(
+                json!({
+                    "success": true,
+                    "errors": [],
+                    "result": records,
+                    "result_info": {"page": 1, "total_pages": 1}
+                })
+                .to_string(),
+            )
+            .create()
+    }
+
+    #[test]
+    fn test_upsert_record_creates_missing_record() {
+        // setup:
+        let record = DnsRecord::new("app.cluster.example.com", DnsRecordType::Cname, "lb.example.net", 300);
+        let zone = mock_zone();
+        let find = mock_find_records("app.cluster.example.com", "CNAME", json!([]));
+        let create = mock("POST", "/zones/zone-id/dns_records")
+            .match_body(Matcher::Json(json!({
+                "type": "CNAME",
+                "name": "app.cluster.example.com",
+                "content": "lb.example.net",
+                "ttl": 300,
+                "proxied": false
+            })))
+            .with_body(
+                json!({
+                    "success": true,
+                    "errors": [],
+                    "result": {
+                        "id": "record-id",
+                        "type": "CNAME",
+                        "name": "app.cluster.example.com",
+                        "content": "lb.example.net",
+                        "ttl": 300,
+                        "proxied": false
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        // execute:
+        let result = cloudflare().upsert_record(&record);
+
+        // verify:
+        assert_eq!(Ok(record), result);
+        zone.assert();
+        find.assert();
+        create.assert();
+    }
+
+    #[test]
+    fn test_upsert_record_updates_existing_record() {
+        // setup:
+        let record = DnsRecord {
+            proxied: true,
+            ..DnsRecord::new("www.cluster.example.com", DnsRecordType::A, "1.2.3.4", 1)
+        };
+        let zone = mock_zone();
+        let find = mock_find_records(
+            "www.cluster.example.com",
+            "A",
+            json!([{
+                "id": "existing-id",
+                "type": "A",
+                "name": "www.cluster.example.com",
+                "content": "4.3.2.1",
+                "ttl": 1,
+                "proxied": false
+            }]),
+        );
+        let update = mock("PUT", "/zones/zone-id/dns_records/existing-id")
+            .match_body(Matcher::PartialJson(json!({"content": "1.2.3.4", "proxied": true})))
+            .with_body(
+                json!({
+                    "success": true,
+                    "errors": [],
+                    "result": {
+                        "id": "existing-id",
+                        "type": "A",
+                        "name": "www.cluster.example.com",
+                        "content": "1.2.3.4",
+                        "ttl": 1,
+                        "proxied": true
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+
+        // execute:
+        let result = cloudflare().upsert_record(&record);
+
+        // verify:
+        assert_eq!(Ok(record), result);
+        zone.assert();
+        find.assert();
+        update.assert();
+    }
+
+    #[test]
+    fn test_upsert_record_invalid_record() {
+        // setup:
+        let cloudflare = cloudflare();
+
+        // execute & verify:
+        assert!(matches!(
+            cloudflare.upsert_record(&DnsRecord::new("app.other.com", DnsRecordType::A, "1.2.3.4", 300)),
+            Err(DnsProviderError::InvalidRecord { .. })
+        ));
+        assert!(matches!(
+            cloudflare.upsert_record(&DnsRecord::new("app.cluster.example.com", DnsRecordType::A, "1.2.3.4", 30)),
+            Err(DnsProviderError::InvalidRecord { .. })
+        ));
+    }
+
+    #[test]
+    fn test_delete_record() {
+        // setup:
+        let zone = mock_zone();
+        let find = mock_find_records(
+            "_qovery.cluster.example.com",
+            "TXT",
+            json!([
+                {"id": "txt-1", "type": "TXT", "name": "_qovery.cluster.example.com", "content": "a", "ttl": 1},
+                {"id": "txt-2", "type": "TXT", "name": "_qovery.cluster.example.com", "content": "b", "ttl": 1}
+            ]),
+        );
+        let deletes = ["txt-1", "txt-2"]
+            .iter()
+            .map(|id| {
+                mock("DELETE", format!("/zones/zone-id/dns_records/{}", id).as_str())
+                    .with_body(json!({"success": true, "errors": [], "result": {"id": id}}).to_string())
+                    .create()
+            })
+            .collect::<Vec<Mock>>();
+
+        // execute:
+        let result = cloudflare().delete_record("_qovery.cluster.example.com", DnsRecordType::Txt);
+
+        // verify:
+        assert_eq!(Ok(()), result);
+        zone.assert();
+        find.assert();
+        deletes.iter().for_each(|delete| delete.assert());
+    }
+
+    #[test]
+    fn test_list_records_follows_pagination_and_skips_unmanaged_types() {
+        // setup:
+        let zone = mock_zone();
+        let pages = (1..=2)
+            .map(|page| {
+                mock("GET", "/zones/zone-id/dns_records")
+                    .match_query(Matcher::UrlEncoded("page".to_string(), page.to_string()))
+                    .with_body(
+                        json!({
+                            "success": true,
+                            "errors": [],
+                            "result": [{
+                                "id": format!("record-{}", page),
+                                "type": "A",
+                                "name": format!("app{}.cluster.example.com", page),
+                                "content": "1.2.3.4",
+                                "ttl": 300,
+                                "proxied": false
+                            }, {
+                                // not managed by the engine, skipped
+                                "id": format!("mx-record-{}", page),
+                                "type": "MX",
+                                "name": "cluster.example.com",
+                                "content": "mail.example.com",
+                                "priority": 10,
+                                "ttl": 300,
+                                "proxied": false
+                            }],
+                            "result_info": {"page": page, "total_pages": 2}
+                        })
+                        .to_string(),
+                    )
+                    .create()
+            })
+            .collect::<Vec<Mock>>();
+
+        // execute:
+        let result = cloudflare().list_records();
+
+        // verify:
+        assert_eq!(
+            Ok(vec![
+                DnsRecord::new("app1.cluster.example.com", DnsRecordType::A, "1.2.3.4", 300),
+                DnsRecord::new("app2.cluster.example.com", DnsRecordType::A, "1.2.3.4", 300),
+            ]),
+            result
+        );
+        zone.assert();
+        pages.iter().for_each(|page| page.assert());
+    }
+
+    #[test]
+    fn test_api_error() {
+        // setup:
+        let zone = mock("GET", "/zones")
+            .match_query(Matcher::Any)
+            .with_status(403)
+            .with_body(
+                json!({"success": false, "errors": [{"code": 9109, "message": "Invalid access token"}], "result": null})
+                    .to_string(),
+            )
+            .create();
+
+        // execute:
+        let result = cloudflare().list_records();
+
+        // verify:
+        assert_eq!(
+            Err(DnsProviderError::CannotFindZone {
+                zone_name: "example.com".to_string(),
+                raw_error_message: "Cloudflare API error (status 403 Forbidden): Invalid access token (9109)"
+                    .to_string(),
+            }),
+            result
+        );
+        zone.assert();
+    }
 }
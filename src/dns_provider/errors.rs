@@ -4,4 +4,29 @@ use thiserror::Error;
 pub enum DnsProviderError {
     #[error("Invalid credentials error.")]
     InvalidCredentials,
+    #[error("Invalid record `{record_name:?}` error: {raw_error_message:?}.")]
+    InvalidRecord {
+        record_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot find DNS zone `{zone_name:?}` error: {raw_error_message:?}.")]
+    CannotFindZone {
+        zone_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot list records error in zone `{zone_name:?}`: {raw_error_message:?}.")]
+    CannotListRecords {
+        zone_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot upsert record `{record_name:?}` error: {raw_error_message:?}.")]
+    CannotUpsertRecord {
+        record_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot delete record `{record_name:?}` error: {raw_error_message:?}.")]
+    CannotDeleteRecord {
+        record_name: String,
+        raw_error_message: String,
+    },
 }
@@ -1,12 +1,17 @@
-use std::net::Ipv4Addr;
+use std::fmt::{Display, Formatter};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
 
 use crate::dns_provider::errors::DnsProviderError;
+use crate::dns_provider::rfc2136::TsigAlgorithm;
 use serde::{Deserialize, Serialize};
 
 use crate::io_models::{Context, Domain};
+use tera::Context as TeraContext;
 
 pub mod cloudflare;
 pub mod errors;
+pub mod rfc2136;
+pub mod route53;
 
 pub trait DnsProvider {
     fn context(&self) -> &Context;
@@ -21,11 +26,316 @@ pub trait DnsProvider {
     fn token(&self) -> &str;
     fn domain(&self) -> &Domain;
     fn resolvers(&self) -> Vec<Ipv4Addr>;
+    /// Nameservers queried to check domains resolution and ownership, public resolvers are used when empty.
+    fn domain_check_nameservers(&self) -> Vec<SocketAddr> {
+        vec![]
+    }
     fn is_valid(&self) -> Result<(), DnsProviderError>;
+    /// Provider settings external-dns and cert-manager DNS-01 solver need to manage records.
+    fn configuration(&self) -> DnsProviderConfiguration;
+    /// Creates the record, or updates the existing record having the same name and type.
+    fn upsert_record(&self, record: &DnsRecord) -> Result<DnsRecord, DnsProviderError>;
+    /// Deletes all records matching name and type, deleting a missing record is not an error.
+    fn delete_record(&self, record_name: &str, record_type: DnsRecordType) -> Result<(), DnsProviderError>;
+    fn list_records(&self) -> Result<Vec<DnsRecord>, DnsProviderError>;
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Kind {
     Cloudflare,
+    Route53,
+    Rfc2136,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DnsProviderConfiguration {
+    Cloudflare {
+        api_token: String,
+        email: String,
+    },
+    Route53 {
+        access_key_id: String,
+        secret_access_key: String,
+        region: String,
+        hosted_zone_id: Option<String>,
+    },
+    Rfc2136 {
+        nameserver: Ipv4Addr,
+        port: u16,
+        zone: String,
+        tsig_key_name: String,
+        tsig_secret: String,
+        tsig_algorithm: TsigAlgorithm,
+    },
+}
+
+impl DnsProviderConfiguration {
+    /// Provider name as expected by external-dns.
+    pub fn external_dns_provider(&self) -> &str {
+        match self {
+            DnsProviderConfiguration::Cloudflare { .. } => "cloudflare",
+            DnsProviderConfiguration::Route53 { .. } => "aws",
+            DnsProviderConfiguration::Rfc2136 { .. } => "rfc2136",
+        }
+    }
+
+    /// Inserts variables used by `external-dns.j2.yaml` chart values.
+    pub fn insert_into_tera_context(&self, context: &mut TeraContext) {
+        context.insert("external_dns_provider", self.external_dns_provider());
+
+        match self {
+            DnsProviderConfiguration::Cloudflare { api_token, email } => {
+                context.insert("cloudflare_api_token", api_token);
+                context.insert("cloudflare_email", email);
+            }
+            DnsProviderConfiguration::Route53 {
+                access_key_id,
+                secret_access_key,
+                region,
+                hosted_zone_id,
+            } => {
+                context.insert("route53_access_key_id", access_key_id);
+                context.insert("route53_secret_access_key", secret_access_key);
+                context.insert("route53_region", region);
+                context.insert("route53_hosted_zone_id", hosted_zone_id);
+            }
+            DnsProviderConfiguration::Rfc2136 {
+                nameserver,
+                port,
+                zone,
+                tsig_key_name,
+                tsig_secret,
+                tsig_algorithm,
+            } => {
+                context.insert("rfc2136_host", &nameserver.to_string());
+                context.insert("rfc2136_port", port);
+                context.insert("rfc2136_zone", zone);
+                context.insert("rfc2136_tsig_keyname", tsig_key_name);
+                context.insert("rfc2136_tsig_secret", tsig_secret);
+                context.insert("rfc2136_tsig_secret_alg", tsig_algorithm.to_bind_format());
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DnsRecordType {
+    A,
+    Aaaa,
+    Cname,
+    Txt,
+}
+
+impl Display for DnsRecordType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DnsRecordType::A => "A",
+            DnsRecordType::Aaaa => "AAAA",
+            DnsRecordType::Cname => "CNAME",
+            DnsRecordType::Txt => "TXT",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DnsRecord {
+    /// Fully qualified record name, e.g. `app.example.com`.
+    pub name: String,
+    pub record_type: DnsRecordType,
+    pub content: String,
+    pub ttl: u32,
+    /// Only supported by Cloudflare, other providers ignore it.
+    pub proxied: bool,
+}
+
+impl DnsRecord {
+    pub fn new(name: &str, record_type: DnsRecordType, content: &str, ttl: u32) -> Self {
+        DnsRecord {
+            name: name.to_string(),
+            record_type,
+            content: content.to_string(),
+            ttl,
+            proxied: false,
+        }
+    }
+
+    /// Checks the record belongs to `zone` and its content matches its type.
+    pub fn is_valid(&self, zone: &Domain) -> Result<(), DnsProviderError> {
+        let invalid_record = |raw_error_message: String| DnsProviderError::InvalidRecord {
+            record_name: self.name.to_string(),
+            raw_error_message,
+        };
+
+        let zone = zone.to_string();
+        let name = self.name.trim_end_matches('.');
+        if name != zone && !name.ends_with(format!(".{}", zone).as_str()) {
+            return Err(invalid_record(format!("record name should be part of `{}` zone", zone)));
+        }
+
+        if self.content.is_empty() {
+            return Err(invalid_record("record content cannot be empty".to_string()));
+        }
+
+        match self.record_type {
+            DnsRecordType::A if self.content.parse::<Ipv4Addr>().is_err() => {
+                Err(invalid_record(format!("`{}` is not a valid IPv4 address", self.content)))
+            }
+            DnsRecordType::Aaaa if self.content.parse::<Ipv6Addr>().is_err() => {
+                Err(invalid_record(format!("`{}` is not a valid IPv6 address", self.content)))
+            }
+            DnsRecordType::Txt if self.proxied => Err(invalid_record("TXT records cannot be proxied".to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestCase<'a> {
+        input: DnsRecord,
+        expected_valid: bool,
+        description: &'a str,
+    }
+
+    #[test]
+    fn test_dns_record_is_valid() {
+        // setup:
+        let zone = Domain::new("example.com".to_string());
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                input: DnsRecord::new("app.example.com", DnsRecordType::A, "1.2.3.4", 300),
+                expected_valid: true,
+                description: "valid A record",
+            },
+            TestCase {
+                input: DnsRecord::new("example.com", DnsRecordType::A, "1.2.3.4", 300),
+                expected_valid: true,
+                description: "valid zone apex record",
+            },
+            TestCase {
+                input: DnsRecord::new("app.other.com", DnsRecordType::A, "1.2.3.4", 300),
+                expected_valid: false,
+                description: "record outside of the zone",
+            },
+            TestCase {
+                input: DnsRecord::new("appexample.com", DnsRecordType::A, "1.2.3.4", 300),
+                expected_valid: false,
+                description: "record name only ends with the zone name",
+            },
+            TestCase {
+                input: DnsRecord::new("app.example.com", DnsRecordType::A, "::1", 300),
+                expected_valid: false,
+                description: "A record with an IPv6 address",
+            },
+            TestCase {
+                input: DnsRecord::new("app.example.com", DnsRecordType::Aaaa, "2001:db8::1", 300),
+                expected_valid: true,
+                description: "valid AAAA record",
+            },
+            TestCase {
+                input: DnsRecord::new("app.example.com", DnsRecordType::Aaaa, "1.2.3.4", 300),
+                expected_valid: false,
+                description: "AAAA record with an IPv4 address",
+            },
+            TestCase {
+                input: DnsRecord::new("app.example.com", DnsRecordType::Cname, "lb.example.net", 300),
+                expected_valid: true,
+                description: "valid CNAME record",
+            },
+            TestCase {
+                input: DnsRecord::new("app.example.com", DnsRecordType::Cname, "", 300),
+                expected_valid: false,
+                description: "empty content",
+            },
+            TestCase {
+                input: DnsRecord {
+                    proxied: true,
+                    ..DnsRecord::new("_challenge.example.com", DnsRecordType::Txt, "token", 300)
+                },
+                expected_valid: false,
+                description: "proxied TXT record",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = tc.input.is_valid(&zone);
+
+            // verify:
+            assert_eq!(tc.expected_valid, result.is_ok(), "case: {}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_external_dns_chart_values() {
+        // setup:
+        let test_cases = vec![
+            (
+                DnsProviderConfiguration::Cloudflare {
+                    api_token: "token".to_string(),
+                    email: "user@example.com".to_string(),
+                },
+                "cloudflare",
+                "cloudflare.apiToken",
+                "token",
+            ),
+            (
+                DnsProviderConfiguration::Route53 {
+                    access_key_id: "access_key".to_string(),
+                    secret_access_key: "secret_key".to_string(),
+                    region: "eu-west-3".to_string(),
+                    hosted_zone_id: Some("Z123".to_string()),
+                },
+                "aws",
+                "aws.credentials.secretKey",
+                "secret_key",
+            ),
+            (
+                DnsProviderConfiguration::Rfc2136 {
+                    nameserver: Ipv4Addr::new(10, 0, 0, 53),
+                    port: 53,
+                    zone: "example.com".to_string(),
+                    tsig_key_name: "qovery".to_string(),
+                    tsig_secret: "c2VjcmV0".to_string(),
+                    tsig_algorithm: TsigAlgorithm::HmacSha256,
+                },
+                "rfc2136",
+                "rfc2136.host",
+                "10.0.0.53",
+            ),
+        ];
+
+        for (configuration, expected_provider, expected_key, expected_value) in test_cases {
+            for lib_dir in &["aws", "digitalocean", "scaleway"] {
+                let template = std::fs::read_to_string(format!(
+                    "{}/lib/{}/bootstrap/chart_values/external-dns.j2.yaml",
+                    env!("CARGO_MANIFEST_DIR"),
+                    lib_dir
+                ))
+                .unwrap();
+                let mut context = TeraContext::new();
+                context.insert("managed_dns_domains_root_helm_format", "[example.com]");
+                context.insert("kubernetes_cluster_id", "cluster_id");
+                context.insert("doks_cluster_id", "cluster_id");
+
+                // execute:
+                configuration.insert_into_tera_context(&mut context);
+                let values: serde_yaml::Value = serde_yaml::from_str(
+                    tera::Tera::one_off(template.as_str(), &context, false)
+                        .unwrap()
+                        .as_str(),
+                )
+                .unwrap();
+
+                // verify:
+                assert_eq!(expected_provider, values["provider"].as_str().unwrap(), "{}", lib_dir);
+                let value = expected_key.split('.').fold(&values, |value, key| &value[key]);
+                assert_eq!(Some(expected_value), value.as_str(), "{} {}", lib_dir, expected_key);
+            }
+        }
+    }
 }
@@ -0,0 +1,449 @@
+use std::fs::File;
+use std::io::Write;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cmd::command::QoveryCommand;
+use crate::dns_provider::errors::DnsProviderError;
+use crate::dns_provider::{DnsProvider, DnsProviderConfiguration, DnsRecord, DnsRecordType, Kind};
+use crate::io_models::{Context, Domain};
+
+/// TSIG algorithms supported by external-dns, cert-manager and BIND.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TsigAlgorithm {
+    HmacMd5,
+    HmacSha1,
+    HmacSha256,
+    HmacSha512,
+}
+
+impl TsigAlgorithm {
+    /// Algorithm name used by BIND tools and external-dns, e.g. `hmac-sha256`.
+    pub fn to_bind_format(&self) -> &str {
+        match self {
+            TsigAlgorithm::HmacMd5 => "hmac-md5",
+            TsigAlgorithm::HmacSha1 => "hmac-sha1",
+            TsigAlgorithm::HmacSha256 => "hmac-sha256",
+            TsigAlgorithm::HmacSha512 => "hmac-sha512",
+        }
+    }
+
+    /// Algorithm name used by cert-manager rfc2136 solver, e.g. `HMACSHA256`.
+    pub fn to_cert_manager_format(&self) -> &str {
+        match self {
+            TsigAlgorithm::HmacMd5 => "HMACMD5",
+            TsigAlgorithm::HmacSha1 => "HMACSHA1",
+            TsigAlgorithm::HmacSha256 => "HMACSHA256",
+            TsigAlgorithm::HmacSha512 => "HMACSHA512",
+        }
+    }
+}
+
+pub struct Rfc2136 {
+    context: Context,
+    id: String,
+    name: String,
+    domain: Domain,
+    nameserver: Ipv4Addr,
+    port: u16,
+    zone: String,
+    tsig_key_name: String,
+    tsig_secret: String,
+    tsig_algorithm: TsigAlgorithm,
+}
+
+impl Rfc2136 {
+    pub fn new(
+        context: Context,
+        id: &str,
+        name: &str,
+        domain: Domain,
+        nameserver: Ipv4Addr,
+        port: u16,
+        zone: &str,
+        tsig_key_name: &str,
+        tsig_secret: &str,
+        tsig_algorithm: TsigAlgorithm,
+    ) -> Self {
+        Rfc2136 {
+            context,
+            id: id.to_string(),
+            name: name.to_string(),
+            domain,
+            nameserver,
+            port,
+            zone: zone.trim_end_matches('.').to_string(),
+            tsig_key_name: tsig_key_name.to_string(),
+            tsig_secret: tsig_secret.to_string(),
+            tsig_algorithm,
+        }
+    }
+
+    fn workspace_directory(&self) -> Result<String, String> {
+        crate::fs::workspace_directory(
+            self.context.workspace_root_dir(),
+            self.context.execution_id(),
+            format!("dns-provider/rfc2136/{}", self.id),
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    /// Writes the TSIG key in a file, so the secret doesn't show up in commands arguments.
+    fn write_tsig_key_file(&self, workspace_directory: &str) -> Result<String, String> {
+        let key_file_path = format!("{}/tsig.key", workspace_directory);
+        write_file(
+            key_file_path.as_str(),
+            tsig_key_file_content(self.tsig_key_name.as_str(), self.tsig_algorithm, self.tsig_secret.as_str()).as_str(),
+        )?;
+
+        Ok(key_file_path)
+    }
+
+    fn nsupdate(&self, commands: &[String]) -> Result<(), String> {
+        let workspace_directory = self.workspace_directory()?;
+        let key_file_path = self.write_tsig_key_file(workspace_directory.as_str())?;
+        let script_path = format!("{}/nsupdate-{}.txt", workspace_directory, uuid::Uuid::new_v4());
+        write_file(
+            script_path.as_str(),
+            nsupdate_script(self.nameserver, self.port, self.zone.as_str(), commands).as_str(),
+        )?;
+
+        let mut errors = vec![];
+        let result = QoveryCommand::new("nsupdate", &["-k", key_file_path.as_str(), script_path.as_str()], &[])
+            .exec_with_output(&mut |line| info!("{}", line), &mut |line| errors.push(line));
+        let _ = std::fs::remove_file(&script_path);
+
+        result.map_err(|e| format!("{} {}", e, errors.join("\n")))
+    }
+
+    fn transfer_zone(&self) -> Result<Vec<DnsRecord>, String> {
+        let workspace_directory = self.workspace_directory()?;
+        let key_file_path = self.write_tsig_key_file(workspace_directory.as_str())?;
+        let nameserver = format!("@{}", self.nameserver);
+        let port = self.port.to_string();
+
+        let mut lines = vec![];
+        let mut errors = vec![];
+        QoveryCommand::new(
+            "dig",
+            &[
+                "-k",
+                key_file_path.as_str(),
+                "-p",
+                port.as_str(),
+                nameserver.as_str(),
+                self.zone.as_str(),
+                "AXFR",
+                "+noall",
+                "+answer",
+            ],
+            &[],
+        )
+        .exec_with_output(&mut |line| lines.push(line), &mut |line| errors.push(line))
+        .map_err(|e| format!("{} {}", e, errors.join("\n")))?;
+
+        // dig doesn't fail on refused transfers, it only prints a comment
+        if let Some(failure) = lines.iter().find(|line| line.contains("Transfer failed")) {
+            return Err(failure.to_string());
+        }
+
+        Ok(lines.iter().filter_map(|line| parse_zone_transfer_line(line)).collect())
+    }
+}
+
+fn write_file(path: &str, content: &str) -> Result<(), String> {
+    if let Some(parent_dir) = Path::new(path).parent() {
+        std::fs::create_dir_all(parent_dir).map_err(|e| e.to_string())?;
+    }
+
+    File::create(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+fn tsig_key_file_content(key_name: &str, algorithm: TsigAlgorithm, secret: &str) -> String {
+    format!(
+        "key \"{}\" {{\n  algorithm {};\n  secret \"{}\";\n}};\n",
+        key_name,
+        algorithm.to_bind_format(),
+        secret
+    )
+}
+
+fn nsupdate_script(nameserver: Ipv4Addr, port: u16, zone: &str, commands: &[String]) -> String {
+    let mut script = vec![format!("server {} {}", nameserver, port), format!("zone {}.", zone)];
+    script.extend(commands.iter().cloned());
+    script.push("send".to_string());
+
+    format!("{}\n", script.join("\n"))
+}
+
+fn record_value(record_type: DnsRecordType, content: &str) -> String {
+    match record_type {
+        DnsRecordType::Txt if !content.starts_with('"') => format!("\"{}\"", content.replace('"', "\\\"")),
+        DnsRecordType::Cname => format!("{}.", content.trim_end_matches('.')),
+        _ => content.to_string(),
+    }
+}
+
+/// Parses a `dig +noall +answer` line, e.g. `app.example.com. 300 IN A 1.2.3.4`.
+fn parse_zone_transfer_line(line: &str) -> Option<DnsRecord> {
+    let mut fields = line.split_whitespace();
+    let name = fields.next()?.trim_end_matches('.').to_string();
+    let ttl = fields.next()?.parse::<u32>().ok()?;
+    let _class = fields.next()?;
+    let record_type = match fields.next()? {
+        "A" => DnsRecordType::A,
+        "AAAA" => DnsRecordType::Aaaa,
+        "CNAME" => DnsRecordType::Cname,
+        "TXT" => DnsRecordType::Txt,
+        // SOA, NS and other record types are not managed by the engine
+        _ => return None,
+    };
+    let value = fields.collect::<Vec<&str>>().join(" ");
+
+    let content = match record_type {
+        // long TXT values are split in several quoted strings
+        DnsRecordType::Txt => value
+            .split("\" \"")
+            .collect::<String>()
+            .trim_matches('"')
+            .replace("\\\"", "\""),
+        DnsRecordType::Cname => value.trim_end_matches('.').to_string(),
+        _ => value,
+    };
+
+    Some(DnsRecord {
+        name,
+        record_type,
+        content,
+        ttl,
+        proxied: false,
+    })
+}
+
+impl DnsProvider for Rfc2136 {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn provider_name(&self) -> &str {
+        "rfc2136"
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Rfc2136
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn account(&self) -> &str {
+        &self.tsig_key_name
+    }
+
+    fn token(&self) -> &str {
+        &self.tsig_secret
+    }
+
+    fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    fn resolvers(&self) -> Vec<Ipv4Addr> {
+        vec![self.nameserver]
+    }
+
+    // records of a private zone are only known by its own nameserver
+    fn domain_check_nameservers(&self) -> Vec<SocketAddr> {
+        vec![SocketAddr::new(IpAddr::V4(self.nameserver), self.port)]
+    }
+
+    fn is_valid(&self) -> Result<(), DnsProviderError> {
+        if self.tsig_key_name.is_empty() || base64::decode(&self.tsig_secret).is_err() {
+            return Err(DnsProviderError::InvalidCredentials);
+        }
+
+        let domain = self.domain.to_string();
+        if self.zone.is_empty() || (domain != self.zone && !domain.ends_with(format!(".{}", self.zone).as_str())) {
+            return Err(DnsProviderError::CannotFindZone {
+                zone_name: self.zone.to_string(),
+                raw_error_message: format!("domain `{}` is not part of the zone", domain),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn configuration(&self) -> DnsProviderConfiguration {
+        DnsProviderConfiguration::Rfc2136 {
+            nameserver: self.nameserver,
+            port: self.port,
+            zone: self.zone.to_string(),
+            tsig_key_name: self.tsig_key_name.to_string(),
+            tsig_secret: self.tsig_secret.to_string(),
+            tsig_algorithm: self.tsig_algorithm,
+        }
+    }
+
+    fn upsert_record(&self, record: &DnsRecord) -> Result<DnsRecord, DnsProviderError> {
+        record.is_valid(&Domain::new(self.zone.to_string()))?;
+
+        let name = format!("{}.", record.name.trim_end_matches('.'));
+        let commands = vec![
+            format!("update delete {} {}", name, record.record_type),
+            format!(
+                "update add {} {} {} {}",
+                name,
+                record.ttl,
+                record.record_type,
+                record_value(record.record_type, record.content.as_str())
+            ),
+        ];
+
+        match self.nsupdate(&commands) {
+            Ok(_) => Ok(DnsRecord {
+                name: record.name.trim_end_matches('.').to_string(),
+                proxied: false,
+                ..record.clone()
+            }),
+            Err(raw_error_message) => Err(DnsProviderError::CannotUpsertRecord {
+                record_name: record.name.to_string(),
+                raw_error_message,
+            }),
+        }
+    }
+
+    fn delete_record(&self, record_name: &str, record_type: DnsRecordType) -> Result<(), DnsProviderError> {
+        // deleting a missing record set is a no-op for the nameserver
+        self.nsupdate(&[format!(
+            "update delete {}. {}",
+            record_name.trim_end_matches('.'),
+            record_type
+        )])
+        .map_err(|raw_error_message| DnsProviderError::CannotDeleteRecord {
+            record_name: record_name.to_string(),
+            raw_error_message,
+        })
+    }
+
+    fn list_records(&self) -> Result<Vec<DnsRecord>, DnsProviderError> {
+        self.transfer_zone()
+            .map_err(|raw_error_message| DnsProviderError::CannotListRecords {
+                zone_name: self.zone.to_string(),
+                raw_error_message,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::docker::Docker;
+
+    #[test]
+    fn test_tsig_key_file_content() {
+        // execute:
+        let content = tsig_key_file_content("qovery", TsigAlgorithm::HmacSha256, "c2VjcmV0");
+
+        // verify:
+        assert_eq!(
+            "key \"qovery\" {\n  algorithm hmac-sha256;\n  secret \"c2VjcmV0\";\n};\n",
+            content
+        );
+    }
+
+    #[test]
+    fn test_nsupdate_script() {
+        // setup:
+        let commands = vec![
+            "update delete _acme.example.com. TXT".to_string(),
+            format!(
+                "update add _acme.example.com. 60 TXT {}",
+                record_value(DnsRecordType::Txt, "token")
+            ),
+            format!(
+                "update add app.example.com. 60 CNAME {}",
+                record_value(DnsRecordType::Cname, "lb.example.com")
+            ),
+        ];
+
+        // execute:
+        let script = nsupdate_script(Ipv4Addr::new(10, 0, 0, 53), 53, "example.com", &commands);
+
+        // verify:
+        assert_eq!(
+            "server 10.0.0.53 53\nzone example.com.\nupdate delete _acme.example.com. TXT\nupdate add _acme.example.com. 60 TXT \"token\"\nupdate add app.example.com. 60 CNAME lb.example.com.\nsend\n",
+            script
+        );
+    }
+
+    #[test]
+    fn test_parse_zone_transfer_line() {
+        // execute & verify:
+        assert_eq!(
+            Some(DnsRecord::new("app.example.com", DnsRecordType::A, "1.2.3.4", 300)),
+            parse_zone_transfer_line("app.example.com.\t300\tIN\tA\t1.2.3.4")
+        );
+        assert_eq!(
+            Some(DnsRecord::new("www.example.com", DnsRecordType::Cname, "lb.example.com", 60)),
+            parse_zone_transfer_line("www.example.com. 60 IN CNAME lb.example.com.")
+        );
+        assert_eq!(
+            Some(DnsRecord::new(
+                "_acme.example.com",
+                DnsRecordType::Txt,
+                "first part second part",
+                60
+            )),
+            parse_zone_transfer_line("_acme.example.com. 60 IN TXT \"first part \" \"second part\"")
+        );
+        assert_eq!(
+            None,
+            parse_zone_transfer_line("example.com. 3600 IN SOA ns.example.com. admin.example.com. 1 3600 600 86400 60")
+        );
+        assert_eq!(None, parse_zone_transfer_line("; Transfer failed."));
+    }
+
+    #[test]
+    fn test_domain_check_nameservers_use_configured_port() {
+        // setup:
+        let rfc2136 = Rfc2136::new(
+            Context::new(
+                "organization_id".to_string(),
+                "cluster_id".to_string(),
+                "execution_id".to_string(),
+                "/tmp".to_string(),
+                "/tmp".to_string(),
+                true,
+                None,
+                vec![],
+                None,
+                Docker::new_with_options(false, None).unwrap(),
+            ),
+            "id",
+            "bind",
+            Domain::new("example.com".to_string()),
+            Ipv4Addr::new(10, 0, 0, 53),
+            5353,
+            "example.com",
+            "qovery",
+            "c2VjcmV0",
+            TsigAlgorithm::HmacSha256,
+        );
+
+        // execute & verify:
+        assert_eq!(
+            vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 53)), 5353)],
+            rfc2136.domain_check_nameservers()
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+use rusoto_core::credential::StaticProvider;
+use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
+use rusoto_route53::{
+    Change, ChangeBatch, ChangeResourceRecordSetsRequest, HostedZone, ListHostedZonesByNameRequest,
+    ListResourceRecordSetsRequest, ResourceRecord, ResourceRecordSet, Route53 as RusotoRoute53, Route53Client,
+};
+
+use crate::dns_provider::errors::DnsProviderError;
+use crate::dns_provider::{DnsProvider, DnsProviderConfiguration, DnsRecord, DnsRecordType, Kind};
+use crate::io_models::{Context, Domain};
+use crate::runtime::block_on;
+
+pub struct Route53 {
+    context: Context,
+    id: String,
+    name: String,
+    domain: Domain,
+    access_key_id: String,
+    secret_access_key: String,
+    region: String,
+    hosted_zone_id: Option<String>,
+}
+
+impl Route53 {
+    pub fn new(
+        context: Context,
+        id: &str,
+        name: &str,
+        domain: Domain,
+        access_key_id: &str,
+        secret_access_key: &str,
+        region: &str,
+        hosted_zone_id: Option<&str>,
+    ) -> Self {
+        Route53 {
+            context,
+            id: id.to_string(),
+            name: name.to_string(),
+            domain,
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            region: region.to_string(),
+            hosted_zone_id: hosted_zone_id.map(|id| id.to_string()),
+        }
+    }
+
+    fn get_route53_client(&self) -> Route53Client {
+        // Route53 is a global service, region is only used to sign requests
+        let region = RusotoRegion::from_str(self.region.as_str()).unwrap_or(RusotoRegion::UsEast1);
+        let client = Client::new_with(
+            StaticProvider::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None),
+            HttpClient::new().expect("unable to create new Http client"),
+        );
+
+        Route53Client::new_with_client(client, region)
+    }
+
+    /// Returns the configured hosted zone id, or looks up the hosted zone the domain belongs to.
+    pub fn hosted_zone_id(&self) -> Result<String, DnsProviderError> {
+        if let Some(hosted_zone_id) = &self.hosted_zone_id {
+            return Ok(hosted_zone_id.to_string());
+        }
+
+        let domain = self.domain.to_string();
+        let cannot_find_zone = |raw_error_message: String| DnsProviderError::CannotFindZone {
+            zone_name: domain.to_string(),
+            raw_error_message,
+        };
+
+        let route53_client = self.get_route53_client();
+        let mut hosted_zones = vec![];
+        let mut next_zone: Option<(Option<String>, Option<String>)> = None;
+
+        loop {
+            let (dns_name, hosted_zone_id) = next_zone.clone().unwrap_or((None, None));
+            let res = block_on(route53_client.list_hosted_zones_by_name(ListHostedZonesByNameRequest {
+                dns_name,
+                hosted_zone_id,
+                ..Default::default()
+            }))
+            .map_err(|e| cannot_find_zone(e.to_string()))?;
+
+            hosted_zones.extend(res.hosted_zones);
+
+            match res.is_truncated {
+                true => next_zone = Some((res.next_dns_name, res.next_hosted_zone_id)),
+                false => break,
+            }
+        }
+
+        match find_hosted_zone(&hosted_zones, domain.as_str()) {
+            Some(hosted_zone) => Ok(hosted_zone.id.trim_start_matches("/hostedzone/").to_string()),
+            None => Err(cannot_find_zone("no hosted zone matches this domain".to_string())),
+        }
+    }
+
+    fn list_record_sets(&self, hosted_zone_id: &str) -> Result<Vec<ResourceRecordSet>, String> {
+        let route53_client = self.get_route53_client();
+        let mut record_sets = vec![];
+        let mut start_record: Option<(Option<String>, Option<String>)> = None;
+
+        loop {
+            let (start_record_name, start_record_type) = start_record.clone().unwrap_or((None, None));
+            let res = block_on(route53_client.list_resource_record_sets(ListResourceRecordSetsRequest {
+                hosted_zone_id: hosted_zone_id.to_string(),
+                start_record_name,
+                start_record_type,
+                ..Default::default()
+            }))
+            .map_err(|e| e.to_string())?;
+
+            record_sets.extend(res.resource_record_sets);
+
+            match res.is_truncated {
+                true => start_record = Some((res.next_record_name, res.next_record_type)),
+                false => break,
+            }
+        }
+
+        Ok(record_sets)
+    }
+
+    fn change_record_set(
+        &self,
+        hosted_zone_id: &str,
+        action: &str,
+        record_set: ResourceRecordSet,
+    ) -> Result<(), String> {
+        block_on(
+            self.get_route53_client()
+                .change_resource_record_sets(ChangeResourceRecordSetsRequest {
+                    hosted_zone_id: hosted_zone_id.to_string(),
+                    change_batch: ChangeBatch {
+                        changes: vec![Change {
+                            action: action.to_string(),
+                            resource_record_set: record_set,
+                        }],
+                        comment: Some("managed by Qovery".to_string()),
+                    },
+                }),
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Picks the most specific hosted zone the domain belongs to, public zones first.
+fn find_hosted_zone<'a>(hosted_zones: &'a [HostedZone], domain: &str) -> Option<&'a HostedZone> {
+    let domain = to_fqdn(domain);
+
+    hosted_zones
+        .iter()
+        .filter(|zone| domain == zone.name || domain.ends_with(format!(".{}", zone.name).as_str()))
+        .max_by_key(|zone| {
+            let is_public = !matches!(&zone.config, Some(config) if config.private_zone == Some(true));
+            (zone.name.len(), is_public)
+        })
+}
+
+fn to_fqdn(name: &str) -> String {
+    format!("{}.", name.trim_end_matches('.'))
+}
+
+fn to_record_set(record: &DnsRecord) -> ResourceRecordSet {
+    let value = match record.record_type {
+        // TXT values have to be quoted
+        DnsRecordType::Txt if !record.content.starts_with('"') => format!("\"{}\"", record.content),
+        _ => record.content.to_string(),
+    };
+
+    ResourceRecordSet {
+        name: to_fqdn(record.name.as_str()),
+        type_: record.record_type.to_string(),
+        ttl: Some(record.ttl as i64),
+        resource_records: Some(vec![ResourceRecord { value }]),
+        ..Default::default()
+    }
+}
+
+fn from_record_set(record_set: ResourceRecordSet) -> Vec<DnsRecord> {
+    let record_type = match record_set.type_.as_str() {
+        "A" => DnsRecordType::A,
+        "AAAA" => DnsRecordType::Aaaa,
+        "CNAME" => DnsRecordType::Cname,
+        "TXT" => DnsRecordType::Txt,
+        // other record types are not managed by the engine
+        _ => return vec![],
+    };
+    // Route53 escapes wildcards in records names
+    let name = record_set.name.trim_end_matches('.').replace("\\052", "*");
+    let ttl = record_set.ttl.unwrap_or(0) as u32;
+
+    // alias records have no resource records, they can't be managed as plain records
+    record_set
+        .resource_records
+        .unwrap_or_default()
+        .into_iter()
+        .map(|r| DnsRecord {
+            name: name.to_string(),
+            record_type,
+            content: match record_type {
+                DnsRecordType::Txt => r.value.trim_matches('"').to_string(),
+                _ => r.value,
+            },
+            ttl,
+            proxied: false,
+        })
+        .collect()
+}
+
+impl DnsProvider for Route53 {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn provider_name(&self) -> &str {
+        "route53"
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Route53
+    }
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn account(&self) -> &str {
+        &self.access_key_id
+    }
+
+    fn token(&self) -> &str {
+        &self.secret_access_key
+    }
+
+    fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    fn resolvers(&self) -> Vec<Ipv4Addr> {
+        vec![Ipv4Addr::new(1, 1, 1, 1), Ipv4Addr::new(1, 0, 0, 1)]
+    }
+
+    fn is_valid(&self) -> Result<(), DnsProviderError> {
+        if self.access_key_id.is_empty() || self.secret_access_key.is_empty() {
+            Err(DnsProviderError::InvalidCredentials)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn configuration(&self) -> DnsProviderConfiguration {
+        DnsProviderConfiguration::Route53 {
+            access_key_id: self.access_key_id.to_string(),
+            secret_access_key: self.secret_access_key.to_string(),
+            region: self.region.to_string(),
+            hosted_zone_id: self.hosted_zone_id.clone(),
+        }
+    }
+
+    fn upsert_record(&self, record: &DnsRecord) -> Result<DnsRecord, DnsProviderError> {
+        record.is_valid(&self.domain.root_domain())?;
+
+        let hosted_zone_id = self.hosted_zone_id()?;
+        match self.change_record_set(hosted_zone_id.as_str(), "UPSERT", to_record_set(record)) {
+            Ok(_) => Ok(DnsRecord {
+                name: record.name.trim_end_matches('.').to_string(),
+                proxied: false,
+                ..record.clone()
+            }),
+            Err(raw_error_message) => Err(DnsProviderError::CannotUpsertRecord {
+                record_name: record.name.to_string(),
+                raw_error_message,
+            }),
+        }
+    }
+
+    fn delete_record(&self, record_name: &str, record_type: DnsRecordType) -> Result<(), DnsProviderError> {
+        let cannot_delete_record = |raw_error_message: String| DnsProviderError::CannotDeleteRecord {
+            record_name: record_name.to_string(),
+            raw_error_message,
+        };
+
+        let hosted_zone_id = self.hosted_zone_id()?;
+        let record_name = to_fqdn(record_name);
+        let record_type = record_type.to_string();
+
+        // deletion requires the exact current record set
+        let record_sets = self
+            .list_record_sets(hosted_zone_id.as_str())
+            .map_err(cannot_delete_record)?
+            .into_iter()
+            .filter(|r| r.name.replace("\\052", "*") == record_name && r.type_ == record_type);
+
+        for record_set in record_sets {
+            self.change_record_set(hosted_zone_id.as_str(), "DELETE", record_set)
+                .map_err(cannot_delete_record)?;
+        }
+
+        Ok(())
+    }
+
+    fn list_records(&self) -> Result<Vec<DnsRecord>, DnsProviderError> {
+        let hosted_zone_id = self.hosted_zone_id()?;
+
+        match self.list_record_sets(hosted_zone_id.as_str()) {
+            Ok(record_sets) => Ok(record_sets.into_iter().flat_map(from_record_set).collect()),
+            Err(raw_error_message) => Err(DnsProviderError::CannotListRecords {
+                zone_name: hosted_zone_id,
+                raw_error_message,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusoto_route53::HostedZoneConfig;
+
+    fn hosted_zone(id: &str, name: &str, private_zone: bool) -> HostedZone {
+        HostedZone {
+            id: format!("/hostedzone/{}", id),
+            name: name.to_string(),
+            config: Some(HostedZoneConfig {
+                comment: None,
+                private_zone: Some(private_zone),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_hosted_zone() {
+        // setup:
+        let hosted_zones = vec![
+            hosted_zone("ROOT", "example.com.", false),
+            hosted_zone("ROOT_PRIVATE", "example.com.", true),
+            hosted_zone("SUB", "cluster.example.com.", false),
+            hosted_zone("OTHER", "ample.com.", false),
+        ];
+
+        // execute & verify:
+        assert_eq!(
+            Some("/hostedzone/SUB"),
+            find_hosted_zone(&hosted_zones, "app.cluster.example.com").map(|z| z.id.as_str())
+        );
+        assert_eq!(
+            Some("/hostedzone/SUB"),
+            find_hosted_zone(&hosted_zones, "cluster.example.com").map(|z| z.id.as_str())
+        );
+        assert_eq!(
+            Some("/hostedzone/ROOT"),
+            find_hosted_zone(&hosted_zones, "www.example.com").map(|z| z.id.as_str())
+        );
+        assert_eq!(None, find_hosted_zone(&hosted_zones, "example.org").map(|z| z.id.as_str()));
+    }
+
+    #[test]
+    fn test_record_set_conversion() {
+        // setup:
+        let txt_record = DnsRecord::new("_qovery.example.com", DnsRecordType::Txt, "token", 300);
+        let a_record = DnsRecord::new("*.example.com", DnsRecordType::A, "1.2.3.4", 60);
+
+        // execute:
+        let txt_record_set = to_record_set(&txt_record);
+        let mut a_record_set = to_record_set(&a_record);
+        a_record_set.name = "\\052.example.com.".to_string();
+
+        // verify:
+        assert_eq!("_qovery.example.com.", txt_record_set.name);
+        assert_eq!("TXT", txt_record_set.type_);
+        assert_eq!(Some(300), txt_record_set.ttl);
+        assert_eq!(
+            Some(vec![ResourceRecord {
+                value: "\"token\"".to_string()
+            }]),
+            txt_record_set.resource_records
+        );
+        assert_eq!(vec![txt_record], from_record_set(txt_record_set));
+        assert_eq!(vec![a_record], from_record_set(a_record_set));
+        assert!(from_record_set(ResourceRecordSet {
+            name: "example.com.".to_string(),
+            type_: "MX".to_string(),
+            ..Default::default()
+        })
+        .is_empty());
+    }
+}
@@ -53,10 +53,19 @@ pub enum Tag {
     K8sHistory,
     K8sCannotCreateNamespace,
     K8sPodIsNotReady,
+    K8sJobFailed,
+    K8sCannotSuspendCronJob,
+    DeploymentHookFailed,
+    InvalidDeploymentHook,
+    UnknownNodePool,
+    PodSecurityPolicyViolation,
+    K8sCannotGetVolumeSnapshots,
+    VolumeSnapshotRestoreError,
     K8sNodeIsNotReadyWithTheRequestedVersion,
     K8sNodeIsNotReady,
     UnsupportedRegion,
     UnsupportedZone,
+    UnsupportedPublicPortProtocol,
     CannotFindRequiredBinary,
     SubnetsCountShouldBeEven,
     CannotGetOrCreateIamRole,
@@ -155,6 +164,14 @@ impl From<errors::Tag> for Tag {
             errors::Tag::K8sHistory => Tag::K8sHistory,
             errors::Tag::K8sCannotCreateNamespace => Tag::K8sCannotCreateNamespace,
             errors::Tag::K8sPodIsNotReady => Tag::K8sPodIsNotReady,
+            errors::Tag::K8sJobFailed => Tag::K8sJobFailed,
+            errors::Tag::K8sCannotSuspendCronJob => Tag::K8sCannotSuspendCronJob,
+            errors::Tag::DeploymentHookFailed => Tag::DeploymentHookFailed,
+            errors::Tag::InvalidDeploymentHook => Tag::InvalidDeploymentHook,
+            errors::Tag::UnknownNodePool => Tag::UnknownNodePool,
+            errors::Tag::PodSecurityPolicyViolation => Tag::PodSecurityPolicyViolation,
+            errors::Tag::K8sCannotGetVolumeSnapshots => Tag::K8sCannotGetVolumeSnapshots,
+            errors::Tag::VolumeSnapshotRestoreError => Tag::VolumeSnapshotRestoreError,
             errors::Tag::CannotFindRequiredBinary => Tag::CannotFindRequiredBinary,
             errors::Tag::SubnetsCountShouldBeEven => Tag::SubnetsCountShouldBeEven,
             errors::Tag::CannotGetOrCreateIamRole => Tag::CannotGetOrCreateIamRole,
@@ -179,6 +196,7 @@ impl From<errors::Tag> for Tag {
             errors::Tag::ObjectStorageCannotPutFileIntoBucket => Tag::ObjectStorageCannotPutFileIntoBucket,
             errors::Tag::UnsupportedRegion => Tag::UnsupportedRegion,
             errors::Tag::UnsupportedZone => Tag::UnsupportedZone,
+            errors::Tag::UnsupportedPublicPortProtocol => Tag::UnsupportedPublicPortProtocol,
             errors::Tag::K8sNodeIsNotReadyWithTheRequestedVersion => Tag::K8sNodeIsNotReadyWithTheRequestedVersion,
             errors::Tag::K8sNodeIsNotReady => Tag::K8sNodeIsNotReady,
             errors::Tag::NoClusterFound => Tag::NoClusterFound,
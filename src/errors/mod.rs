@@ -181,6 +181,8 @@ pub enum Tag {
     UnsupportedRegion,
     /// UnsupportedZone: represents an unsupported zone in region for the given cloud provider.
     UnsupportedZone,
+    /// UnsupportedPublicPortProtocol: represents a public port protocol the cluster load balancer can't expose.
+    UnsupportedPublicPortProtocol,
     /// CannotRetrieveKubernetesConfigFile: represents an error while trying to retrieve Kubernetes config file.
     CannotRetrieveClusterConfigFile,
     /// CannotCreateFile: represents an error while trying to create a file.
@@ -233,6 +235,22 @@ pub enum Tag {
     K8sCannotCreateNamespace,
     /// K8sPodIsNotReady: represents an error where the given pod is not ready.
     K8sPodIsNotReady,
+    /// K8sJobFailed: represents an error where the given job failed or did not complete in time.
+    K8sJobFailed,
+    /// K8sCannotSuspendCronJob: represents an error while trying to suspend a k8s cron job.
+    K8sCannotSuspendCronJob,
+    /// DeploymentHookFailed: represents an error where a pre or post deployment hook of a service failed.
+    DeploymentHookFailed,
+    /// InvalidDeploymentHook: represents an error where a pre or post deployment hook of a service is misconfigured.
+    InvalidDeploymentHook,
+    /// UnknownNodePool: represents an error where a service targets a node pool which doesn't exist in the cluster.
+    UnknownNodePool,
+    /// PodSecurityPolicyViolation: represents an error where a service security settings don't comply with the cluster pod security policy.
+    PodSecurityPolicyViolation,
+    /// K8sCannotGetVolumeSnapshots: represents an error while trying to get kubernetes volume snapshots.
+    K8sCannotGetVolumeSnapshots,
+    /// VolumeSnapshotRestoreError: represents an error while trying to restore a persistent volume from a volume snapshot.
+    VolumeSnapshotRestoreError,
     /// K8sNodeIsNotReadyInTheGivenVersion: represents an error where the given node is not ready in the given version.
     K8sNodeIsNotReadyWithTheRequestedVersion,
     /// K8sNodeIsNotReady: represents an error where the given node is not ready.
@@ -476,6 +494,7 @@ impl EngineError {
                     EngineErrorScope::Database(id, db_type, name) => Transmitter::Database(id, db_type, name),
                     EngineErrorScope::Application(id, name, commit) => Transmitter::Application(id, name, commit),
                     EngineErrorScope::Router(id, name) => Transmitter::Router(id, name),
+                    EngineErrorScope::Job(id, name, commit) => Transmitter::Job(id, name, commit),
                 },
             ),
             qovery_log_message: message.to_string(),
@@ -707,6 +726,37 @@ impl EngineError {
         )
     }
 
+    /// Creates new error for unsupported public port protocol.
+    ///
+    /// Cloud provider load balancer can't expose the requested protocol.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `cloud_provider`: Cloud provider full name.
+    /// * `protocol`: Raw requested protocol string.
+    /// * `port`: Requested port.
+    pub fn new_unsupported_public_port_protocol(
+        event_details: EventDetails,
+        cloud_provider: &str,
+        protocol: &str,
+        port: u16,
+    ) -> EngineError {
+        let message = format!(
+            "{} load balancers can't expose {} port `{}` publicly.",
+            cloud_provider, protocol, port
+        );
+        EngineError::new(
+            event_details,
+            Tag::UnsupportedPublicPortProtocol,
+            message.to_string(),
+            message,
+            None,
+            None, // TODO(documentation): Create a page entry to details this error
+            Some("Make this port private or use another protocol, please check provider's documentation.".to_string()),
+        )
+    }
+
     /// Creates new error: cannot get workspace directory.
     ///
     /// Error occured while trying to get workspace directory.
@@ -1408,6 +1458,230 @@ impl EngineError {
         )
     }
 
+    /// Creates new error for kubernetes job which failed or did not complete in time.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `job_name`: Job's name.
+    /// * `namespace`: Job's namespace.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_job_failed(
+        event_details: EventDetails,
+        job_name: String,
+        namespace: String,
+        raw_error: Option<CommandError>,
+    ) -> EngineError {
+        let message = format!("Error, job `{}` in namespace `{}` did not complete.", job_name, namespace);
+
+        EngineError::new(
+            event_details,
+            Tag::K8sJobFailed,
+            message.to_string(),
+            message,
+            raw_error,
+            None,
+            Some("Check the job logs to understand why it failed, or increase its maximum duration.".to_string()),
+        )
+    }
+
+    /// Creates new error for kubernetes cron job which cannot be suspended.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `cronjob_name`: Cron job's name.
+    /// * `namespace`: Cron job's namespace.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_cannot_suspend_cronjob(
+        event_details: EventDetails,
+        cronjob_name: String,
+        namespace: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!(
+            "Error, cannot suspend cron job `{}` in namespace `{}`.",
+            cronjob_name, namespace
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotSuspendCronJob,
+            message.to_string(),
+            message,
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
+    /// Creates new error for a service deployment hook which failed, aborting the deployment.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_name`: Name of the service the hook belongs to.
+    /// * `hook_name`: Hook's name, eq. `pre-deploy`.
+    /// * `raw_error`: Raw error message.
+    pub fn new_deployment_hook_failed(
+        event_details: EventDetails,
+        service_name: String,
+        hook_name: String,
+        raw_error: Option<CommandError>,
+    ) -> EngineError {
+        let message = format!("Error, {} hook of `{}` failed.", hook_name, service_name);
+
+        EngineError::new(
+            event_details,
+            Tag::DeploymentHookFailed,
+            message.to_string(),
+            message,
+            raw_error,
+            None,
+            Some("Check the hook logs to understand why it failed, the service is kept on or rolled back to its previous version.".to_string()),
+        )
+    }
+
+    /// Creates new error for a service deployment hook which can't be run due to its configuration.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_name`: Name of the service the hook belongs to.
+    /// * `hook_name`: Hook's name, eq. `pre-deploy`.
+    /// * `raw_error`: Raw error message.
+    pub fn new_invalid_deployment_hook(
+        event_details: EventDetails,
+        service_name: String,
+        hook_name: String,
+        raw_error: String,
+    ) -> EngineError {
+        let message = format!("Error, {} hook of `{}` is invalid: {}", hook_name, service_name, raw_error);
+
+        EngineError::new(
+            event_details,
+            Tag::InvalidDeploymentHook,
+            message.to_string(),
+            message,
+            None,
+            None,
+            Some("Fix the hook command or maximum duration.".to_string()),
+        )
+    }
+
+    /// Creates new error for a service targeting a node pool which doesn't exist in the cluster.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `node_pool`: Requested node pool name.
+    /// * `available_node_pools`: Node pools of the cluster.
+    pub fn new_unknown_node_pool(
+        event_details: EventDetails,
+        node_pool: String,
+        available_node_pools: Vec<String>,
+    ) -> EngineError {
+        let message = format!(
+            "Error, node pool `{}` doesn't exist in the cluster, available node pools: {}.",
+            node_pool,
+            available_node_pools.join(", ")
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::UnknownNodePool,
+            message.to_string(),
+            message,
+            None,
+            None,
+            Some("Target one of the cluster node pools or remove the node pool from the service settings.".to_string()),
+        )
+    }
+
+    /// Creates new error for a service which security settings don't comply with the cluster pod security policy.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `service_name`: Name of the non compliant service.
+    /// * `violations`: Policy requirements the service doesn't meet.
+    pub fn new_pod_security_policy_violation(
+        event_details: EventDetails,
+        service_name: String,
+        violations: Vec<String>,
+    ) -> EngineError {
+        let message = format!(
+            "Error, `{}` doesn't comply with the cluster pod security policy: {}.",
+            service_name,
+            violations.join(", ")
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::PodSecurityPolicyViolation,
+            message.to_string(),
+            message,
+            None,
+            None,
+            Some("Update the service security settings to meet the cluster pod security policy.".to_string()),
+        )
+    }
+
+    /// Creates new error for kubernetes volume snapshots which cannot be retrieved.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `namespace`: Namespace of the volume snapshots.
+    /// * `raw_error`: Raw error message.
+    pub fn new_k8s_cannot_get_volume_snapshots(
+        event_details: EventDetails,
+        namespace: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!("Error, cannot get volume snapshots in namespace `{}`.", namespace);
+
+        EngineError::new(
+            event_details,
+            Tag::K8sCannotGetVolumeSnapshots,
+            message.to_string(),
+            message,
+            Some(raw_error),
+            None,
+            None,
+        )
+    }
+
+    /// Creates new error for a persistent volume which cannot be restored from a volume snapshot.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `volume_name`: Name of the persistent volume claim to restore.
+    /// * `snapshot_name`: Name of the volume snapshot to restore from.
+    /// * `raw_error`: Raw error message.
+    pub fn new_volume_snapshot_restore_error(
+        event_details: EventDetails,
+        volume_name: String,
+        snapshot_name: String,
+        raw_error: CommandError,
+    ) -> EngineError {
+        let message = format!(
+            "Error, cannot restore volume `{}` from snapshot `{}`.",
+            volume_name, snapshot_name
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::VolumeSnapshotRestoreError,
+            message.to_string(),
+            message,
+            Some(raw_error),
+            None,
+            Some("Check the snapshot exists and is ready to use, then deploy the application again.".to_string()),
+        )
+    }
+
     /// Creates new error for kubernetes node not being ready with the requested version.
     ///
     /// Arguments:
@@ -1992,6 +2266,37 @@ impl EngineError {
         )
     }
 
+    /// Creates new error: the ingress controller doesn't support the Kubernetes version.
+    ///
+    /// Arguments:
+    ///
+    /// * `event_details`: Error linked event details.
+    /// * `kubernetes_version`: Requested Kubernetes version.
+    /// * `ingress_controller_version`: Bundled ingress controller version.
+    pub fn new_ingress_controller_unsupported_kubernetes_version(
+        event_details: EventDetails,
+        kubernetes_version: String,
+        ingress_controller_version: String,
+    ) -> EngineError {
+        let message = format!(
+            "Kubernetes version `{}` is not supported by the ingress-nginx controller `{}`, it doesn't watch `networking.k8s.io/v1` Ingress.",
+            kubernetes_version, ingress_controller_version
+        );
+
+        EngineError::new(
+            event_details,
+            Tag::UnsupportedVersion,
+            message.to_string(),
+            message,
+            None,
+            None,
+            Some(
+                "Keep the cluster on Kubernetes 1.21 until the ingress-nginx controller is upgraded to 1.0."
+                    .to_string(),
+            ),
+        )
+    }
+
     /// Creates new error while trying to get cluster.
     ///
     /// Arguments:
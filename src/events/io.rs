@@ -211,6 +211,11 @@ pub enum Transmitter {
         id: TransmitterId,
         name: TransmitterName,
     },
+    Job {
+        id: TransmitterId,
+        name: TransmitterName,
+        commit: TransmitterVersion,
+    },
 }
 
 impl From<events::Transmitter> for Transmitter {
@@ -226,6 +231,7 @@ impl From<events::Transmitter> for Transmitter {
             events::Transmitter::Database(id, db_type, name) => Transmitter::Database { id, db_type, name },
             events::Transmitter::Application(id, name, commit) => Transmitter::Application { id, name, commit },
             events::Transmitter::Router(id, name) => Transmitter::Router { id, name },
+            events::Transmitter::Job(id, name, commit) => Transmitter::Job { id, name, commit },
         }
     }
 }
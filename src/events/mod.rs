@@ -352,6 +352,8 @@ pub enum Transmitter {
     Application(TransmitterId, TransmitterName, TransmitterVersion),
     /// Router: router engine part.
     Router(TransmitterId, TransmitterName),
+    /// Job: job engine part.
+    Job(TransmitterId, TransmitterName, TransmitterVersion),
 }
 
 impl Display for Transmitter {
@@ -371,6 +373,7 @@ impl Display for Transmitter {
                 Transmitter::Application(id, name, version) =>
                     format!("application({}, {}, commit: {})", id, name, version),
                 Transmitter::Router(id, name) => format!("router({}, {})", id, name),
+                Transmitter::Job(id, name, version) => format!("job({}, {}, commit: {})", id, name, version),
             }
         )
     }
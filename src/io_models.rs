@@ -16,6 +16,9 @@ use uuid::Uuid;
 
 use crate::build_platform::{Build, Credentials, GitRepository, Image, SshKey};
 use crate::cloud_provider::environment::Environment;
+use crate::cloud_provider::kubernetes::validate_scheduling;
+use crate::cloud_provider::network_policy::EnvironmentNetworkPolicy;
+use crate::cloud_provider::pod_security::PodSecuritySettings;
 use crate::cloud_provider::service::{DatabaseOptions, RouterService};
 use crate::cloud_provider::Kind as CPKind;
 use crate::cloud_provider::{service, CloudProvider};
@@ -27,9 +30,11 @@ use crate::models::application::{ApplicationError, ApplicationService};
 use crate::models::aws::{AwsAppExtraSettings, AwsRouterExtraSettings, AwsStorageType};
 use crate::models::database::{Container, DatabaseError, Managed, MongoDB, MySQL, PostgresSQL, Redis};
 use crate::models::digital_ocean::{DoAppExtraSettings, DoRouterExtraSettings, DoStorageType};
+use crate::models::job::{JobError, JobService, JobSettings};
 use crate::models::router::RouterError;
 use crate::models::scaleway::{ScwAppExtraSettings, ScwRouterExtraSettings, ScwStorageType};
 use crate::models::types::{CloudProvider as CP, VersionsNumber, AWS, DO, SCW};
+use crate::object_storage::encryption::ObjectStorageEncryption;
 use crate::utilities::to_short_id;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -90,7 +95,11 @@ pub struct EnvironmentRequest {
     pub applications: Vec<Application>,
     pub routers: Vec<Router>,
     pub databases: Vec<Database>,
+    #[serde(default)]
+    pub jobs: Vec<Job>,
     pub clone_from_environment_id: Option<String>,
+    #[serde(default)]
+    pub network_policy: EnvironmentNetworkPolicy,
 }
 
 impl EnvironmentRequest {
@@ -101,6 +110,10 @@ impl EnvironmentRequest {
         container_registry: &ContainerRegistryInfo,
         logger: Box<dyn Logger>,
     ) -> Result<Environment, ApplicationError> {
+        self.network_policy
+            .validate()
+            .map_err(|e| ApplicationError::InvalidConfig(format!("network policy {}", e)))?;
+
         let mut applications = Vec::with_capacity(self.applications.len());
         for app in &self.applications {
             match app.to_application_domain(context, app.to_build(container_registry), cloud_provider, logger.clone()) {
@@ -133,6 +146,17 @@ impl EnvironmentRequest {
             }
         }
 
+        let mut jobs = Vec::with_capacity(self.jobs.len());
+        for job in &self.jobs {
+            match job.to_job_domain(context, job.to_build(container_registry), cloud_provider, logger.clone()) {
+                Ok(job) => jobs.push(job),
+                Err(err) => {
+                    //FIXME: propagate the correct Error
+                    return Err(ApplicationError::InvalidConfig(format!("{}", err)));
+                }
+            }
+        }
+
         Ok(Environment::new(
             self.id.as_str(),
             self.project_id.as_str(),
@@ -142,6 +166,8 @@ impl EnvironmentRequest {
             applications,
             routers,
             databases,
+            jobs,
+            self.network_policy.clone(),
         ))
     }
 }
@@ -186,6 +212,204 @@ pub struct Port {
     pub name: Option<String>,
     pub publicly_accessible: bool,
     pub protocol: Protocol,
+    /// Public TCP and UDP ports are only exposed once opted in, each protocol gets its own billed cloud load balancer.
+    #[serde(default)]
+    pub expose_with_load_balancer: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum ProbeScheme {
+    #[default]
+    HTTP,
+    HTTPS,
+}
+
+impl ProbeScheme {
+    pub fn to_k8s_format(&self) -> &str {
+        match self {
+            ProbeScheme::HTTP => "HTTP",
+            ProbeScheme::HTTPS => "HTTPS",
+        }
+    }
+}
+
+/// How Kubernetes checks an application container, ports default to the application public port.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProbeType {
+    Http {
+        path: String,
+        #[serde(default)]
+        port: Option<u16>,
+        #[serde(default)]
+        scheme: ProbeScheme,
+        /// Use BTreeMap to get Hash trait which is not available on HashMap
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    Tcp {
+        #[serde(default)]
+        port: Option<u16>,
+    },
+    Exec {
+        command: Vec<String>,
+    },
+    None,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(default)]
+pub struct Probe {
+    #[serde(flatten)]
+    pub probe_type: ProbeType,
+    /// Defaults to `deployment_delay_start_time_sec` when not set
+    pub initial_delay_seconds: Option<u32>,
+    pub period_seconds: u32,
+    pub timeout_seconds: u32,
+    pub success_threshold: u32,
+    pub failure_threshold: u32,
+}
+
+impl Probe {
+    fn with_period_seconds(period_seconds: u32) -> Self {
+        Probe {
+            period_seconds,
+            ..Probe::default()
+        }
+    }
+}
+
+impl Default for Probe {
+    fn default() -> Self {
+        Probe {
+            probe_type: ProbeType::Tcp { port: None },
+            initial_delay_seconds: None,
+            period_seconds: 10,
+            timeout_seconds: 1,
+            success_threshold: 1,
+            failure_threshold: 3,
+        }
+    }
+}
+
+fn default_readiness_probe() -> Probe {
+    Probe::with_period_seconds(10)
+}
+
+fn default_liveness_probe() -> Probe {
+    Probe::with_period_seconds(20)
+}
+
+/// Where a custom autoscaling metric comes from, both are served by prometheus-adapter.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoscalingMetricSource {
+    /// Metric describing the application pods, like requests per second
+    Pods,
+    /// Metric not related to Kubernetes objects, like a queue depth
+    External,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AutoscalingMetric {
+    pub source: AutoscalingMetricSource,
+    pub name: String,
+    /// Labels selecting the metric series, only used by external metrics
+    #[serde(default)]
+    pub selector: BTreeMap<String, String>,
+    /// Average value per pod as a Kubernetes quantity, eq. `100` or `500m`
+    pub target_average_value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ScalingPolicyType {
+    Pods,
+    Percent,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ScalingSelectPolicy {
+    Max,
+    Min,
+    Disabled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ScalingPolicy {
+    pub policy_type: ScalingPolicyType,
+    pub value: u32,
+    pub period_seconds: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[serde(default)]
+pub struct ScalingRules {
+    pub stabilization_window_seconds: Option<u32>,
+    pub select_policy: Option<ScalingSelectPolicy>,
+    pub policies: Vec<ScalingPolicy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(default)]
+pub struct AutoscalingSettings {
+    pub cpu_average_utilization_percent: Option<u32>,
+    pub memory_average_utilization_percent: Option<u32>,
+    pub metrics: Vec<AutoscalingMetric>,
+    /// Kubernetes defaults apply when not set
+    pub scale_up: Option<ScalingRules>,
+    /// Kubernetes defaults apply when not set
+    pub scale_down: Option<ScalingRules>,
+}
+
+impl Default for AutoscalingSettings {
+    fn default() -> Self {
+        AutoscalingSettings {
+            cpu_average_utilization_percent: Some(60),
+            memory_average_utilization_percent: None,
+            metrics: vec![],
+            scale_up: None,
+            scale_down: None,
+        }
+    }
+}
+
+/// Spreading of the application pods over the cluster nodes.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PodAntiAffinity {
+    /// At most one pod per node, pods stay Pending when there are more instances than nodes
+    #[default]
+    Required,
+    /// Pods are spread on different nodes when possible
+    Preferred,
+    None,
+}
+
+impl PodAntiAffinity {
+    pub fn to_template_format(&self) -> &str {
+        match self {
+            PodAntiAffinity::Required => "required",
+            PodAntiAffinity::Preferred => "preferred",
+            PodAntiAffinity::None => "none",
+        }
+    }
+}
+
+/// Run in the container before it gets the termination signal, to let it drain its connections.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PreStopHook {
+    /// Requires a `sleep` binary in the image
+    Sleep {
+        duration_in_sec: u32,
+    },
+    Exec {
+        command: Vec<String>,
+    },
+}
+
+fn default_termination_grace_period_seconds() -> u32 {
+    60
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -194,6 +418,33 @@ pub struct ApplicationAdvancedSettings {
     pub deployment_delay_start_time_sec: u32,
     #[serde(alias = "build.timeout_max_sec")]
     pub build_timeout_max_sec: u32,
+    #[serde(default = "default_readiness_probe")]
+    pub readiness_probe: Probe,
+    #[serde(default = "default_liveness_probe")]
+    pub liveness_probe: Probe,
+    /// Holds readiness and liveness probes until it succeeds, for applications with a slow start
+    #[serde(default)]
+    pub startup_probe: Option<Probe>,
+    /// Only used when min and max instances differ
+    #[serde(default)]
+    pub autoscaling: AutoscalingSettings,
+    /// Pods created above the desired number during a rollout, a number or a percentage like `25%`
+    #[serde(default, alias = "deployment.max_surge")]
+    pub rolling_update_max_surge: Option<String>,
+    /// Pods which can be unavailable during a rollout, a number or a percentage like `25%`
+    #[serde(default, alias = "deployment.max_unavailable")]
+    pub rolling_update_max_unavailable: Option<String>,
+    #[serde(
+        default = "default_termination_grace_period_seconds",
+        alias = "deployment.termination_grace_period_seconds"
+    )]
+    pub termination_grace_period_seconds: u32,
+    #[serde(default)]
+    pub pre_stop_hook: Option<PreStopHook>,
+    #[serde(default, alias = "deployment.antiaffinity.pod")]
+    pub pod_anti_affinity: PodAntiAffinity,
+    #[serde(default)]
+    pub security: PodSecuritySettings,
 }
 
 impl Default for ApplicationAdvancedSettings {
@@ -201,6 +452,16 @@ impl Default for ApplicationAdvancedSettings {
         ApplicationAdvancedSettings {
             deployment_delay_start_time_sec: 30,
             build_timeout_max_sec: 30 * 60, // 30min
+            readiness_probe: default_readiness_probe(),
+            liveness_probe: default_liveness_probe(),
+            startup_probe: None,
+            autoscaling: AutoscalingSettings::default(),
+            rolling_update_max_surge: None,
+            rolling_update_max_unavailable: None,
+            termination_grace_period_seconds: default_termination_grace_period_seconds(),
+            pre_stop_hook: None,
+            pod_anti_affinity: PodAntiAffinity::default(),
+            security: PodSecuritySettings::default(),
         }
     }
 }
@@ -230,6 +491,12 @@ pub struct Application {
     pub environment_vars: BTreeMap<String, String>,
     #[serde(default)]
     pub advanced_settings: ApplicationAdvancedSettings,
+    #[serde(default)]
+    pub lifecycle_hooks: LifecycleHooks,
+    #[serde(default)]
+    pub mounted_files: Vec<MountedFile>,
+    #[serde(default)]
+    pub scheduling: Scheduling,
 }
 
 impl Application {
@@ -259,6 +526,9 @@ impl Application {
                 self.storage.iter().map(|s| s.to_aws_storage()).collect::<Vec<_>>(),
                 environment_variables,
                 self.advanced_settings.clone(),
+                self.lifecycle_hooks.clone(),
+                self.mounted_files.clone(),
+                self.scheduling.clone(),
                 AwsAppExtraSettings {},
                 listeners,
                 logger.clone(),
@@ -278,6 +548,9 @@ impl Application {
                 self.storage.iter().map(|s| s.to_do_storage()).collect::<Vec<_>>(),
                 environment_variables,
                 self.advanced_settings.clone(),
+                self.lifecycle_hooks.clone(),
+                self.mounted_files.clone(),
+                self.scheduling.clone(),
                 DoAppExtraSettings {},
                 listeners,
                 logger.clone(),
@@ -297,6 +570,9 @@ impl Application {
                 self.storage.iter().map(|s| s.to_scw_storage()).collect::<Vec<_>>(),
                 environment_variables,
                 self.advanced_settings.clone(),
+                self.lifecycle_hooks.clone(),
+                self.mounted_files.clone(),
+                self.scheduling.clone(),
                 ScwAppExtraSettings {},
                 listeners,
                 logger.clone(),
@@ -304,20 +580,303 @@ impl Application {
         }
     }
 
+    pub fn to_build(&self, registry_url: &ContainerRegistryInfo) -> Build {
+        GitBuildSource {
+            long_id: &self.long_id,
+            name: &self.name,
+            git_url: &self.git_url,
+            git_credentials: &self.git_credentials,
+            commit_id: &self.commit_id,
+            dockerfile_path: &self.dockerfile_path,
+            buildpack_language: &self.buildpack_language,
+            root_path: &self.root_path,
+            environment_vars: &self.environment_vars,
+        }
+        .to_build(registry_url)
+    }
+}
+
+fn default_lifecycle_hook_max_duration_in_sec() -> u32 {
+    600
+}
+
+/// Command run to completion with the application image, eq. `["rails", "db:migrate"]`.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LifecycleHook {
+    /// Passed as arguments to the image entrypoint
+    pub command: Vec<String>,
+    #[serde(default = "default_lifecycle_hook_max_duration_in_sec")]
+    pub max_duration_in_sec: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct LifecycleHooks {
+    /// Run before the new version is rolled out, the deployment is aborted if it fails
+    #[serde(default)]
+    pub pre_deploy: Option<LifecycleHook>,
+    /// Run once the new version is rolled out
+    #[serde(default)]
+    pub post_deploy: Option<LifecycleHook>,
+}
+
+fn default_mounted_file_mode() -> u32 {
+    0o644
+}
+
+/// File mounted read only in the application containers, without hiding the other files of its directory.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct MountedFile {
+    /// Absolute path of the file in the containers, eq. `/etc/nginx/nginx.conf`
+    pub mount_path: String,
+    /// base64 encoded content
+    pub content: String,
+    /// Unix permissions of the file, eq. `420` for `0644`
+    #[serde(default = "default_mounted_file_mode")]
+    pub mode: u32,
+    /// Stored in a Secret instead of a ConfigMap
+    #[serde(default)]
+    pub is_secret: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TolerationOperator {
+    #[default]
+    Equal,
+    Exists,
+}
+
+impl TolerationOperator {
+    pub fn to_k8s_format(&self) -> &str {
+        match self {
+            TolerationOperator::Equal => "Equal",
+            TolerationOperator::Exists => "Exists",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TaintEffect {
+    NoSchedule,
+    PreferNoSchedule,
+    NoExecute,
+}
+
+impl TaintEffect {
+    pub fn to_k8s_format(&self) -> &str {
+        match self {
+            TaintEffect::NoSchedule => "NoSchedule",
+            TaintEffect::PreferNoSchedule => "PreferNoSchedule",
+            TaintEffect::NoExecute => "NoExecute",
+        }
+    }
+}
+
+/// Allows pods to be scheduled on nodes with a matching taint.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Toleration {
+    /// Tolerates every taint when empty, with the `Exists` operator
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub operator: TolerationOperator,
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Tolerates every effect when not set
+    #[serde(default)]
+    pub effect: Option<TaintEffect>,
+    /// How long pods stay on a node after a `NoExecute` taint is added, forever when not set
+    #[serde(default)]
+    pub toleration_seconds: Option<u32>,
+}
+
+/// Constraints on the nodes a service pods are scheduled on, pods can land on any node by default.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[serde(default)]
+pub struct Scheduling {
+    /// Labels the nodes must have
+    pub node_selector: BTreeMap<String, String>,
+    pub tolerations: Vec<Toleration>,
+    /// Name of the cluster node group the pods are pinned to
+    pub node_pool: Option<String>,
+}
+
+/// When a job runs: once on each deployment of its environment, or on a cron schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobSchedule {
+    OnDeploy,
+    /// Standard 5 fields cron expression, eq. `*/15 * * * *`, evaluated in UTC
+    Cron {
+        schedule: String,
+    },
+}
+
+/// What to do when a cron job run is due while the previous one is still running.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum JobConcurrencyPolicy {
+    Allow,
+    #[default]
+    Forbid,
+    Replace,
+}
+
+impl JobConcurrencyPolicy {
+    pub fn to_k8s_format(&self) -> &str {
+        match self {
+            JobConcurrencyPolicy::Allow => "Allow",
+            JobConcurrencyPolicy::Forbid => "Forbid",
+            JobConcurrencyPolicy::Replace => "Replace",
+        }
+    }
+}
+
+fn default_job_max_duration_in_sec() -> u32 {
+    3600
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct Job {
+    pub long_id: Uuid,
+    pub name: String,
+    pub action: Action,
+    pub git_url: String,
+    pub git_credentials: Option<GitCredentials>,
+    pub branch: String,
+    pub commit_id: String,
+    pub dockerfile_path: Option<String>,
+    pub buildpack_language: Option<String>,
+    #[serde(default = "default_root_path_value")]
+    pub root_path: String,
+    pub schedule: JobSchedule,
+    #[serde(default)]
+    pub concurrency_policy: JobConcurrencyPolicy,
+    /// Number of retries before considering a run as failed
+    #[serde(default)]
+    pub max_nb_restart: u32,
+    #[serde(default = "default_job_max_duration_in_sec")]
+    pub max_duration_in_sec: u32,
+    /// Passed as arguments to the image entrypoint, the image command is used if empty
+    #[serde(default)]
+    pub command: Vec<String>,
+    #[serde(default)]
+    pub security: PodSecuritySettings,
+    pub total_cpus: String,
+    pub cpu_burst: String,
+    pub total_ram_in_mib: u32,
+    /// Key is a String, Value is a base64 encoded String
+    /// Use BTreeMap to get Hash trait which is not available on HashMap
+    pub environment_vars: BTreeMap<String, String>,
+}
+
+impl Job {
+    pub fn to_job_domain(
+        &self,
+        context: &Context,
+        build: Build,
+        cloud_provider: &dyn CloudProvider,
+        logger: Box<dyn Logger>,
+    ) -> Result<Box<dyn JobService>, JobError> {
+        let environment_variables = to_environment_variable(&self.environment_vars);
+        let listeners = cloud_provider.listeners().clone();
+        let settings = JobSettings {
+            schedule: self.schedule.clone(),
+            concurrency_policy: self.concurrency_policy,
+            max_nb_restart: self.max_nb_restart,
+            max_duration_in_sec: self.max_duration_in_sec,
+            command: self.command.clone(),
+            security: self.security.clone(),
+        };
+
+        match cloud_provider.kind() {
+            CPKind::Aws => Ok(Box::new(models::job::Job::<AWS>::new(
+                context.clone(),
+                self.long_id,
+                self.action.to_service_action(),
+                self.name.as_str(),
+                settings,
+                self.total_cpus.clone(),
+                self.cpu_burst.clone(),
+                self.total_ram_in_mib,
+                build,
+                environment_variables,
+                listeners,
+                logger,
+            )?)),
+            CPKind::Do => Ok(Box::new(models::job::Job::<DO>::new(
+                context.clone(),
+                self.long_id,
+                self.action.to_service_action(),
+                self.name.as_str(),
+                settings,
+                self.total_cpus.clone(),
+                self.cpu_burst.clone(),
+                self.total_ram_in_mib,
+                build,
+                environment_variables,
+                listeners,
+                logger,
+            )?)),
+            CPKind::Scw => Ok(Box::new(models::job::Job::<SCW>::new(
+                context.clone(),
+                self.long_id,
+                self.action.to_service_action(),
+                self.name.as_str(),
+                settings,
+                self.total_cpus.clone(),
+                self.cpu_burst.clone(),
+                self.total_ram_in_mib,
+                build,
+                environment_variables,
+                listeners,
+                logger,
+            )?)),
+        }
+    }
+
+    pub fn to_build(&self, registry_url: &ContainerRegistryInfo) -> Build {
+        GitBuildSource {
+            long_id: &self.long_id,
+            name: &self.name,
+            git_url: &self.git_url,
+            git_credentials: &self.git_credentials,
+            commit_id: &self.commit_id,
+            dockerfile_path: &self.dockerfile_path,
+            buildpack_language: &self.buildpack_language,
+            root_path: &self.root_path,
+            environment_vars: &self.environment_vars,
+        }
+        .to_build(registry_url)
+    }
+}
+
+/// Git repository an image is built from, shared by applications and jobs.
+struct GitBuildSource<'a> {
+    long_id: &'a Uuid,
+    name: &'a str,
+    git_url: &'a str,
+    git_credentials: &'a Option<GitCredentials>,
+    commit_id: &'a str,
+    dockerfile_path: &'a Option<String>,
+    buildpack_language: &'a Option<String>,
+    root_path: &'a str,
+    environment_vars: &'a BTreeMap<String, String>,
+}
+
+impl GitBuildSource<'_> {
     fn to_image(&self, cr_info: &ContainerRegistryInfo) -> Image {
         Image {
-            application_id: to_short_id(&self.long_id),
-            name: (cr_info.get_image_name)(&self.name),
+            application_id: to_short_id(self.long_id),
+            name: (cr_info.get_image_name)(self.name),
             tag: "".to_string(), // It needs to be compute after creation
-            commit_id: self.commit_id.clone(),
+            commit_id: self.commit_id.to_string(),
             registry_name: cr_info.registry_name.clone(),
             registry_url: cr_info.endpoint.clone(),
             registry_docker_json_config: cr_info.registry_docker_json_config.clone(),
-            repository_name: (cr_info.get_repository_name)(&self.name),
+            repository_name: (cr_info.get_repository_name)(self.name),
         }
     }
 
-    pub fn to_build(&self, registry_url: &ContainerRegistryInfo) -> Build {
+    fn to_build(&self, registry_url: &ContainerRegistryInfo) -> Build {
         // Retrieve ssh keys from env variables
         const ENV_GIT_PREFIX: &str = "GIT_SSH_KEY";
         let env_ssh_keys: Vec<(String, String)> = self
@@ -378,7 +937,7 @@ impl Application {
         });
 
         //FIXME: Return a result the function
-        let url = Url::parse(&self.git_url).unwrap_or_else(|_| Url::parse("https://invalid-git-url.com").unwrap());
+        let url = Url::parse(self.git_url).unwrap_or_else(|_| Url::parse("https://invalid-git-url.com").unwrap());
 
         let mut disable_build_cache = false;
         let mut build = Build {
@@ -389,7 +948,7 @@ impl Application {
                     password: credentials.access_token.clone(),
                 }),
                 ssh_keys,
-                commit_id: self.commit_id.clone(),
+                commit_id: self.commit_id.to_string(),
                 dockerfile_path,
                 root_path,
                 buildpack_language: self.buildpack_language.clone(),
@@ -451,6 +1010,9 @@ pub struct Storage {
     pub size_in_gib: u16,
     pub mount_point: String,
     pub snapshot_retention_in_days: u16,
+    /// Name of the application volume snapshot to restore the storage from on the next deployment
+    #[serde(default)]
+    pub restore_from_snapshot: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -476,6 +1038,7 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 
@@ -487,6 +1050,7 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 
@@ -498,6 +1062,7 @@ impl Storage {
             size_in_gib: self.size_in_gib,
             mount_point: self.mount_point.clone(),
             snapshot_retention_in_days: self.snapshot_retention_in_days,
+            restore_from_snapshot: self.restore_from_snapshot.clone(),
         }
     }
 }
@@ -515,6 +1080,8 @@ pub struct Router {
     pub sticky_sessions_enabled: bool,
     pub custom_domains: Vec<CustomDomain>,
     pub routes: Vec<Route>,
+    #[serde(default)]
+    pub advanced_settings: RouterAdvancedSettings,
 }
 
 impl Router {
@@ -530,6 +1097,8 @@ impl Router {
             .map(|x| crate::cloud_provider::models::CustomDomain {
                 domain: x.domain.clone(),
                 target_domain: x.target_domain.clone(),
+                verification_token: x.verification_token(context.organization_id()),
+                certificate: x.certificate.clone(),
             })
             .collect::<Vec<_>>();
 
@@ -539,6 +1108,9 @@ impl Router {
             .map(|x| crate::cloud_provider::models::Route {
                 path: x.path.clone(),
                 application_name: x.application_name.clone(),
+                rewrite: x.rewrite.clone(),
+                backend_protocol: x.backend_protocol,
+                weighted_applications: x.weighted_applications.clone(),
             })
             .collect::<Vec<_>>();
 
@@ -555,6 +1127,7 @@ impl Router {
                     custom_domains,
                     routes,
                     self.sticky_sessions_enabled,
+                    self.advanced_settings.clone(),
                     AwsRouterExtraSettings {},
                     listeners,
                     logger,
@@ -571,6 +1144,7 @@ impl Router {
                     custom_domains,
                     routes,
                     self.sticky_sessions_enabled,
+                    self.advanced_settings.clone(),
                     DoRouterExtraSettings {},
                     listeners,
                     logger,
@@ -587,6 +1161,7 @@ impl Router {
                     custom_domains,
                     routes,
                     self.sticky_sessions_enabled,
+                    self.advanced_settings.clone(),
                     ScwRouterExtraSettings {},
                     listeners,
                     logger,
@@ -601,12 +1176,160 @@ impl Router {
 pub struct CustomDomain {
     pub domain: String,
     pub target_domain: String,
+    /// User supplied certificate, Let's Encrypt is used when not set
+    #[serde(default)]
+    pub certificate: Option<CustomDomainCertificate>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct CustomDomainCertificate {
+    /// PEM certificates, starting with the domain certificate followed by its intermediate CAs
+    pub certificate_chain_pem: String,
+    pub private_key_pem: String,
+}
+
+impl CustomDomain {
+    /// TXT record the domain owner has to create to prove the domain ownership.
+    pub fn verification_record_name(&self) -> String {
+        crate::cloud_provider::models::custom_domain_verification_record_name(self.domain.as_str())
+    }
+
+    /// Value expected in the verification TXT record, unique per organization and domain.
+    pub fn verification_token(&self, organization_id: &str) -> String {
+        format!(
+            "qovery-verification={}",
+            crate::crypto::to_sha1(format!("{}/{}", organization_id, self.domain.trim_end_matches('.')).as_str())
+        )
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 pub struct Route {
     pub path: String,
     pub application_name: String,
+    #[serde(default)]
+    pub rewrite: Option<RouteRewrite>,
+    #[serde(default)]
+    pub backend_protocol: RouteBackendProtocol,
+    /// Splits the route traffic between `application_name` and another application, weights total 100
+    #[serde(default)]
+    pub weighted_applications: Vec<RouteWeightedApplication>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RouteWeightedApplication {
+    pub application_name: String,
+    /// Percentage of the route requests sent to the application
+    pub weight: u32,
+}
+
+/// Protocol spoken by the application behind a route, the public port has to be an HTTP port.
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug, Default)]
+pub enum RouteBackendProtocol {
+    #[default]
+    HTTP,
+    HTTPS,
+    GRPC,
+    GRPCS,
+    /// WebSocket over HTTP, connections are kept open longer than HTTP requests
+    WS,
+}
+
+impl RouteBackendProtocol {
+    /// Value of the ingress-nginx `backend-protocol` annotation
+    pub fn to_nginx_format(&self) -> &str {
+        match self {
+            RouteBackendProtocol::HTTP | RouteBackendProtocol::WS => "HTTP",
+            RouteBackendProtocol::HTTPS => "HTTPS",
+            RouteBackendProtocol::GRPC => "GRPC",
+            RouteBackendProtocol::GRPCS => "GRPCS",
+        }
+    }
+
+    pub fn is_long_lived(&self) -> bool {
+        matches!(
+            self,
+            RouteBackendProtocol::GRPC | RouteBackendProtocol::GRPCS | RouteBackendProtocol::WS
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct RouteRewrite {
+    /// Path requests are forwarded to, e.g. `/` or `/api/v1`
+    pub target: String,
+    /// Removes the route path before forwarding, `/app/users` is forwarded to `{target}/users`
+    /// instead of `{target}/app/users`
+    #[serde(default)]
+    pub strip_prefix: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash, Debug)]
+#[serde(default)]
+pub struct RouterAdvancedSettings {
+    #[serde(alias = "network.ingress.enable_cors")]
+    pub enable_cors: bool,
+    #[serde(alias = "network.ingress.cors_allow_origins")]
+    pub cors_allow_origins: Vec<String>,
+    #[serde(alias = "network.ingress.cors_allow_methods")]
+    pub cors_allow_methods: Vec<String>,
+    #[serde(alias = "network.ingress.cors_allow_headers")]
+    pub cors_allow_headers: Vec<String>,
+    /// CIDR ranges allowed to reach the router, everyone is allowed when empty
+    #[serde(alias = "network.ingress.whitelist_source_range")]
+    pub whitelist_source_ranges: Vec<String>,
+    /// htpasswd file content, enables HTTP basic auth when set
+    #[serde(alias = "network.ingress.basic_auth_htpasswd")]
+    pub basic_auth_htpasswd: Option<String>,
+    /// Requests per second accepted from a single IP
+    #[serde(alias = "network.ingress.limit_rps")]
+    pub limit_rps: Option<u32>,
+    /// Concurrent connections accepted from a single IP
+    #[serde(alias = "network.ingress.limit_connections")]
+    pub limit_connections: Option<u32>,
+    #[serde(alias = "network.ingress.proxy_body_size_mb")]
+    pub proxy_body_size_mb: u32,
+    #[serde(alias = "network.ingress.proxy_connect_timeout_seconds")]
+    pub proxy_connect_timeout_seconds: u32,
+    #[serde(alias = "network.ingress.proxy_send_timeout_seconds")]
+    pub proxy_send_timeout_seconds: u32,
+    #[serde(alias = "network.ingress.proxy_read_timeout_seconds")]
+    pub proxy_read_timeout_seconds: u32,
+}
+
+impl Default for RouterAdvancedSettings {
+    fn default() -> Self {
+        RouterAdvancedSettings {
+            enable_cors: false,
+            cors_allow_origins: vec!["*".to_string()],
+            cors_allow_methods: vec!["GET", "PUT", "POST", "DELETE", "PATCH", "OPTIONS"]
+                .into_iter()
+                .map(|method| method.to_string())
+                .collect(),
+            cors_allow_headers: vec![
+                "DNT",
+                "Keep-Alive",
+                "User-Agent",
+                "X-Requested-With",
+                "If-Modified-Since",
+                "Cache-Control",
+                "Content-Type",
+                "Range",
+                "Authorization",
+            ]
+            .into_iter()
+            .map(|header| header.to_string())
+            .collect(),
+            whitelist_source_ranges: vec![],
+            basic_auth_htpasswd: None,
+            limit_rps: None,
+            limit_connections: None,
+            proxy_body_size_mb: 100,
+            proxy_connect_timeout_seconds: 60,
+            proxy_send_timeout_seconds: 60,
+            proxy_read_timeout_seconds: 60,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
@@ -639,6 +1362,9 @@ pub struct Database {
     pub activate_backups: bool,
     pub publicly_accessible: bool,
     pub mode: DatabaseMode,
+    /// Only supported by container databases
+    #[serde(default)]
+    pub scheduling: Scheduling,
 }
 
 impl Database {
@@ -660,8 +1386,16 @@ impl Database {
             activate_high_availability: self.activate_high_availability,
             activate_backups: self.activate_backups,
             publicly_accessible: self.publicly_accessible,
+            scheduling: self.scheduling.clone(),
         };
 
+        if self.mode == DatabaseMode::MANAGED && self.scheduling != Scheduling::default() {
+            return Err(DatabaseError::InvalidConfig(
+                "scheduling settings are only supported by container databases".to_string(),
+            ));
+        }
+        validate_scheduling(&self.scheduling).map_err(DatabaseError::InvalidConfig)?;
+
         let listeners = cloud_provider.listeners().clone();
         let version = VersionsNumber::from_str(self.version.as_str())
             .map_err(|_| DatabaseError::InvalidConfig(format!("Bad version number: {}", self.version)))?;
@@ -1133,9 +1867,44 @@ pub enum ProgressScope {
     Database { id: String },
     Application { id: String },
     Router { id: String },
+    Job { id: String },
     Environment { id: String },
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DomainVerificationStatus {
+    Verified,
+    TxtRecordNotFound,
+    TxtRecordMismatch {
+        found_values: Vec<String>,
+    },
+    CnameNotFound,
+    CnameMismatch {
+        found_target: String,
+    },
+    /// Records couldn't be resolved, the domain configuration is unknown
+    ResolutionFailed {
+        raw_error_message: String,
+    },
+}
+
+/// Result of a custom domain ownership verification, the domain is routed only once verified.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DomainVerification {
+    pub domain: String,
+    pub txt_record_name: String,
+    pub expected_txt_value: String,
+    pub expected_cname_target: String,
+    pub status: DomainVerificationStatus,
+}
+
+impl DomainVerification {
+    pub fn is_verified(&self) -> bool {
+        self.status == DomainVerificationStatus::Verified
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ProgressLevel {
@@ -1156,6 +1925,7 @@ pub trait ProgressListener: Send + Sync {
     fn deployment_error(&self, info: ProgressInfo);
     fn pause_error(&self, info: ProgressInfo);
     fn delete_error(&self, info: ProgressInfo);
+    fn domain_verification(&self, info: ProgressInfo, verification: DomainVerification);
 }
 
 pub struct NoOpProgressListener {}
@@ -1171,6 +1941,7 @@ impl ProgressListener for NoOpProgressListener {
     fn deployment_error(&self, _info: ProgressInfo) {}
     fn pause_error(&self, _info: ProgressInfo) {}
     fn delete_error(&self, _info: ProgressInfo) {}
+    fn domain_verification(&self, _info: ProgressInfo, _verification: DomainVerification) {}
 }
 
 pub trait Listen {
@@ -1237,6 +2008,12 @@ impl<'a> ListenersHelper<'a> {
     pub fn delete_error(&self, info: ProgressInfo) {
         self.listeners.iter().for_each(|l| l.delete_error(info.clone()));
     }
+
+    pub fn domain_verification(&self, info: ProgressInfo, verification: DomainVerification) {
+        self.listeners
+            .iter()
+            .for_each(|l| l.domain_verification(info.clone(), verification.clone()));
+    }
 }
 
 #[derive(Clone)]
@@ -1349,6 +2126,13 @@ impl Context {
         }
     }
 
+    pub fn object_storage_encryption(&self) -> Option<&ObjectStorageEncryption> {
+        match &self.metadata {
+            Some(meta) => meta.object_storage_encryption.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn requires_forced_upgrade(&self) -> bool {
         match &self.metadata {
             Some(meta) => matches!(meta.forced_upgrade, Some(true)),
@@ -1386,6 +2170,8 @@ pub struct Metadata {
     pub resource_expiration_in_seconds: Option<u32>,
     pub forced_upgrade: Option<bool>,
     pub disable_pleco: Option<bool>,
+    #[serde(default)]
+    pub object_storage_encryption: Option<ObjectStorageEncryption>,
 }
 
 impl Metadata {
@@ -1394,12 +2180,14 @@ impl Metadata {
         resource_expiration_in_seconds: Option<u32>,
         forced_upgrade: Option<bool>,
         disable_pleco: Option<bool>,
+        object_storage_encryption: Option<ObjectStorageEncryption>,
     ) -> Self {
         Metadata {
             dry_run_deploy,
             resource_expiration_in_seconds,
             forced_upgrade,
             disable_pleco,
+            object_storage_encryption,
         }
     }
 }
@@ -1,26 +1,48 @@
 use crate::build_platform::Build;
-use crate::cloud_provider::environment::Environment;
-use crate::cloud_provider::kubernetes::Kubernetes;
-use crate::cloud_provider::models::{EnvironmentVariable, EnvironmentVariableDataTemplate, Storage};
+use crate::cloud_provider::kubernetes::validate_scheduling;
+use crate::cloud_provider::models::{
+    AutoscalingMetricDataTemplate, EnvironmentVariable, EnvironmentVariableDataTemplate, MountedFileDataTemplate,
+    ProbeDataTemplate, ProbeHeaderDataTemplate, PublicPortDataTemplate, PublicServiceDataTemplate,
+    ScalingPolicyDataTemplate, ScalingRulesDataTemplate, Storage, TolerationDataTemplate,
+};
+use crate::cloud_provider::pod_security::PodSecuritySettings;
 use crate::cloud_provider::service::{delete_stateless_service, scale_down_application};
 use crate::cloud_provider::service::{
     deploy_stateless_service_error, deploy_user_stateless_service, send_progress_on_long_task, Action, Create, Delete,
-    Helm, Pause, Service, ServiceType, StatelessService,
+    DeploymentHooks, Helm, Pause, Service, ServiceType, StatelessService,
 };
 use crate::cloud_provider::utilities::{print_action, sanitize_name};
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::kubectl::ScalingKind::{Deployment, Statefulset};
-use crate::errors::EngineError;
-use crate::events::{EnvironmentStep, EventDetails, Stage, ToTransmitter, Transmitter};
-use crate::io_models::{ApplicationAdvancedSettings, Context, Listen, Listener, Listeners, Port, QoveryIdentifier};
+use crate::cmd::kubectl::{
+    kubectl_apply_with_path, kubectl_exec_delete_pvc, kubectl_exec_get_load_balancer_address,
+    kubectl_exec_get_storage_class, kubectl_exec_get_volume_snapshot_class, kubectl_exec_get_volume_snapshots,
+    kubectl_exec_scale_replicas, kubectl_get_pvc,
+};
+use crate::errors::{CommandError, EngineError, ErrorMessageVerbosity};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage, ToTransmitter, Transmitter};
+use crate::io_models::{
+    ApplicationAdvancedSettings, AutoscalingMetricSource, AutoscalingSettings, Context, JobConcurrencyPolicy,
+    JobSchedule, LifecycleHook, LifecycleHooks, Listen, Listener, Listeners, ListenersHelper, MountedFile, Port,
+    PreStopHook, Probe, ProbeType, ProgressInfo, ProgressLevel, Protocol, QoveryIdentifier, ScalingPolicyType,
+    ScalingRules, ScalingSelectPolicy, Scheduling,
+};
 use crate::logger::Logger;
+use crate::models::job::{validate_job_settings, Job, JobSettings};
 use crate::models::types::{CloudProvider, ToTeraContext};
 use crate::utilities::to_short_id;
 use function_name::named;
+use retry::delay::Fibonacci;
+use retry::OperationResult;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use tera::Context as TeraContext;
 use uuid::Uuid;
 
+const MOUNTED_CONFIG_FILES_VOLUME: &str = "mounted-config-files";
+const MOUNTED_SECRET_FILES_VOLUME: &str = "mounted-secret-files";
+const MOUNTED_FILES_MAX_SIZE_IN_BYTES: usize = 1024 * 1024;
+
 #[derive(thiserror::Error, Debug)]
 pub enum ApplicationError {
     #[error("Application invalid configuration: {0}")]
@@ -46,6 +68,9 @@ pub struct Application<T: CloudProvider> {
     pub(super) listeners: Listeners,
     pub(super) logger: Box<dyn Logger>,
     pub(super) advanced_settings: ApplicationAdvancedSettings,
+    pub(super) lifecycle_hooks: LifecycleHooks,
+    pub(super) mounted_files: Vec<MountedFile>,
+    pub(super) scheduling: Scheduling,
     pub(super) _extra_settings: T::AppExtraSettings,
 }
 
@@ -66,11 +91,34 @@ impl<T: CloudProvider> Application<T> {
         storage: Vec<Storage<T::StorageTypes>>,
         environment_variables: Vec<EnvironmentVariable>,
         advance_settings: ApplicationAdvancedSettings,
+        lifecycle_hooks: LifecycleHooks,
+        mounted_files: Vec<MountedFile>,
+        scheduling: Scheduling,
         extra_settings: T::AppExtraSettings,
         listeners: Listeners,
         logger: Box<dyn Logger>,
     ) -> Result<Self, ApplicationError> {
         // TODO: Check that the information provided are coherent
+        validate_probe("readiness", &advance_settings.readiness_probe, &ports, false)?;
+        validate_probe("liveness", &advance_settings.liveness_probe, &ports, true)?;
+        if let Some(startup_probe) = &advance_settings.startup_probe {
+            validate_probe("startup", startup_probe, &ports, true)?;
+        }
+        validate_autoscaling(&advance_settings.autoscaling, min_instances != max_instances)?;
+        validate_rollout(&advance_settings, !storage.is_empty())?;
+        advance_settings
+            .security
+            .validate()
+            .map_err(|e| ApplicationError::InvalidConfig(format!("security {}", e)))?;
+        if let Some(pre_deploy) = &lifecycle_hooks.pre_deploy {
+            validate_lifecycle_hook("pre-deploy", pre_deploy)?;
+        }
+        if let Some(post_deploy) = &lifecycle_hooks.post_deploy {
+            validate_lifecycle_hook("post-deploy", post_deploy)?;
+        }
+        validate_mounted_files(&mounted_files, &storage)?;
+        validate_snapshot_restore(&storage, max_instances)?;
+        validate_scheduling(&scheduling).map_err(ApplicationError::InvalidConfig)?;
 
         Ok(Self {
             _marker: PhantomData,
@@ -91,11 +139,17 @@ impl<T: CloudProvider> Application<T> {
             listeners,
             logger,
             advanced_settings: advance_settings,
+            lifecycle_hooks,
+            mounted_files,
+            scheduling,
             _extra_settings: extra_settings,
         })
     }
 
-    pub(super) fn default_tera_context(&self, kubernetes: &dyn Kubernetes, environment: &Environment) -> TeraContext {
+    pub(super) fn default_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
+        let kubernetes = target.kubernetes;
+        let environment = target.environment;
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
         let mut context = TeraContext::new();
         context.insert("id", self.id());
         context.insert("long_id", &self.long_id);
@@ -126,10 +180,6 @@ impl<T: CloudProvider> Application<T> {
         let commit_id = self.build.image.commit_id.as_str();
         context.insert("helm_app_version", &commit_id[..7]);
         context.insert("image_name_with_tag", &self.build.image.full_image_name_with_tag());
-        context.insert(
-            "start_timeout_in_seconds",
-            &self.advanced_settings.deployment_delay_start_time_sec,
-        );
 
         let environment_variables = self
             .environment_variables
@@ -142,6 +192,59 @@ impl<T: CloudProvider> Application<T> {
 
         context.insert("environment_variables", &environment_variables);
         context.insert("ports", &self.ports);
+        context.insert("public_services", &public_services(&self.ports));
+        context.insert("probes", &self.probes());
+        context.insert("autoscaling_metrics", &self.autoscaling_metrics());
+        context.insert("scaling_behaviors", &self.scaling_behaviors());
+        context.insert("rolling_update_max_surge", &self.advanced_settings.rolling_update_max_surge);
+        context.insert(
+            "rolling_update_max_unavailable",
+            &self.advanced_settings.rolling_update_max_unavailable,
+        );
+        context.insert(
+            "termination_grace_period_seconds",
+            &self.advanced_settings.termination_grace_period_seconds,
+        );
+        context.insert("pre_stop_command", &self.pre_stop_command());
+        context.insert(
+            "pod_anti_affinity",
+            self.advanced_settings.pod_anti_affinity.to_template_format(),
+        );
+        let mounted_files = self.mounted_files();
+        let (mounted_secret_files, mounted_config_files): (Vec<_>, Vec<_>) = mounted_files
+            .iter()
+            .partition(|file| file.volume_name == MOUNTED_SECRET_FILES_VOLUME);
+        context.insert("mounted_files", &mounted_files);
+        context.insert("mounted_config_files", &mounted_config_files);
+        context.insert("mounted_secret_files", &mounted_secret_files);
+        let security = &self.advanced_settings.security;
+        let violations = kubernetes.pod_security_policy().violations(security);
+        if !violations.is_empty() {
+            return Err(EngineError::new_pod_security_policy_violation(
+                event_details,
+                self.name().to_string(),
+                violations,
+            ));
+        }
+        context.insert("pod_security_context", &security.pod_security_context());
+        context.insert("container_security_context", &security.container_security_context());
+        context.insert("writable_paths", &security.writable_paths);
+        context.insert("node_selector", &kubernetes.node_selector(&self.scheduling, event_details)?);
+        context.insert(
+            "tolerations",
+            &self
+                .scheduling
+                .tolerations
+                .iter()
+                .map(TolerationDataTemplate::from)
+                .collect::<Vec<_>>(),
+        );
+        target.api_capabilities.insert_into_tera_context(&mut context);
+        environment.network_policy.insert_into_tera_context(&mut context);
+        context.insert(
+            "volume_snapshot_class",
+            &kubernetes.volume_snapshot_class(target.api_capabilities),
+        );
         context.insert("is_registry_secret", &true);
         context.insert("registry_secret", self.build().image.registry_host());
 
@@ -149,7 +252,7 @@ impl<T: CloudProvider> Application<T> {
             context.insert("resource_expiration_in_seconds", &self.context.resource_expiration_in_seconds())
         }
 
-        context
+        Ok(context)
     }
 
     pub fn is_stateful(&self) -> bool {
@@ -181,10 +284,514 @@ impl<T: CloudProvider> Application<T> {
     }
 
     pub fn public_port(&self) -> Option<u16> {
+        self.main_public_port().map(|port| port.port as u16)
+    }
+
+    /// First publicly accessible port, HTTP ports come first since they are the ones routers send traffic to.
+    fn main_public_port(&self) -> Option<&Port> {
         self.ports
             .iter()
-            .find(|port| port.publicly_accessible)
-            .map(|port| port.port as u16)
+            .filter(|port| port.publicly_accessible)
+            .min_by_key(|port| port.protocol != Protocol::HTTP)
+    }
+
+    /// Probes rendered on the application container, TCP and HTTP probes without port use the first public
+    /// HTTP or TCP port and are skipped when there is none.
+    pub(super) fn probes(&self) -> Vec<ProbeDataTemplate> {
+        let settings = &self.advanced_settings;
+
+        [
+            ("readinessProbe", Some(&settings.readiness_probe)),
+            ("livenessProbe", Some(&settings.liveness_probe)),
+            ("startupProbe", settings.startup_probe.as_ref()),
+        ]
+        .iter()
+        .filter_map(|(kind, probe)| {
+            let probe = (*probe)?;
+            let mut template = ProbeDataTemplate {
+                kind: kind.to_string(),
+                probe_type: String::new(),
+                path: None,
+                port: None,
+                scheme: None,
+                headers: vec![],
+                command: vec![],
+                initial_delay_seconds: probe
+                    .initial_delay_seconds
+                    .unwrap_or(settings.deployment_delay_start_time_sec),
+                period_seconds: probe.period_seconds,
+                timeout_seconds: probe.timeout_seconds,
+                success_threshold: probe.success_threshold,
+                failure_threshold: probe.failure_threshold,
+            };
+
+            match &probe.probe_type {
+                ProbeType::Http {
+                    path,
+                    port,
+                    scheme,
+                    headers,
+                } => {
+                    template.probe_type = "http".to_string();
+                    template.path = Some(path.clone());
+                    template.port = Some(port.or_else(|| default_probe_port(&self.ports))?);
+                    template.scheme = Some(scheme.to_k8s_format().to_string());
+                    template.headers = headers
+                        .iter()
+                        .map(|(name, value)| ProbeHeaderDataTemplate {
+                            name: name.clone(),
+                            value: value.clone(),
+                        })
+                        .collect();
+                }
+                ProbeType::Tcp { port } => {
+                    template.probe_type = "tcp".to_string();
+                    template.port = Some(port.or_else(|| default_probe_port(&self.ports))?);
+                }
+                ProbeType::Exec { command } => {
+                    template.probe_type = "exec".to_string();
+                    template.command = command.clone();
+                }
+                ProbeType::None => return None,
+            }
+
+            Some(template)
+        })
+        .collect()
+    }
+
+    pub(super) fn autoscaling_metrics(&self) -> Vec<AutoscalingMetricDataTemplate> {
+        let settings = &self.advanced_settings.autoscaling;
+        let resource = |name: &str, average_utilization: Option<u32>| {
+            average_utilization.map(|average_utilization| AutoscalingMetricDataTemplate {
+                metric_type: "Resource".to_string(),
+                name: name.to_string(),
+                selector: BTreeMap::new(),
+                average_utilization: Some(average_utilization),
+                average_value: None,
+            })
+        };
+
+        resource("cpu", settings.cpu_average_utilization_percent)
+            .into_iter()
+            .chain(resource("memory", settings.memory_average_utilization_percent))
+            .chain(settings.metrics.iter().map(|metric| {
+                AutoscalingMetricDataTemplate {
+                    metric_type: match metric.source {
+                        AutoscalingMetricSource::Pods => "Pods",
+                        AutoscalingMetricSource::External => "External",
+                    }
+                    .to_string(),
+                    name: metric.name.clone(),
+                    selector: metric.selector.clone(),
+                    average_utilization: None,
+                    average_value: Some(metric.target_average_value.clone()),
+                }
+            }))
+            .collect()
+    }
+
+    pub(super) fn scaling_behaviors(&self) -> Vec<ScalingRulesDataTemplate> {
+        let settings = &self.advanced_settings.autoscaling;
+
+        [("scaleUp", &settings.scale_up), ("scaleDown", &settings.scale_down)]
+            .iter()
+            .filter_map(|(direction, rules)| {
+                rules.as_ref().map(|rules| ScalingRulesDataTemplate {
+                    direction: direction.to_string(),
+                    stabilization_window_seconds: rules.stabilization_window_seconds,
+                    select_policy: rules.select_policy.map(|select_policy| {
+                        match select_policy {
+                            ScalingSelectPolicy::Max => "Max",
+                            ScalingSelectPolicy::Min => "Min",
+                            ScalingSelectPolicy::Disabled => "Disabled",
+                        }
+                        .to_string()
+                    }),
+                    policies: rules
+                        .policies
+                        .iter()
+                        .map(|policy| ScalingPolicyDataTemplate {
+                            policy_type: match policy.policy_type {
+                                ScalingPolicyType::Pods => "Pods",
+                                ScalingPolicyType::Percent => "Percent",
+                            }
+                            .to_string(),
+                            value: policy.value,
+                            period_seconds: policy.period_seconds,
+                        })
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    /// Public endpoints are reported with the address of the load balancer in front of them, once it got one.
+    fn report_public_endpoints(&self, target: &DeploymentTarget)
+    where
+        Self: Service,
+    {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let listeners_helper = ListenersHelper::new(&self.listeners);
+        let kubernetes_config_file_path = match target.kubernetes.get_kubeconfig_file_path() {
+            Ok(path) => path,
+            Err(err) => {
+                self.logger().log(EngineEvent::Warning(
+                    event_details,
+                    EventMessage::new_from_safe(format!(
+                        "Cannot get public endpoints of application {}: {}",
+                        self.name(),
+                        err.message(ErrorMessageVerbosity::SafeOnly)
+                    )),
+                ));
+                return;
+            }
+        };
+        let envs = target.kubernetes.cloud_provider().credentials_environment_variables();
+
+        for service in public_services(&self.ports) {
+            let service_name = format!("{}-public-{}", self.sanitized_name(), service.name);
+            let address =
+                retry::retry(
+                    Fibonacci::from_millis(3000).take(8),
+                    || match kubectl_exec_get_load_balancer_address(
+                        &kubernetes_config_file_path,
+                        target.environment.namespace(),
+                        &service_name,
+                        envs.clone(),
+                    ) {
+                        Ok(Some(address)) => OperationResult::Ok(address),
+                        Ok(None) => OperationResult::Retry(None),
+                        Err(err) => OperationResult::Retry(Some(err)),
+                    },
+                );
+
+            let address = match address {
+                Ok(address) => address,
+                Err(err) => {
+                    let reason = match err {
+                        retry::Error::Operation { error: Some(err), .. } => {
+                            err.message(ErrorMessageVerbosity::SafeOnly)
+                        }
+                        _ => "the load balancer has no address yet".to_string(),
+                    };
+                    self.logger().log(EngineEvent::Warning(
+                        event_details.clone(),
+                        EventMessage::new_from_safe(format!(
+                            "Application {} {} ports are not publicly reachable yet: {}",
+                            self.name(),
+                            service.protocol,
+                            reason
+                        )),
+                    ));
+                    continue;
+                }
+            };
+
+            for port in service.ports {
+                let message = format!(
+                    "Application {} {} port {} is publicly exposed on {}:{}.",
+                    self.name(),
+                    service.protocol,
+                    port.target_port,
+                    address,
+                    port.port
+                );
+
+                listeners_helper.deployment_in_progress(ProgressInfo::new(
+                    self.progress_scope(),
+                    ProgressLevel::Info,
+                    Some(message.to_string()),
+                    self.context.execution_id(),
+                ));
+                self.logger()
+                    .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+            }
+        }
+    }
+
+    /// Statefulset volume of a storage, only the first replica volume can be restored from a snapshot.
+    fn storage_volume_name(&self, storage_id: &str) -> String {
+        format!("{}-{}-0", storage_id, self.sanitized_name())
+    }
+
+    /// Recreate the storage volumes from their snapshot to restore before the statefulset starts.
+    /// The statefulset is scaled down to release the volumes, deploying the application scales it up again.
+    /// Volumes already restored from the requested snapshot are left untouched by the next deployments.
+    fn restore_volume_snapshots(&self, target: &DeploymentTarget) -> Result<(), EngineError>
+    where
+        Self: Service,
+    {
+        let restores = self
+            .storage
+            .iter()
+            .filter_map(|s| s.restore_from_snapshot.as_ref().map(|snapshot_name| (s, snapshot_name)))
+            .collect::<Vec<_>>();
+        if restores.is_empty() {
+            return Ok(());
+        }
+
+        let kubernetes = target.kubernetes;
+        let namespace = target.environment.namespace();
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let listeners_helper = ListenersHelper::new(&self.listeners);
+        let kubernetes_config_file_path = kubernetes.get_kubeconfig_file_path()?;
+        let envs = kubernetes.cloud_provider().credentials_environment_variables();
+
+        for (storage, snapshot_name) in restores {
+            let volume_name = self.storage_volume_name(&storage.id);
+            let restore_error = |error: CommandError| {
+                EngineError::new_volume_snapshot_restore_error(
+                    event_details.clone(),
+                    volume_name.clone(),
+                    snapshot_name.clone(),
+                    error,
+                )
+            };
+
+            let volume_snapshot_class = kubernetes
+                .volume_snapshot_class(target.api_capabilities)
+                .ok_or_else(|| {
+                    restore_error(CommandError::new_from_safe_message(
+                        "Volume snapshots are not supported on this cluster.".to_string(),
+                    ))
+                })?;
+
+            // the restored volume keeps the storage class, size and labels of the one it replaces
+            let volume = kubectl_get_pvc(&kubernetes_config_file_path, namespace, envs.clone())
+                .map_err(restore_error)?
+                .items
+                .unwrap_or_default()
+                .into_iter()
+                .find(|volume| volume.metadata.name == volume_name)
+                .ok_or_else(|| {
+                    restore_error(CommandError::new_from_safe_message(
+                        "Volume to restore doesn't exist.".to_string(),
+                    ))
+                })?;
+
+            // a restore is done once, the volume keeps the snapshot it has been restored from as data source
+            if let Some(data_source) = &volume.spec.data_source {
+                if data_source.kind == "VolumeSnapshot" && &data_source.name == snapshot_name {
+                    continue;
+                }
+            }
+
+            let snapshot = kubectl_exec_get_volume_snapshots(
+                &kubernetes_config_file_path,
+                namespace,
+                &format!("appId={},diskId={}", self.id, storage.id),
+                envs.clone(),
+            )
+            .map_err(restore_error)?
+            .items
+            .into_iter()
+            .find(|snapshot| &snapshot.metadata.name == snapshot_name);
+            match snapshot {
+                Some(snapshot) if snapshot.is_ready_to_use() => {}
+                Some(_) => {
+                    return Err(restore_error(CommandError::new_from_safe_message(
+                        "Snapshot is not ready to use yet.".to_string(),
+                    )))
+                }
+                None => {
+                    return Err(restore_error(CommandError::new_from_safe_message(format!(
+                        "Snapshot doesn't exist for storage `{}`.",
+                        storage.name
+                    ))))
+                }
+            }
+
+            // only CSI provisioners fill a volume from its data source, others silently create an empty volume
+            let provisioner = kubectl_exec_get_storage_class(
+                &kubernetes_config_file_path,
+                &volume.spec.storage_class_name,
+                envs.clone(),
+            )
+            .map_err(restore_error)?
+            .provisioner;
+            let driver = kubectl_exec_get_volume_snapshot_class(
+                &kubernetes_config_file_path,
+                volume_snapshot_class,
+                envs.clone(),
+            )
+            .map_err(restore_error)?
+            .driver;
+            if provisioner != driver {
+                return Err(restore_error(CommandError::new_from_safe_message(format!(
+                    "Storage class `{}` provisioner `{}` can't restore `{}` snapshots.",
+                    volume.spec.storage_class_name, provisioner, driver
+                ))));
+            }
+
+            let message = format!(
+                "Restoring storage {} of application {} from snapshot {}.",
+                storage.name,
+                self.name(),
+                snapshot_name
+            );
+            listeners_helper.deployment_in_progress(ProgressInfo::new(
+                self.progress_scope(),
+                ProgressLevel::Info,
+                Some(message.to_string()),
+                self.context.execution_id(),
+            ));
+            self.logger()
+                .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+
+            kubectl_exec_scale_replicas(
+                &kubernetes_config_file_path,
+                envs.clone(),
+                namespace,
+                Statefulset,
+                &self.sanitized_name(),
+                0,
+            )
+            .map_err(restore_error)?;
+            kubectl_exec_delete_pvc(&kubernetes_config_file_path, namespace, &volume_name, envs.clone())
+                .map_err(restore_error)?;
+
+            let restored_volume = serde_json::json!({
+                "apiVersion": "v1",
+                "kind": "PersistentVolumeClaim",
+                "metadata": {
+                    "name": volume_name,
+                    "namespace": namespace,
+                    "labels": volume.metadata.labels,
+                },
+                "spec": {
+                    "accessModes": volume.spec.access_modes,
+                    "storageClassName": volume.spec.storage_class_name,
+                    "resources": {
+                        "requests": {
+                            "storage": volume.spec.resources.requests.storage,
+                        },
+                    },
+                    "dataSource": {
+                        "apiGroup": "snapshot.storage.k8s.io",
+                        "kind": "VolumeSnapshot",
+                        "name": snapshot_name,
+                    },
+                },
+            });
+            let workspace_dir = self.workspace_directory();
+            let restored_volume_path = format!("{}/{}-restored.json", workspace_dir, volume_name);
+            std::fs::create_dir_all(&workspace_dir)
+                .and_then(|_| std::fs::write(&restored_volume_path, restored_volume.to_string()))
+                .map_err(|e| {
+                    restore_error(CommandError::new(
+                        "Cannot write the restored volume manifest.".to_string(),
+                        Some(e.to_string()),
+                        None,
+                    ))
+                })?;
+            kubectl_apply_with_path(&kubernetes_config_file_path, envs.clone(), &restored_volume_path)
+                .map_err(restore_error)?;
+        }
+
+        Ok(())
+    }
+
+    /// Send the storage volume snapshots to the listeners, they are the ones which can be restored.
+    fn report_volume_snapshots(&self, target: &DeploymentTarget)
+    where
+        Self: Service,
+    {
+        let kubernetes = target.kubernetes;
+        if self.storage.is_empty() || kubernetes.volume_snapshot_class(target.api_capabilities).is_none() {
+            return;
+        }
+
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let listeners_helper = ListenersHelper::new(&self.listeners);
+        let snapshots = kubernetes
+            .get_kubeconfig_file_path()
+            .and_then(|kubernetes_config_file_path| {
+                kubectl_exec_get_volume_snapshots(
+                    kubernetes_config_file_path,
+                    target.environment.namespace(),
+                    &format!("appId={}", self.id),
+                    kubernetes.cloud_provider().credentials_environment_variables(),
+                )
+                .map_err(|e| {
+                    EngineError::new_k8s_cannot_get_volume_snapshots(
+                        event_details.clone(),
+                        target.environment.namespace().to_string(),
+                        e,
+                    )
+                })
+            });
+        let snapshots = match snapshots {
+            Ok(snapshots) => snapshots.items,
+            Err(err) => {
+                self.logger().log(EngineEvent::Warning(
+                    event_details,
+                    EventMessage::new(
+                        format!("Cannot retrieve application {} volume snapshots", self.name()),
+                        Some(err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                    ),
+                ));
+                return;
+            }
+        };
+
+        for storage in &self.storage {
+            let storage_snapshots = snapshots
+                .iter()
+                .filter(|snapshot| snapshot.metadata.labels.get("diskId") == Some(&storage.id))
+                .map(|snapshot| format!("{} ({})", snapshot.metadata.name, snapshot.metadata.creation_timestamp))
+                .collect::<Vec<_>>();
+            let message = match storage_snapshots.is_empty() {
+                true => format!("Application {} storage {} has no snapshot.", self.name(), storage.name),
+                false => format!(
+                    "Application {} storage {} snapshots: {}.",
+                    self.name(),
+                    storage.name,
+                    storage_snapshots.join(", ")
+                ),
+            };
+
+            listeners_helper.deployment_in_progress(ProgressInfo::new(
+                self.progress_scope(),
+                ProgressLevel::Info,
+                Some(message.to_string()),
+                self.context.execution_id(),
+            ));
+            self.logger()
+                .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+        }
+    }
+
+    /// Run once job running the hook command with the application image, environment and resources.
+    /// Hooks share the application id, they are run one after the other and replace the previous run.
+    fn lifecycle_hook_job(
+        &self,
+        hook_name: &str,
+        hook: &LifecycleHook,
+        event_details: EventDetails,
+    ) -> Result<Job<T>, EngineError> {
+        Job::new(
+            self.context.clone(),
+            self.long_id,
+            Action::Create,
+            format!("{}-{}", self.name, hook_name).as_str(),
+            lifecycle_hook_job_settings(hook, &self.advanced_settings.security),
+            self.total_cpus.clone(),
+            self.cpu_burst.clone(),
+            self.total_ram_in_mib,
+            self.build.clone(),
+            self.environment_variables.clone(),
+            self.listeners.clone(),
+            self.logger.clone(),
+        )
+        .map_err(|e| {
+            EngineError::new_invalid_deployment_hook(
+                event_details,
+                self.name.clone(),
+                hook_name.to_string(),
+                e.to_string(),
+            )
+        })
     }
 
     pub fn total_cpus(&self) -> String {
@@ -231,6 +838,47 @@ impl<T: CloudProvider> Application<T> {
         sanitize_name("app", self.id())
     }
 
+    fn pre_stop_command(&self) -> Vec<String> {
+        match &self.advanced_settings.pre_stop_hook {
+            Some(PreStopHook::Sleep { duration_in_sec }) => vec!["sleep".to_string(), duration_in_sec.to_string()],
+            Some(PreStopHook::Exec { command }) => command.clone(),
+            None => vec![],
+        }
+    }
+
+    fn mounted_files(&self) -> Vec<MountedFileDataTemplate> {
+        self.mounted_files
+            .iter()
+            .enumerate()
+            .map(|(idx, file)| {
+                // ConfigMap and Secret keys only accept alphanumerics, `-`, `_` and `.`
+                let file_name = file
+                    .mount_path
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+                    .chars()
+                    .take(200)
+                    .map(|c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                        true => c,
+                        false => '-',
+                    })
+                    .collect::<String>();
+                let key = format!("{}-{}", idx, file_name);
+                MountedFileDataTemplate {
+                    key,
+                    mount_path: file.mount_path.clone(),
+                    content: file.content.clone(),
+                    mode: file.mode,
+                    volume_name: match file.is_secret {
+                        true => MOUNTED_SECRET_FILES_VOLUME.to_string(),
+                        false => MOUNTED_CONFIG_FILES_VOLUME.to_string(),
+                    },
+                }
+            })
+            .collect()
+    }
+
     pub(crate) fn get_event_details(&self, stage: Stage) -> EventDetails {
         let context = self.context();
         EventDetails::new(
@@ -245,6 +893,308 @@ impl<T: CloudProvider> Application<T> {
     }
 }
 
+/// Publicly accessible TCP and UDP ports opted in a load balancer, exposed through a LoadBalancer Service per protocol.
+fn public_services(ports: &[Port]) -> Vec<PublicServiceDataTemplate> {
+    [(Protocol::TCP, "tcp"), (Protocol::UDP, "udp")]
+        .iter()
+        .filter_map(|(protocol, name)| {
+            let ports = ports
+                .iter()
+                .filter(|port| port.publicly_accessible && port.expose_with_load_balancer && &port.protocol == protocol)
+                .map(|port| PublicPortDataTemplate {
+                    name: format!("p{}", port.port),
+                    port: port.public_port.unwrap_or(port.port),
+                    target_port: port.port,
+                })
+                .collect::<Vec<_>>();
+
+            if ports.is_empty() {
+                return None;
+            }
+
+            Some(PublicServiceDataTemplate {
+                name: name.to_string(),
+                protocol: name.to_uppercase(),
+                ports,
+            })
+        })
+        .collect()
+}
+
+/// Port probed by TCP and HTTP probes without port, the first public HTTP port or else the first public TCP port.
+fn default_probe_port(ports: &[Port]) -> Option<u16> {
+    ports
+        .iter()
+        .filter(|port| port.publicly_accessible && port.protocol != Protocol::UDP)
+        .min_by_key(|port| port.protocol != Protocol::HTTP)
+        .map(|port| port.port)
+}
+
+fn validate_probe(name: &str, probe: &Probe, ports: &[Port], single_success: bool) -> Result<(), ApplicationError> {
+    let invalid = |reason: &str| Err(ApplicationError::InvalidConfig(format!("{} probe {}", name, reason)));
+
+    if probe.period_seconds == 0 || probe.timeout_seconds == 0 || probe.failure_threshold == 0 {
+        return invalid("period, timeout and failure threshold must be greater than 0");
+    }
+
+    // Kubernetes only accepts a success threshold of 1 for liveness and startup probes
+    if probe.success_threshold == 0 || (single_success && probe.success_threshold != 1) {
+        return invalid(match single_success {
+            true => "success threshold must be 1",
+            false => "success threshold must be greater than 0",
+        });
+    }
+
+    // UDP ports can't be probed
+    let is_known_port = |port: &Option<u16>| match port {
+        Some(port) => ports.iter().any(|p| p.port == *port && p.protocol != Protocol::UDP),
+        None => true,
+    };
+    match &probe.probe_type {
+        ProbeType::Http {
+            path, port, headers, ..
+        } => {
+            if !path.starts_with('/') {
+                return invalid("path must start with /");
+            }
+            if !is_known_port(port) {
+                return invalid("port must be one of the application HTTP or TCP ports");
+            }
+            if port.is_none() && default_probe_port(ports).is_none() {
+                return invalid("port is required when the application has no public HTTP or TCP port");
+            }
+            if headers.keys().any(|name| name.is_empty()) {
+                return invalid("header names can't be empty");
+            }
+        }
+        ProbeType::Tcp { port } => {
+            if !is_known_port(port) {
+                return invalid("port must be one of the application HTTP or TCP ports");
+            }
+        }
+        ProbeType::Exec { command } => {
+            if command.is_empty() {
+                return invalid("command can't be empty");
+            }
+        }
+        ProbeType::None => {}
+    }
+
+    Ok(())
+}
+
+/// Hooks run with the application image, they keep its security settings.
+fn lifecycle_hook_job_settings(hook: &LifecycleHook, security: &PodSecuritySettings) -> JobSettings {
+    JobSettings {
+        schedule: JobSchedule::OnDeploy,
+        concurrency_policy: JobConcurrencyPolicy::Forbid,
+        max_nb_restart: 0,
+        max_duration_in_sec: hook.max_duration_in_sec,
+        command: hook.command.clone(),
+        security: security.clone(),
+    }
+}
+
+fn validate_lifecycle_hook(name: &str, hook: &LifecycleHook) -> Result<(), ApplicationError> {
+    if hook.command.is_empty() {
+        return Err(ApplicationError::InvalidConfig(format!(
+            "{} hook command must not be empty",
+            name
+        )));
+    }
+
+    // the application security settings are validated on their own
+    validate_job_settings(&lifecycle_hook_job_settings(hook, &PodSecuritySettings::default()))
+        .map_err(|e| ApplicationError::InvalidConfig(format!("{} hook {}", name, e)))
+}
+
+fn validate_mounted_files<S>(files: &[MountedFile], storage: &[Storage<S>]) -> Result<(), ApplicationError> {
+    let invalid =
+        |path: &str, reason: &str| Err(ApplicationError::InvalidConfig(format!("mounted file `{}` {}", path, reason)));
+
+    let mut config_files_size = 0;
+    let mut secret_files_size = 0;
+    for (idx, file) in files.iter().enumerate() {
+        let path = file.mount_path.as_str();
+        if !path.starts_with('/') || path.ends_with('/') {
+            return invalid(path, "must be an absolute file path");
+        }
+        if files[..idx].iter().any(|other| other.mount_path == path) {
+            return invalid(path, "is mounted more than once");
+        }
+        if storage
+            .iter()
+            .any(|s| path.starts_with(&format!("{}/", s.mount_point.trim_end_matches('/'))))
+        {
+            return invalid(path, "can't be mounted inside a storage");
+        }
+        if file.mode > 0o777 {
+            return invalid(path, "mode must be between 0 and 0777");
+        }
+
+        let size = match base64::decode(&file.content) {
+            Ok(content) => content.len(),
+            Err(_) => return invalid(path, "content must be base64 encoded"),
+        };
+        match file.is_secret {
+            true => secret_files_size += size,
+            false => config_files_size += size,
+        }
+    }
+
+    // ConfigMaps and Secrets can't exceed 1MiB
+    if config_files_size > MOUNTED_FILES_MAX_SIZE_IN_BYTES || secret_files_size > MOUNTED_FILES_MAX_SIZE_IN_BYTES {
+        return Err(ApplicationError::InvalidConfig(
+            "mounted files total size can't exceed 1MiB".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_snapshot_restore<S>(storage: &[Storage<S>], max_instances: u32) -> Result<(), ApplicationError> {
+    for s in storage {
+        let snapshot_name = match &s.restore_from_snapshot {
+            Some(snapshot_name) => snapshot_name,
+            None => continue,
+        };
+        if snapshot_name.is_empty() {
+            return Err(ApplicationError::InvalidConfig(format!(
+                "storage `{}` snapshot to restore can't be empty",
+                s.name
+            )));
+        }
+        // every statefulset replica has its own volume, a snapshot can only restore a single one of them
+        if max_instances > 1 {
+            return Err(ApplicationError::InvalidConfig(format!(
+                "storage `{}` can only be restored from a snapshot with a single instance",
+                s.name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Applications with storage are statefulsets, they are rolled out one pod at a time.
+fn validate_rollout(settings: &ApplicationAdvancedSettings, is_statefulset: bool) -> Result<(), ApplicationError> {
+    let invalid = |reason: String| Err(ApplicationError::InvalidConfig(format!("rollout {}", reason)));
+
+    let max_surge = settings.rolling_update_max_surge.as_deref();
+    let max_unavailable = settings.rolling_update_max_unavailable.as_deref();
+    if is_statefulset && (max_surge.is_some() || max_unavailable.is_some()) {
+        return invalid("max surge and max unavailable are not supported on applications with storage".to_string());
+    }
+    for (name, value) in [("max surge", max_surge), ("max unavailable", max_unavailable)] {
+        match value {
+            Some(value) if !is_valid_int_or_percent(value) => {
+                return invalid(format!("{} `{}` must be a number or a percentage up to 100%", name, value))
+            }
+            _ => {}
+        }
+    }
+
+    // Kubernetes refuses a rollout which can't create nor remove any pod
+    let is_zero = |value: Option<&str>| matches!(value, Some("0") | Some("0%"));
+    if is_zero(max_surge) && is_zero(max_unavailable) {
+        return invalid("max surge and max unavailable can't both be 0".to_string());
+    }
+
+    match &settings.pre_stop_hook {
+        Some(PreStopHook::Exec { command }) if command.is_empty() => {
+            invalid("pre-stop hook command can't be empty".to_string())
+        }
+        Some(PreStopHook::Sleep { duration_in_sec })
+            if *duration_in_sec >= settings.termination_grace_period_seconds =>
+        {
+            invalid("pre-stop hook sleep must be shorter than the termination grace period".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks a Kubernetes IntOrString like `1` or `25%`.
+fn is_valid_int_or_percent(value: &str) -> bool {
+    match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<u32>().map(|percent| percent <= 100).unwrap_or(false),
+        None => value.parse::<u32>().is_ok(),
+    }
+}
+
+fn validate_autoscaling(settings: &AutoscalingSettings, is_autoscaled: bool) -> Result<(), ApplicationError> {
+    let invalid = |reason: String| Err(ApplicationError::InvalidConfig(format!("autoscaling {}", reason)));
+
+    if is_autoscaled
+        && settings.cpu_average_utilization_percent.is_none()
+        && settings.memory_average_utilization_percent.is_none()
+        && settings.metrics.is_empty()
+    {
+        return invalid("requires at least one metric".to_string());
+    }
+
+    if settings.cpu_average_utilization_percent == Some(0) || settings.memory_average_utilization_percent == Some(0) {
+        return invalid("utilization targets must be greater than 0".to_string());
+    }
+
+    for metric in &settings.metrics {
+        if metric.name.is_empty() {
+            return invalid("metric names can't be empty".to_string());
+        }
+        if metric.source == AutoscalingMetricSource::Pods && !metric.selector.is_empty() {
+            return invalid(format!("pods metric `{}` can't have a selector", metric.name));
+        }
+        if !is_valid_quantity(&metric.target_average_value) {
+            return invalid(format!(
+                "metric `{}` target `{}` isn't a valid quantity",
+                metric.name, metric.target_average_value
+            ));
+        }
+    }
+
+    let validate_rules = |direction: &str, rules: &Option<ScalingRules>| {
+        let rules = match rules {
+            Some(rules) => rules,
+            None => return Ok(()),
+        };
+
+        // limits enforced by the Kubernetes API
+        if rules.stabilization_window_seconds.unwrap_or_default() > 3600 {
+            return invalid(format!("{} stabilization window can't exceed 3600 seconds", direction));
+        }
+        if rules.select_policy != Some(ScalingSelectPolicy::Disabled) && rules.policies.is_empty() {
+            return invalid(format!("{} requires at least one policy", direction));
+        }
+        if rules
+            .policies
+            .iter()
+            .any(|policy| policy.value == 0 || policy.period_seconds == 0 || policy.period_seconds > 1800)
+        {
+            return invalid(format!(
+                "{} policies value must be greater than 0 and period between 1 and 1800 seconds",
+                direction
+            ));
+        }
+
+        Ok(())
+    };
+    validate_rules("scale up", &settings.scale_up)?;
+    validate_rules("scale down", &settings.scale_down)
+}
+
+/// Checks a Kubernetes quantity like `100`, `0.5` or `500m`, exponent notations aren't accepted.
+fn is_valid_quantity(quantity: &str) -> bool {
+    let number = ["Ki", "Mi", "Gi", "Ti", "m", "k", "M", "G", "T"]
+        .iter()
+        .find_map(|suffix| quantity.strip_suffix(suffix))
+        .unwrap_or(quantity);
+
+    !number.is_empty()
+        && !number.starts_with('.')
+        && !number.ends_with('.')
+        && number.matches('.').count() <= 1
+        && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
 // Traits implementations
 impl<T: CloudProvider> ToTransmitter for Application<T> {
     fn to_transmitter(&self) -> Transmitter {
@@ -372,6 +1322,7 @@ impl<T: CloudProvider> Helm for Application<T> {
 impl<T: CloudProvider> Create for Application<T>
 where
     Application<T>: Service,
+    Job<T>: Service,
 {
     #[named]
     fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
@@ -381,13 +1332,37 @@ where
             "application",
             function_name!(),
             self.name(),
-            event_details,
+            event_details.clone(),
             self.logger(),
         );
-        send_progress_on_long_task(self, Action::Create, || deploy_user_stateless_service(target, self))
+        let pre_deploy = self
+            .lifecycle_hooks
+            .pre_deploy
+            .as_ref()
+            .map(|hook| self.lifecycle_hook_job("pre-deploy", hook, event_details.clone()))
+            .transpose()?;
+        let post_deploy = self
+            .lifecycle_hooks
+            .post_deploy
+            .as_ref()
+            .map(|hook| self.lifecycle_hook_job("post-deploy", hook, event_details.clone()))
+            .transpose()?;
+        let hooks = DeploymentHooks {
+            pre_deploy: pre_deploy.as_ref().map(|job| job as &dyn StatelessService),
+            post_deploy: post_deploy.as_ref().map(|job| job as &dyn StatelessService),
+        };
+
+        send_progress_on_long_task(self, Action::Create, || {
+            self.restore_volume_snapshots(target)?;
+            deploy_user_stateless_service(target, self, &hooks)
+        })?;
+        self.report_public_endpoints(target);
+        self.report_volume_snapshots(target);
+
+        Ok(())
     }
 
-    fn on_create_check(&self) -> Result<(), EngineError> {
+    fn on_create_check(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
         Ok(())
     }
 
@@ -410,6 +1385,7 @@ where
 impl<T: CloudProvider> Pause for Application<T>
 where
     Application<T>: Service,
+    Job<T>: Service,
 {
     #[named]
     fn on_pause(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
@@ -451,6 +1427,7 @@ where
 impl<T: CloudProvider> Delete for Application<T>
 where
     Application<T>: Service,
+    Job<T>: Service,
 {
     #[named]
     fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
@@ -464,6 +1441,23 @@ where
             self.logger(),
         );
 
+        // hooks share the same release, the last one run is still installed
+        let hook = self
+            .lifecycle_hooks
+            .pre_deploy
+            .as_ref()
+            .map(|hook| self.lifecycle_hook_job("pre-deploy", hook, event_details.clone()))
+            .or_else(|| {
+                self.lifecycle_hooks
+                    .post_deploy
+                    .as_ref()
+                    .map(|hook| self.lifecycle_hook_job("post-deploy", hook, event_details.clone()))
+            })
+            .transpose()?;
+        if let Some(hook) = hook {
+            delete_stateless_service(target, &hook, event_details.clone())?;
+        }
+
         send_progress_on_long_task(self, Action::Delete, || {
             delete_stateless_service(target, self, event_details.clone())
         })
@@ -494,6 +1488,7 @@ where
 impl<T: CloudProvider> StatelessService for Application<T>
 where
     Application<T>: Service,
+    Job<T>: Service,
 {
     fn as_stateless_service(&self) -> &dyn StatelessService {
         self
@@ -503,11 +1498,13 @@ where
 pub trait ApplicationService: StatelessService {
     fn get_build(&self) -> &Build;
     fn get_build_mut(&mut self) -> &mut Build;
+    fn public_port_protocol(&self) -> Option<&Protocol>;
 }
 
 impl<T: CloudProvider> ApplicationService for Application<T>
 where
     Application<T>: Service,
+    Job<T>: Service,
 {
     fn get_build(&self) -> &Build {
         self.build()
@@ -516,4 +1513,1485 @@ where
     fn get_build_mut(&mut self) -> &mut Build {
         self.build_mut()
     }
+
+    fn public_port_protocol(&self) -> Option<&Protocol> {
+        self.main_public_port().map(|port| &port.protocol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        default_probe_port, is_valid_quantity, public_services, validate_autoscaling, validate_lifecycle_hook,
+        validate_mounted_files, validate_probe, validate_rollout, validate_snapshot_restore,
+    };
+    use crate::cloud_provider::models::Storage;
+    use crate::cloud_provider::models::{
+        AutoscalingMetricDataTemplate, MountedFileDataTemplate, ProbeDataTemplate, ProbeHeaderDataTemplate,
+        PublicPortDataTemplate, PublicServiceDataTemplate, ScalingPolicyDataTemplate, ScalingRulesDataTemplate,
+        StorageDataTemplate, TolerationDataTemplate,
+    };
+    use crate::cloud_provider::network_policy::{EnvironmentNetworkPolicy, NetworkPolicyMode};
+    use crate::cloud_provider::pod_security::{PodSecuritySettings, SeccompProfile};
+    use crate::io_models::{
+        ApplicationAdvancedSettings, AutoscalingMetric, AutoscalingMetricSource, AutoscalingSettings, LifecycleHook,
+        LifecycleHooks, MountedFile, PodAntiAffinity, Port, PreStopHook, Probe, ProbeScheme, ProbeType, Protocol,
+        ScalingPolicy, ScalingPolicyType, ScalingRules, ScalingSelectPolicy,
+    };
+    use std::collections::BTreeMap;
+    use tera::Context as TeraContext;
+
+    fn q_application_templates(template_name: &str, context: &TeraContext) -> Vec<(String, Vec<serde_yaml::Value>)> {
+        ["aws", "aws-ec2", "digitalocean", "scaleway"]
+            .iter()
+            .map(|lib_dir| {
+                let template = std::fs::read_to_string(format!(
+                    "{}/lib/{}/charts/q-application/templates/{}",
+                    env!("CARGO_MANIFEST_DIR"),
+                    lib_dir,
+                    template_name
+                ))
+                .unwrap();
+                let rendered = tera::Tera::one_off(template.as_str(), context, false).unwrap();
+                let documents = rendered
+                    .split("\n---\n")
+                    .filter_map(|document| serde_yaml::from_str::<serde_yaml::Value>(document).ok())
+                    .filter(|document| !document.is_null())
+                    .collect();
+
+                (lib_dir.to_string(), documents)
+            })
+            .collect()
+    }
+
+    fn port(port: u16, public_port: Option<u16>, protocol: Protocol) -> Port {
+        Port {
+            id: format!("p{}", port),
+            long_id: uuid::Uuid::new_v4(),
+            port,
+            public_port,
+            name: None,
+            publicly_accessible: true,
+            expose_with_load_balancer: protocol != Protocol::HTTP,
+            protocol,
+        }
+    }
+
+    #[test]
+    fn test_public_services_rendering() {
+        // setup:
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert(
+            "ports",
+            &vec![
+                port(8080, None, Protocol::HTTP),
+                port(1883, Some(1883), Protocol::TCP),
+                port(27015, Some(7015), Protocol::UDP),
+            ],
+        );
+        context.insert(
+            "public_services",
+            &vec![
+                PublicServiceDataTemplate {
+                    name: "tcp".to_string(),
+                    protocol: "TCP".to_string(),
+                    ports: vec![PublicPortDataTemplate {
+                        name: "p1883".to_string(),
+                        port: 1883,
+                        target_port: 1883,
+                    }],
+                },
+                PublicServiceDataTemplate {
+                    name: "udp".to_string(),
+                    protocol: "UDP".to_string(),
+                    ports: vec![PublicPortDataTemplate {
+                        name: "p27015".to_string(),
+                        port: 7015,
+                        target_port: 27015,
+                    }],
+                },
+            ],
+        );
+
+        // execute:
+        let public_services = q_application_templates("service-public.j2.yaml", &context);
+        let services = q_application_templates("service.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in public_services {
+            assert_eq!(2, documents.len(), "{}", lib_dir);
+
+            let udp = &documents[1];
+            assert_eq!(
+                "app-abcdef-public-udp",
+                udp["metadata"]["name"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!("LoadBalancer", udp["spec"]["type"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!("UDP", udp["spec"]["ports"][0]["protocol"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(7015, udp["spec"]["ports"][0]["port"].as_u64().unwrap(), "{}", lib_dir);
+            assert_eq!(27015, udp["spec"]["ports"][0]["targetPort"].as_u64().unwrap(), "{}", lib_dir);
+        }
+
+        for (lib_dir, documents) in services {
+            let protocols = documents[0]["spec"]["ports"]
+                .as_sequence()
+                .unwrap()
+                .iter()
+                .map(|port| port["protocol"].as_str().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(vec!["TCP", "TCP", "UDP"], protocols, "{}", lib_dir);
+        }
+    }
+
+    struct TestCase<'a> {
+        probe: Probe,
+        single_success: bool,
+        expected_valid: bool,
+        description: &'a str,
+    }
+
+    #[test]
+    fn test_validate_probe() {
+        // setup:
+        let http = |path: &str, port: Option<u16>| Probe {
+            probe_type: ProbeType::Http {
+                path: path.to_string(),
+                port,
+                scheme: ProbeScheme::HTTP,
+                headers: BTreeMap::new(),
+            },
+            ..Probe::default()
+        };
+        let ports = vec![port(8080, None, Protocol::HTTP)];
+        let test_cases = vec![
+            TestCase {
+                probe: Probe::default(),
+                single_success: true,
+                expected_valid: true,
+                description: "default TCP probe",
+            },
+            TestCase {
+                probe: http("/healthz", Some(8080)),
+                single_success: false,
+                expected_valid: true,
+                description: "HTTP probe on an application port",
+            },
+            TestCase {
+                probe: http("healthz", None),
+                single_success: false,
+                expected_valid: false,
+                description: "HTTP path without leading slash",
+            },
+            TestCase {
+                probe: http("/healthz", Some(9090)),
+                single_success: false,
+                expected_valid: false,
+                description: "HTTP probe on an unknown port",
+            },
+            TestCase {
+                probe: Probe {
+                    probe_type: ProbeType::Exec { command: vec![] },
+                    ..Probe::default()
+                },
+                single_success: false,
+                expected_valid: false,
+                description: "exec probe without command",
+            },
+            TestCase {
+                probe: Probe {
+                    success_threshold: 2,
+                    ..Probe::default()
+                },
+                single_success: false,
+                expected_valid: true,
+                description: "readiness probe success threshold",
+            },
+            TestCase {
+                probe: Probe {
+                    success_threshold: 2,
+                    ..Probe::default()
+                },
+                single_success: true,
+                expected_valid: false,
+                description: "liveness probe success threshold",
+            },
+            TestCase {
+                probe: Probe {
+                    timeout_seconds: 0,
+                    ..Probe::default()
+                },
+                single_success: false,
+                expected_valid: false,
+                description: "zero timeout",
+            },
+            TestCase {
+                probe: Probe {
+                    probe_type: ProbeType::None,
+                    ..Probe::default()
+                },
+                single_success: true,
+                expected_valid: true,
+                description: "no probe",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_probe("test", &tc.probe, &ports, tc.single_success);
+
+            // verify:
+            assert_eq!(tc.expected_valid, result.is_ok(), "case: {}", tc.description);
+        }
+
+        let private_ports = vec![Port {
+            publicly_accessible: false,
+            ..port(8080, None, Protocol::HTTP)
+        }];
+        assert!(validate_probe("test", &http("/healthz", None), &private_ports, false).is_err());
+
+        let udp_ports = vec![
+            port(8080, None, Protocol::HTTP),
+            port(27015, Some(27015), Protocol::UDP),
+        ];
+        assert!(validate_probe("test", &http("/healthz", Some(27015)), &udp_ports, false).is_err());
+        assert!(validate_probe("test", &http("/healthz", None), &udp_ports[1..], false).is_err());
+        assert!(validate_probe("test", &Probe::default(), &udp_ports[1..], false).is_ok());
+    }
+
+    #[test]
+    fn test_default_probe_port() {
+        // setup:
+        let test_cases = vec![
+            (
+                vec![port(27015, None, Protocol::UDP), port(8080, None, Protocol::HTTP)],
+                Some(8080),
+                "HTTP port comes first",
+            ),
+            (
+                vec![port(27015, None, Protocol::UDP), port(1883, None, Protocol::TCP)],
+                Some(1883),
+                "UDP ports are never probed",
+            ),
+            (vec![port(27015, None, Protocol::UDP)], None, "only a UDP port"),
+            (
+                vec![Port {
+                    publicly_accessible: false,
+                    ..port(8080, None, Protocol::HTTP)
+                }],
+                None,
+                "no public port",
+            ),
+        ];
+
+        for (ports, expected_port, description) in test_cases {
+            // execute:
+            let probe_port = default_probe_port(&ports);
+
+            // verify:
+            assert_eq!(expected_port, probe_port, "case: {}", description);
+        }
+    }
+
+    #[test]
+    fn test_public_services() {
+        // setup:
+        let ports = vec![
+            port(8080, None, Protocol::HTTP),
+            port(1883, Some(11883), Protocol::TCP),
+            Port {
+                expose_with_load_balancer: false,
+                ..port(5432, None, Protocol::TCP)
+            },
+            Port {
+                expose_with_load_balancer: false,
+                ..port(27015, None, Protocol::UDP)
+            },
+            Port {
+                publicly_accessible: false,
+                ..port(9000, None, Protocol::UDP)
+            },
+        ];
+
+        // execute:
+        let services = public_services(&ports);
+
+        // verify:
+        assert_eq!(1, services.len(), "only opted in public ports get a load balancer");
+        assert_eq!("tcp", services[0].name);
+        assert_eq!(1, services[0].ports.len());
+        assert_eq!(11883, services[0].ports[0].port);
+        assert_eq!(1883, services[0].ports[0].target_port);
+    }
+
+    #[test]
+    fn test_probe_deserialization() {
+        // setup:
+        let json = r#"{"type": "http", "path": "/healthz", "headers": {"Host": "example.com"}, "period_seconds": 5}"#;
+
+        // execute:
+        let probe: Probe = serde_json::from_str(json).unwrap();
+
+        // verify:
+        match &probe.probe_type {
+            ProbeType::Http {
+                path,
+                port,
+                scheme,
+                headers,
+            } => {
+                assert_eq!("/healthz", path);
+                assert_eq!(None, *port);
+                assert_eq!(ProbeScheme::HTTP, *scheme);
+                assert_eq!(Some(&"example.com".to_string()), headers.get("Host"));
+            }
+            probe_type => panic!("unexpected probe type {:?}", probe_type),
+        }
+        assert_eq!(5, probe.period_seconds);
+        assert_eq!(3, probe.failure_threshold);
+        assert_eq!(None, probe.initial_delay_seconds);
+    }
+
+    #[test]
+    fn test_probes_rendering() {
+        // setup:
+        let probe = |kind: &str, probe_type: &str| ProbeDataTemplate {
+            kind: kind.to_string(),
+            probe_type: probe_type.to_string(),
+            path: None,
+            port: None,
+            scheme: None,
+            headers: vec![],
+            command: vec![],
+            initial_delay_seconds: 30,
+            period_seconds: 10,
+            timeout_seconds: 2,
+            success_threshold: 1,
+            failure_threshold: 3,
+        };
+        let mut context = TeraContext::new();
+        context.insert("is_storage", &false);
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("min_instances", &1);
+        context.insert("max_instances", &2);
+        context.insert("version", "commit");
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", "registry");
+        context.insert("registry_secret_name", "registry-token-abcdef");
+        context.insert("image_name_with_tag", "image:tag");
+        context.insert("environment_variables", &Vec::<String>::new());
+        context.insert("private_port", &8080);
+        context.insert("ports", &vec![port(8080, None, Protocol::HTTP)]);
+        context.insert("cpu_burst", "500m");
+        context.insert("total_cpus", "250m");
+        context.insert("total_ram_in_mib", &256);
+        context.insert("termination_grace_period_seconds", &60);
+        context.insert("pod_anti_affinity", "required");
+        context.insert(
+            "probes",
+            &vec![
+                ProbeDataTemplate {
+                    path: Some("/healthz".to_string()),
+                    port: Some(8080),
+                    scheme: Some("HTTPS".to_string()),
+                    headers: vec![ProbeHeaderDataTemplate {
+                        name: "Host".to_string(),
+                        value: "example.com".to_string(),
+                    }],
+                    ..probe("readinessProbe", "http")
+                },
+                ProbeDataTemplate {
+                    command: vec!["sh".to_string(), "-c".to_string(), "test -f /tmp/alive".to_string()],
+                    ..probe("livenessProbe", "exec")
+                },
+                ProbeDataTemplate {
+                    port: Some(8080),
+                    failure_threshold: 30,
+                    ..probe("startupProbe", "tcp")
+                },
+            ],
+        );
+
+        // execute:
+        let deployments = q_application_templates("deployment.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in deployments {
+            let container = &documents[0]["spec"]["template"]["spec"]["containers"][0];
+
+            let readiness = &container["readinessProbe"];
+            assert_eq!("/healthz", readiness["httpGet"]["path"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(8080, readiness["httpGet"]["port"].as_u64().unwrap(), "{}", lib_dir);
+            assert_eq!("HTTPS", readiness["httpGet"]["scheme"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(
+                "example.com",
+                readiness["httpGet"]["httpHeaders"][0]["value"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!(2, readiness["timeoutSeconds"].as_u64().unwrap(), "{}", lib_dir);
+
+            let liveness_command = container["livenessProbe"]["exec"]["command"].as_sequence().unwrap();
+            assert_eq!(3, liveness_command.len(), "{}", lib_dir);
+            assert_eq!("test -f /tmp/alive", liveness_command[2].as_str().unwrap(), "{}", lib_dir);
+
+            let startup = &container["startupProbe"];
+            assert_eq!(8080, startup["tcpSocket"]["port"].as_u64().unwrap(), "{}", lib_dir);
+            assert_eq!(30, startup["failureThreshold"].as_u64().unwrap(), "{}", lib_dir);
+        }
+    }
+
+    #[test]
+    fn test_is_valid_quantity() {
+        for quantity in ["100", "0.5", "500m", "10k", "1Gi"] {
+            assert!(is_valid_quantity(quantity), "{}", quantity);
+        }
+        for quantity in ["", "m", "1.", ".5", "1.2.3", "-1", "1e3", "10 rps"] {
+            assert!(!is_valid_quantity(quantity), "{}", quantity);
+        }
+    }
+
+    #[test]
+    fn test_validate_autoscaling() {
+        // setup:
+        let queue_depth = AutoscalingMetric {
+            source: AutoscalingMetricSource::External,
+            name: "rabbitmq_queue_messages".to_string(),
+            selector: vec![("queue".to_string(), "jobs".to_string())].into_iter().collect(),
+            target_average_value: "30".to_string(),
+        };
+        let scale_down = ScalingRules {
+            stabilization_window_seconds: Some(300),
+            select_policy: None,
+            policies: vec![ScalingPolicy {
+                policy_type: ScalingPolicyType::Percent,
+                value: 50,
+                period_seconds: 60,
+            }],
+        };
+        let test_cases = vec![
+            (AutoscalingSettings::default(), true, true, "default settings"),
+            (
+                AutoscalingSettings {
+                    cpu_average_utilization_percent: None,
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                false,
+                "autoscaling without metric",
+            ),
+            (
+                AutoscalingSettings {
+                    cpu_average_utilization_percent: None,
+                    ..AutoscalingSettings::default()
+                },
+                false,
+                true,
+                "fixed instances without metric",
+            ),
+            (
+                AutoscalingSettings {
+                    metrics: vec![queue_depth.clone()],
+                    scale_down: Some(scale_down.clone()),
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                true,
+                "external metric and scale down policy",
+            ),
+            (
+                AutoscalingSettings {
+                    metrics: vec![AutoscalingMetric {
+                        source: AutoscalingMetricSource::Pods,
+                        ..queue_depth.clone()
+                    }],
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                false,
+                "pods metric with selector",
+            ),
+            (
+                AutoscalingSettings {
+                    metrics: vec![AutoscalingMetric {
+                        target_average_value: "thirty".to_string(),
+                        ..queue_depth.clone()
+                    }],
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                false,
+                "invalid target quantity",
+            ),
+            (
+                AutoscalingSettings {
+                    scale_down: Some(ScalingRules {
+                        stabilization_window_seconds: Some(7200),
+                        ..scale_down.clone()
+                    }),
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                false,
+                "stabilization window too long",
+            ),
+            (
+                AutoscalingSettings {
+                    scale_down: Some(ScalingRules {
+                        select_policy: Some(ScalingSelectPolicy::Disabled),
+                        policies: vec![],
+                        ..scale_down.clone()
+                    }),
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                true,
+                "scale down disabled",
+            ),
+            (
+                AutoscalingSettings {
+                    scale_up: Some(ScalingRules::default()),
+                    ..AutoscalingSettings::default()
+                },
+                true,
+                false,
+                "scale up without policy",
+            ),
+        ];
+
+        for (settings, is_autoscaled, expected_valid, description) in test_cases {
+            // execute:
+            let result = validate_autoscaling(&settings, is_autoscaled);
+
+            // verify:
+            assert_eq!(expected_valid, result.is_ok(), "case: {}", description);
+        }
+    }
+
+    #[test]
+    fn test_horizontal_autoscaler_rendering() {
+        // setup:
+        let mut context = TeraContext::new();
+        context.insert("is_storage", &false);
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("min_instances", &1);
+        context.insert("max_instances", &10);
+        context.insert("autoscaling_api_version", "autoscaling/v2beta2");
+        context.insert(
+            "autoscaling_metrics",
+            &vec![
+                AutoscalingMetricDataTemplate {
+                    metric_type: "Resource".to_string(),
+                    name: "memory".to_string(),
+                    selector: BTreeMap::new(),
+                    average_utilization: Some(80),
+                    average_value: None,
+                },
+                AutoscalingMetricDataTemplate {
+                    metric_type: "External".to_string(),
+                    name: "rabbitmq_queue_messages".to_string(),
+                    selector: vec![("queue".to_string(), "jobs".to_string())].into_iter().collect(),
+                    average_utilization: None,
+                    average_value: Some("30".to_string()),
+                },
+            ],
+        );
+        context.insert(
+            "scaling_behaviors",
+            &vec![ScalingRulesDataTemplate {
+                direction: "scaleDown".to_string(),
+                stabilization_window_seconds: Some(0),
+                select_policy: Some("Min".to_string()),
+                policies: vec![ScalingPolicyDataTemplate {
+                    policy_type: "Pods".to_string(),
+                    value: 1,
+                    period_seconds: 60,
+                }],
+            }],
+        );
+
+        // execute:
+        let autoscalers = q_application_templates("horizontal_autoscaler.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in autoscalers {
+            let hpa = &documents[0];
+            assert_eq!("autoscaling/v2beta2", hpa["apiVersion"].as_str().unwrap(), "{}", lib_dir);
+
+            let metrics = hpa["spec"]["metrics"].as_sequence().unwrap();
+            assert_eq!(2, metrics.len(), "{}", lib_dir);
+            assert_eq!("memory", metrics[0]["resource"]["name"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(
+                80,
+                metrics[0]["resource"]["target"]["averageUtilization"].as_u64().unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!(
+                "jobs",
+                metrics[1]["external"]["metric"]["selector"]["matchLabels"]["queue"]
+                    .as_str()
+                    .unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!(
+                "30",
+                metrics[1]["external"]["target"]["averageValue"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+
+            let scale_down = &hpa["spec"]["behavior"]["scaleDown"];
+            assert_eq!(0, scale_down["stabilizationWindowSeconds"].as_u64().unwrap(), "{}", lib_dir);
+            assert_eq!("Min", scale_down["selectPolicy"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!("Pods", scale_down["policies"][0]["type"].as_str().unwrap(), "{}", lib_dir);
+            assert!(hpa["spec"]["behavior"]["scaleUp"].is_null(), "{}", lib_dir);
+        }
+    }
+
+    #[test]
+    fn test_validate_lifecycle_hook() {
+        // setup:
+        struct TestCase<'a> {
+            hook: LifecycleHook,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                hook: LifecycleHook {
+                    command: vec!["rails".to_string(), "db:migrate".to_string()],
+                    max_duration_in_sec: 600,
+                },
+                expected_error: None,
+                description: "migration command",
+            },
+            TestCase {
+                hook: LifecycleHook {
+                    command: vec![],
+                    max_duration_in_sec: 600,
+                },
+                expected_error: Some("pre-deploy hook command must not be empty"),
+                description: "no command",
+            },
+            TestCase {
+                hook: LifecycleHook {
+                    command: vec!["alembic".to_string(), "upgrade".to_string(), "head".to_string()],
+                    max_duration_in_sec: 0,
+                },
+                expected_error: Some("pre-deploy hook Job invalid configuration: max duration"),
+                description: "no max duration",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_lifecycle_hook("pre-deploy", &tc.hook);
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => {
+                    assert!(result.unwrap_err().to_string().contains(expected_error), "{}", tc.description)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_hooks_deserialization() {
+        // execute:
+        let hooks: LifecycleHooks =
+            serde_json::from_str(r#"{"pre_deploy": {"command": ["rails", "db:migrate"]}}"#).unwrap();
+
+        // verify:
+        assert_eq!(
+            Some(LifecycleHook {
+                command: vec!["rails".to_string(), "db:migrate".to_string()],
+                max_duration_in_sec: 600,
+            }),
+            hooks.pre_deploy
+        );
+        assert_eq!(None, hooks.post_deploy);
+    }
+
+    #[test]
+    fn test_validate_mounted_files() {
+        // setup:
+        struct TestCase<'a> {
+            files: Vec<MountedFile>,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let file = |mount_path: &str| MountedFile {
+            mount_path: mount_path.to_string(),
+            content: base64::encode("worker_processes 1;"),
+            mode: 0o644,
+            is_secret: false,
+        };
+        let storage = vec![Storage {
+            id: "disk".to_string(),
+            name: "data".to_string(),
+            storage_type: (),
+            size_in_gib: 10,
+            mount_point: "/data".to_string(),
+            snapshot_retention_in_days: 0,
+            restore_from_snapshot: None,
+        }];
+
+        let test_cases = vec![
+            TestCase {
+                files: vec![
+                    file("/etc/nginx/nginx.conf"),
+                    MountedFile {
+                        mode: 0o400,
+                        is_secret: true,
+                        ..file("/etc/ssl/private/server.key")
+                    },
+                ],
+                expected_error: None,
+                description: "config and secret files",
+            },
+            TestCase {
+                files: vec![file("etc/nginx/nginx.conf")],
+                expected_error: Some("must be an absolute file path"),
+                description: "relative path",
+            },
+            TestCase {
+                files: vec![file("/etc/nginx/")],
+                expected_error: Some("must be an absolute file path"),
+                description: "directory path",
+            },
+            TestCase {
+                files: vec![file("/etc/nginx/nginx.conf"), file("/etc/nginx/nginx.conf")],
+                expected_error: Some("is mounted more than once"),
+                description: "duplicated path",
+            },
+            TestCase {
+                files: vec![file("/data/config.yaml")],
+                expected_error: Some("can't be mounted inside a storage"),
+                description: "path inside a storage",
+            },
+            TestCase {
+                files: vec![file("/database.yaml")],
+                expected_error: None,
+                description: "path sharing a storage prefix",
+            },
+            TestCase {
+                files: vec![MountedFile {
+                    mode: 0o1777,
+                    ..file("/etc/nginx/nginx.conf")
+                }],
+                expected_error: Some("mode must be between 0 and 0777"),
+                description: "invalid mode",
+            },
+            TestCase {
+                files: vec![MountedFile {
+                    content: "not base64!".to_string(),
+                    ..file("/etc/nginx/nginx.conf")
+                }],
+                expected_error: Some("content must be base64 encoded"),
+                description: "invalid content",
+            },
+            TestCase {
+                files: vec![MountedFile {
+                    content: base64::encode(vec![0; 1024 * 1024 + 1]),
+                    ..file("/opt/model.bin")
+                }],
+                expected_error: Some("total size can't exceed 1MiB"),
+                description: "too large content",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_mounted_files(&tc.files, &storage);
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => {
+                    assert!(result.unwrap_err().to_string().contains(expected_error), "{}", tc.description)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mounted_files_rendering() {
+        // setup:
+        let config_file = MountedFileDataTemplate {
+            key: "0-nginx.conf".to_string(),
+            mount_path: "/etc/nginx/nginx.conf".to_string(),
+            content: base64::encode("worker_processes 1;"),
+            mode: 0o644,
+            volume_name: "mounted-config-files".to_string(),
+        };
+        let secret_file = MountedFileDataTemplate {
+            key: "1-server.key".to_string(),
+            mount_path: "/etc/ssl/private/server.key".to_string(),
+            content: base64::encode("private key"),
+            mode: 0o400,
+            volume_name: "mounted-secret-files".to_string(),
+        };
+        let mut context = TeraContext::new();
+        context.insert("is_storage", &false);
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("min_instances", &1);
+        context.insert("max_instances", &1);
+        context.insert("version", "commit");
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", "registry");
+        context.insert("registry_secret_name", "registry-token-abcdef");
+        context.insert("image_name_with_tag", "image:tag");
+        context.insert("environment_variables", &Vec::<String>::new());
+        context.insert("probes", &Vec::<ProbeDataTemplate>::new());
+        context.insert("cpu_burst", "500m");
+        context.insert("total_cpus", "250m");
+        context.insert("total_ram_in_mib", &256);
+        context.insert("termination_grace_period_seconds", &60);
+        context.insert("pod_anti_affinity", "required");
+        context.insert("mounted_files", &vec![&config_file, &secret_file]);
+        context.insert("mounted_config_files", &vec![&config_file]);
+        context.insert("mounted_secret_files", &vec![&secret_file]);
+        context.insert("writable_paths", &Vec::<String>::new());
+
+        // execute:
+        let deployments = q_application_templates("deployment.j2.yaml", &context);
+        let mounted_files = q_application_templates("mounted-files.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in deployments {
+            let pod_spec = &documents[0]["spec"]["template"]["spec"];
+
+            let mounts = pod_spec["containers"][0]["volumeMounts"].as_sequence().unwrap();
+            assert_eq!(2, mounts.len(), "{}", lib_dir);
+            assert_eq!("/etc/nginx/nginx.conf", mounts[0]["mountPath"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!("0-nginx.conf", mounts[0]["subPath"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!("mounted-secret-files", mounts[1]["name"].as_str().unwrap(), "{}", lib_dir);
+
+            let volumes = pod_spec["volumes"].as_sequence().unwrap();
+            assert_eq!(2, volumes.len(), "{}", lib_dir);
+            assert_eq!(
+                "app-abcdef-files",
+                volumes[0]["configMap"]["name"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!(0o400, volumes[1]["secret"]["items"][0]["mode"].as_u64().unwrap(), "{}", lib_dir);
+        }
+        for (lib_dir, documents) in mounted_files {
+            assert_eq!(2, documents.len(), "{}", lib_dir);
+            assert_eq!("ConfigMap", documents[0]["kind"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(
+                config_file.content.as_str(),
+                documents[0]["binaryData"]["0-nginx.conf"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!("Secret", documents[1]["kind"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(
+                secret_file.content.as_str(),
+                documents[1]["data"]["1-server.key"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_rollout() {
+        // setup:
+        struct TestCase<'a> {
+            settings: ApplicationAdvancedSettings,
+            is_statefulset: bool,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let rollout = |max_surge: Option<&str>, max_unavailable: Option<&str>, pre_stop_hook: Option<PreStopHook>| {
+            ApplicationAdvancedSettings {
+                rolling_update_max_surge: max_surge.map(|value| value.to_string()),
+                rolling_update_max_unavailable: max_unavailable.map(|value| value.to_string()),
+                termination_grace_period_seconds: 60,
+                pre_stop_hook,
+                ..Default::default()
+            }
+        };
+
+        let test_cases = vec![
+            TestCase {
+                settings: ApplicationAdvancedSettings::default(),
+                is_statefulset: false,
+                expected_error: None,
+                description: "default settings",
+            },
+            TestCase {
+                settings: rollout(Some("50%"), Some("0"), Some(PreStopHook::Sleep { duration_in_sec: 15 })),
+                is_statefulset: false,
+                expected_error: None,
+                description: "surge only rollout with a pre-stop sleep",
+            },
+            TestCase {
+                settings: rollout(Some("1.5"), None, None),
+                is_statefulset: false,
+                expected_error: Some("max surge `1.5` must be a number or a percentage"),
+                description: "decimal max surge",
+            },
+            TestCase {
+                settings: rollout(None, Some("150%"), None),
+                is_statefulset: false,
+                expected_error: Some("max unavailable `150%` must be a number or a percentage"),
+                description: "percentage above 100",
+            },
+            TestCase {
+                settings: rollout(Some("0%"), Some("0"), None),
+                is_statefulset: false,
+                expected_error: Some("max surge and max unavailable can't both be 0"),
+                description: "blocked rollout",
+            },
+            TestCase {
+                settings: rollout(Some("1"), None, None),
+                is_statefulset: true,
+                expected_error: Some("max surge and max unavailable are not supported on applications with storage"),
+                description: "statefulset max surge",
+            },
+            TestCase {
+                settings: rollout(None, None, Some(PreStopHook::Sleep { duration_in_sec: 15 })),
+                is_statefulset: true,
+                expected_error: None,
+                description: "statefulset with a pre-stop sleep",
+            },
+            TestCase {
+                settings: rollout(None, None, Some(PreStopHook::Exec { command: vec![] })),
+                is_statefulset: false,
+                expected_error: Some("pre-stop hook command can't be empty"),
+                description: "empty pre-stop command",
+            },
+            TestCase {
+                settings: rollout(None, None, Some(PreStopHook::Sleep { duration_in_sec: 60 })),
+                is_statefulset: false,
+                expected_error: Some("pre-stop hook sleep must be shorter than the termination grace period"),
+                description: "pre-stop sleep as long as the grace period",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_rollout(&tc.settings, tc.is_statefulset);
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => {
+                    assert!(result.unwrap_err().to_string().contains(expected_error), "{}", tc.description)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rollout_rendering() {
+        // setup:
+        let mut context = TeraContext::new();
+        context.insert("is_storage", &false);
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("min_instances", &3);
+        context.insert("max_instances", &3);
+        context.insert("version", "commit");
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", "registry");
+        context.insert("registry_secret_name", "registry-token-abcdef");
+        context.insert("image_name_with_tag", "image:tag");
+        context.insert("environment_variables", &Vec::<String>::new());
+        context.insert("probes", &Vec::<ProbeDataTemplate>::new());
+        context.insert("cpu_burst", "500m");
+        context.insert("total_cpus", "250m");
+        context.insert("total_ram_in_mib", &256);
+        context.insert("rolling_update_max_surge", "50%");
+        context.insert("rolling_update_max_unavailable", "0");
+        context.insert("termination_grace_period_seconds", &300);
+        context.insert("pre_stop_command", &vec!["sleep", "15"]);
+        context.insert("pod_anti_affinity", PodAntiAffinity::Preferred.to_template_format());
+
+        // execute:
+        let deployments = q_application_templates("deployment.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in deployments {
+            let rolling_update = &documents[0]["spec"]["strategy"]["rollingUpdate"];
+            assert_eq!("50%", rolling_update["maxSurge"].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!(0, rolling_update["maxUnavailable"].as_u64().unwrap(), "{}", lib_dir);
+
+            let pod_spec = &documents[0]["spec"]["template"]["spec"];
+            assert_eq!(300, pod_spec["terminationGracePeriodSeconds"].as_u64().unwrap(), "{}", lib_dir);
+            assert!(
+                pod_spec["affinity"]["podAntiAffinity"]["requiredDuringSchedulingIgnoredDuringExecution"].is_null(),
+                "{}",
+                lib_dir
+            );
+            let preferred =
+                &pod_spec["affinity"]["podAntiAffinity"]["preferredDuringSchedulingIgnoredDuringExecution"][0];
+            assert_eq!(100, preferred["weight"].as_u64().unwrap(), "{}", lib_dir);
+            assert_eq!(
+                "kubernetes.io/hostname",
+                preferred["podAffinityTerm"]["topologyKey"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+
+            let pre_stop = &pod_spec["containers"][0]["lifecycle"]["preStop"]["exec"]["command"];
+            assert_eq!("sleep", pre_stop[0].as_str().unwrap(), "{}", lib_dir);
+            assert_eq!("15", pre_stop[1].as_str().unwrap(), "{}", lib_dir);
+        }
+    }
+
+    #[test]
+    fn test_pre_stop_hook_deserialization() {
+        // execute:
+        let settings: ApplicationAdvancedSettings = serde_json::from_str(
+            r#"{"deployment.delay_start_time_sec": 30, "build.timeout_max_sec": 1800,
+            "pre_stop_hook": {"type": "sleep", "duration_in_sec": 15}, "pod_anti_affinity": "preferred"}"#,
+        )
+        .unwrap();
+
+        // verify:
+        assert_eq!(Some(PreStopHook::Sleep { duration_in_sec: 15 }), settings.pre_stop_hook);
+        assert_eq!(PodAntiAffinity::Preferred, settings.pod_anti_affinity);
+        assert_eq!(60, settings.termination_grace_period_seconds);
+        assert_eq!(None, settings.rolling_update_max_surge);
+    }
+
+    #[test]
+    fn test_scheduling_rendering() {
+        // setup:
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("min_instances", &1);
+        context.insert("max_instances", &1);
+        context.insert("version", "commit");
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", "registry");
+        context.insert("registry_secret_name", "registry-token-abcdef");
+        context.insert("image_name_with_tag", "image:tag");
+        context.insert("environment_variables", &Vec::<String>::new());
+        context.insert("probes", &Vec::<ProbeDataTemplate>::new());
+        context.insert("cpu_burst", "500m");
+        context.insert("total_cpus", "250m");
+        context.insert("total_ram_in_mib", &256);
+        context.insert("termination_grace_period_seconds", &60);
+        context.insert("pod_anti_affinity", "required");
+        context.insert(
+            "node_selector",
+            &vec![("qovery.com/node-group", "spot")]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        );
+        context.insert(
+            "tolerations",
+            &vec![TolerationDataTemplate {
+                key: "spot".to_string(),
+                operator: "Equal".to_string(),
+                value: Some("true".to_string()),
+                effect: Some("NoSchedule".to_string()),
+                toleration_seconds: None,
+            }],
+        );
+        context.insert(
+            "storage",
+            &vec![StorageDataTemplate {
+                id: "disk".to_string(),
+                name: "data".to_string(),
+                storage_type: "gp2".to_string(),
+                size_in_gib: 10,
+                mount_point: "/data".to_string(),
+                snapshot_retention_in_days: 0,
+            }],
+        );
+        context.insert("mounted_files", &Vec::<MountedFileDataTemplate>::new());
+        context.insert("writable_paths", &Vec::<String>::new());
+
+        for (is_storage, template) in [(false, "deployment.j2.yaml"), (true, "statefulset.j2.yaml")] {
+            context.insert("is_storage", &is_storage);
+
+            // execute:
+            let rendered = q_application_templates(template, &context);
+
+            // verify:
+            for (lib_dir, documents) in rendered {
+                let pod_spec = &documents[0]["spec"]["template"]["spec"];
+                assert_eq!(
+                    "spot",
+                    pod_spec["nodeSelector"]["qovery.com/node-group"].as_str().unwrap(),
+                    "{} {}",
+                    lib_dir,
+                    template
+                );
+                let toleration = &pod_spec["tolerations"][0];
+                assert_eq!("Equal", toleration["operator"].as_str().unwrap(), "{} {}", lib_dir, template);
+                assert_eq!("NoSchedule", toleration["effect"].as_str().unwrap(), "{} {}", lib_dir, template);
+                assert!(toleration["tolerationSeconds"].is_null(), "{} {}", lib_dir, template);
+            }
+        }
+    }
+
+    #[test]
+    fn test_security_contexts_rendering() {
+        // setup:
+        let security = PodSecuritySettings {
+            run_as_non_root: true,
+            run_as_user: Some(1000),
+            run_as_group: Some(1000),
+            read_only_root_filesystem: true,
+            writable_paths: vec!["/tmp".to_string(), "/var/cache/nginx".to_string()],
+            drop_capabilities: vec!["ALL".to_string()],
+            seccomp_profile: Some(SeccompProfile::RuntimeDefault),
+            disable_privilege_escalation: true,
+        };
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("min_instances", &1);
+        context.insert("max_instances", &1);
+        context.insert("version", "commit");
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", "registry");
+        context.insert("registry_secret_name", "registry-token-abcdef");
+        context.insert("image_name_with_tag", "image:tag");
+        context.insert("environment_variables", &Vec::<String>::new());
+        context.insert("probes", &Vec::<ProbeDataTemplate>::new());
+        context.insert("cpu_burst", "500m");
+        context.insert("total_cpus", "250m");
+        context.insert("total_ram_in_mib", &256);
+        context.insert("termination_grace_period_seconds", &60);
+        context.insert("pod_anti_affinity", "required");
+        context.insert("storage", &Vec::<StorageDataTemplate>::new());
+        context.insert("mounted_files", &Vec::<MountedFileDataTemplate>::new());
+        context.insert("mounted_config_files", &Vec::<MountedFileDataTemplate>::new());
+        context.insert("mounted_secret_files", &Vec::<MountedFileDataTemplate>::new());
+        context.insert("pod_security_context", &security.pod_security_context());
+        context.insert("container_security_context", &security.container_security_context());
+        context.insert("writable_paths", &security.writable_paths);
+
+        for (is_storage, template) in [(false, "deployment.j2.yaml"), (true, "statefulset.j2.yaml")] {
+            context.insert("is_storage", &is_storage);
+
+            // execute:
+            let rendered = q_application_templates(template, &context);
+
+            // verify:
+            for (lib_dir, documents) in rendered {
+                let pod_spec = &documents[0]["spec"]["template"]["spec"];
+                assert_eq!(
+                    1000,
+                    pod_spec["securityContext"]["fsGroup"].as_u64().unwrap(),
+                    "{} {}",
+                    lib_dir,
+                    template
+                );
+                assert_eq!(
+                    "RuntimeDefault",
+                    pod_spec["securityContext"]["seccompProfile"]["type"].as_str().unwrap(),
+                    "{} {}",
+                    lib_dir,
+                    template
+                );
+
+                let container = &pod_spec["containers"][0];
+                assert!(
+                    container["securityContext"]["readOnlyRootFilesystem"]
+                        .as_bool()
+                        .unwrap(),
+                    "{} {}",
+                    lib_dir,
+                    template
+                );
+                assert_eq!(
+                    "ALL",
+                    container["securityContext"]["capabilities"]["drop"][0]
+                        .as_str()
+                        .unwrap(),
+                    "{} {}",
+                    lib_dir,
+                    template
+                );
+
+                let mounts = container["volumeMounts"].as_sequence().unwrap();
+                let writable_mounts = mounts
+                    .iter()
+                    .filter(|mount| mount["name"].as_str().unwrap().starts_with("writable-"))
+                    .collect::<Vec<_>>();
+                assert_eq!(2, writable_mounts.len(), "{} {}", lib_dir, template);
+                assert_eq!(
+                    "/var/cache/nginx",
+                    writable_mounts[1]["mountPath"].as_str().unwrap(),
+                    "{} {}",
+                    lib_dir,
+                    template
+                );
+                let empty_dirs = pod_spec["volumes"]
+                    .as_sequence()
+                    .unwrap()
+                    .iter()
+                    .filter(|volume| !volume["emptyDir"].is_null())
+                    .count();
+                assert_eq!(2, empty_dirs, "{} {}", lib_dir, template);
+            }
+        }
+    }
+
+    #[test]
+    fn test_network_policies_rendering() {
+        // setup:
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("is_private_port", &true);
+        context.insert("private_port", &8080);
+        context.insert("public_services", &Vec::<String>::new());
+        let strict_policy = EnvironmentNetworkPolicy {
+            mode: NetworkPolicyMode::Strict,
+            allowed_environment_ids: vec!["staging".to_string()],
+            allowed_ingress_cidrs: vec!["10.10.0.0/16".to_string()],
+            restrict_egress: true,
+            allowed_egress_cidrs: vec!["172.30.0.12/32".to_string(), "0.0.0.0/0".to_string()],
+        };
+
+        // execute:
+        EnvironmentNetworkPolicy::default().insert_into_tera_context(&mut context);
+        let default_policies = q_application_templates("networkpolicies.j2.yaml", &context);
+        strict_policy.insert_into_tera_context(&mut context);
+        let strict_policies = q_application_templates("networkpolicies.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in default_policies {
+            let ingress = documents[1]["spec"]["ingress"].as_sequence().unwrap();
+            assert_eq!(2, ingress.len(), "{}", lib_dir);
+            assert_eq!(8080, ingress[1]["ports"][0]["port"].as_u64().unwrap(), "{}", lib_dir);
+            assert!(
+                documents
+                    .iter()
+                    .all(|policy| !policy["metadata"]["name"].as_str().unwrap().ends_with("-egress")),
+                "{}",
+                lib_dir
+            );
+        }
+        for (lib_dir, documents) in strict_policies {
+            let names = documents
+                .iter()
+                .map(|policy| policy["metadata"]["name"].as_str().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec!["app-abcdef-default", "app-abcdef-app-access", "app-abcdef-egress"],
+                names,
+                "{}",
+                lib_dir
+            );
+
+            let ingress = documents[1]["spec"]["ingress"].as_sequence().unwrap();
+            assert_eq!(4, ingress.len(), "{}", lib_dir);
+            assert_eq!(
+                "true",
+                ingress[1]["from"][0]["namespaceSelector"]["matchLabels"]["qovery.com/ingress-controller"]
+                    .as_str()
+                    .unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!(
+                "staging",
+                ingress[2]["from"][0]["podSelector"]["matchLabels"]["envId"]
+                    .as_str()
+                    .unwrap(),
+                "{}",
+                lib_dir
+            );
+            assert_eq!(
+                "10.10.0.0/16",
+                ingress[3]["from"][0]["ipBlock"]["cidr"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+
+            let egress = documents[2]["spec"]["egress"].as_sequence().unwrap();
+            assert_eq!(3, egress.len(), "{}", lib_dir);
+            assert_eq!(
+                "172.30.0.12/32",
+                egress[2]["to"][0]["ipBlock"]["cidr"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+            if lib_dir.starts_with("aws") {
+                assert!(egress[2]["to"][0]["ipBlock"]["except"].is_null(), "{}", lib_dir);
+                assert_eq!(
+                    "169.254.169.254/32",
+                    egress[2]["to"][1]["ipBlock"]["except"][0].as_str().unwrap(),
+                    "{}",
+                    lib_dir
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_snapshot_restore() {
+        // setup:
+        struct TestCase<'a> {
+            restore_from_snapshot: Option<&'a str>,
+            max_instances: u32,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                restore_from_snapshot: None,
+                max_instances: 3,
+                expected_error: None,
+                description: "no restore",
+            },
+            TestCase {
+                restore_from_snapshot: Some("disk-app-abcdef-0-20220620030010"),
+                max_instances: 1,
+                expected_error: None,
+                description: "restore with a single instance",
+            },
+            TestCase {
+                restore_from_snapshot: Some(""),
+                max_instances: 1,
+                expected_error: Some("storage `data` snapshot to restore can't be empty"),
+                description: "empty snapshot name",
+            },
+            TestCase {
+                restore_from_snapshot: Some("disk-app-abcdef-0-20220620030010"),
+                max_instances: 2,
+                expected_error: Some("storage `data` can only be restored from a snapshot with a single instance"),
+                description: "restore with several instances",
+            },
+        ];
+
+        for tc in test_cases {
+            let storage = vec![Storage {
+                id: "disk".to_string(),
+                name: "data".to_string(),
+                storage_type: (),
+                size_in_gib: 10,
+                mount_point: "/data".to_string(),
+                snapshot_retention_in_days: 7,
+                restore_from_snapshot: tc.restore_from_snapshot.map(|name| name.to_string()),
+            }];
+
+            // execute:
+            let result = validate_snapshot_restore(&storage, tc.max_instances);
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => {
+                    assert!(result.unwrap_err().to_string().contains(expected_error), "{}", tc.description)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_volume_snapshots_rendering() {
+        // setup:
+        let storage = |id: &str, snapshot_retention_in_days: u16| StorageDataTemplate {
+            id: id.to_string(),
+            name: id.to_string(),
+            storage_type: "gp2".to_string(),
+            size_in_gib: 10,
+            mount_point: format!("/{}", id),
+            snapshot_retention_in_days,
+        };
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "app-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("cronjob_api_version", "batch/v1");
+        context.insert("is_storage", &true);
+        context.insert("storage", &vec![storage("data", 7), storage("cache", 0)]);
+
+        // execute:
+        context.insert("volume_snapshot_class", &None::<String>);
+        let without_snapshot_class = q_application_templates("volume-snapshots.j2.yaml", &context);
+        context.insert("volume_snapshot_class", "qovery-volume-snapshots");
+        let with_snapshot_class = q_application_templates("volume-snapshots.j2.yaml", &context);
+        context.insert("storage", &vec![storage("cache", 0)]);
+        let without_retention = q_application_templates("volume-snapshots.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, documents) in without_snapshot_class.into_iter().chain(without_retention) {
+            assert!(documents.is_empty(), "{}", lib_dir);
+        }
+        for (lib_dir, documents) in with_snapshot_class {
+            let kinds = documents
+                .iter()
+                .map(|document| document["kind"].as_str().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(vec!["ServiceAccount", "Role", "RoleBinding", "CronJob"], kinds, "{}", lib_dir);
+
+            let pod_spec = &documents[3]["spec"]["jobTemplate"]["spec"]["template"]["spec"];
+            assert_eq!(
+                "app-abcdef-volume-snapshots",
+                pod_spec["serviceAccountName"].as_str().unwrap(),
+                "{}",
+                lib_dir
+            );
+            let script = pod_spec["containers"][0]["args"][0].as_str().unwrap();
+            assert!(script.contains("\nsnapshot data 7\n"), "{}", lib_dir);
+            assert!(!script.contains("snapshot cache"), "{}", lib_dir);
+            assert!(
+                script.contains("volumeSnapshotClassName: qovery-volume-snapshots"),
+                "{}",
+                lib_dir
+            );
+            assert!(script.contains("\nEOF\n"), "{}", lib_dir);
+        }
+    }
 }
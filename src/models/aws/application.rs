@@ -1,9 +1,10 @@
+use crate::cloud_provider::kubernetes;
 use crate::cloud_provider::kubernetes::validate_k8s_required_cpu_and_burstable;
 use crate::cloud_provider::models::StorageDataTemplate;
 use crate::cloud_provider::DeploymentTarget;
 use crate::errors::EngineError;
 use crate::events::{EnvironmentStep, Stage};
-use crate::io_models::ListenersHelper;
+use crate::io_models::{ListenersHelper, Protocol};
 use crate::models::application::Application;
 use crate::models::aws::AwsStorageType;
 use crate::models::types::{ToTeraContext, AWS};
@@ -12,7 +13,22 @@ use tera::Context as TeraContext;
 impl ToTeraContext for Application<AWS> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
-        let mut context = self.default_tera_context(target.kubernetes, target.environment);
+
+        // k3s on EC2 has no load balancer controller and its security group only opens HTTPS and the kube api
+        if matches!(target.kubernetes.kind(), kubernetes::Kind::Ec2) {
+            if let Some(port) = self.ports.iter().find(|port| {
+                port.publicly_accessible && port.expose_with_load_balancer && port.protocol != Protocol::HTTP
+            }) {
+                return Err(EngineError::new_unsupported_public_port_protocol(
+                    event_details,
+                    "AWS EC2",
+                    if port.protocol == Protocol::UDP { "UDP" } else { "TCP" },
+                    port.port,
+                ));
+            }
+        }
+
+        let mut context = self.default_tera_context(target)?;
 
         let cpu_limits = match validate_k8s_required_cpu_and_burstable(
             &ListenersHelper::new(&self.listeners),
@@ -1,6 +1,7 @@
 mod application;
 mod database;
 mod database_utils;
+mod job;
 mod router;
 
 use crate::models::types::CloudProvider;
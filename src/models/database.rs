@@ -1,3 +1,4 @@
+use crate::cloud_provider::models::TolerationDataTemplate;
 use crate::cloud_provider::service::{
     check_service_version, default_tera_context, delete_stateful_service, deploy_stateful_service, get_tfstate_name,
     get_tfstate_suffix, scale_down_database, send_progress_on_long_task, Action, Create, DatabaseOptions,
@@ -302,7 +303,7 @@ where
     }
 
     #[named]
-    fn on_create_check(&self) -> Result<(), EngineError> {
+    fn on_create_check(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
         print_action(
             C::short_name(),
@@ -317,6 +318,7 @@ where
             check_domain_for(
                 ListenersHelper::new(&self.listeners),
                 vec![&self.fqdn],
+                &target.kubernetes.dns_provider().domain_check_nameservers(),
                 self.context.execution_id(),
                 self.context.execution_id(),
                 event_details,
@@ -477,7 +479,7 @@ where
 
         context.insert("namespace", environment.namespace());
 
-        let version = self.get_version(event_details)?.matched_version().to_string();
+        let version = self.get_version(event_details.clone())?.matched_version().to_string();
         context.insert("version", &version);
 
         for (k, v) in kubernetes.cloud_provider().tera_context_environment_variables() {
@@ -504,6 +506,17 @@ where
         context.insert("tfstate_suffix_name", &get_tfstate_suffix(self));
         context.insert("tfstate_name", &get_tfstate_name(self));
         context.insert("publicly_accessible", &self.publicly_accessible);
+        environment.network_policy.insert_into_tera_context(&mut context);
+        context.insert("node_selector", &kubernetes.node_selector(&options.scheduling, event_details)?);
+        context.insert(
+            "tolerations",
+            &options
+                .scheduling
+                .tolerations
+                .iter()
+                .map(TolerationDataTemplate::from)
+                .collect::<Vec<_>>(),
+        );
 
         if self.context.resource_expiration_in_seconds().is_some() {
             context.insert("resource_expiration_in_seconds", &self.context.resource_expiration_in_seconds())
@@ -12,9 +12,7 @@ use tera::Context as TeraContext;
 impl ToTeraContext for Application<DO> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
-        let kubernetes = target.kubernetes;
-        let environment = target.environment;
-        let mut context = self.default_tera_context(kubernetes, environment);
+        let mut context = self.default_tera_context(target)?;
 
         let cpu_limits = match validate_k8s_required_cpu_and_burstable(
             &ListenersHelper::new(&self.listeners),
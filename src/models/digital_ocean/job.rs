@@ -0,0 +1,42 @@
+use crate::cloud_provider::kubernetes::validate_k8s_required_cpu_and_burstable;
+use crate::cloud_provider::DeploymentTarget;
+use crate::errors::EngineError;
+use crate::events::{EnvironmentStep, Stage};
+use crate::io_models::ListenersHelper;
+use crate::models::job::Job;
+use crate::models::types::{ToTeraContext, DO};
+use tera::Context as TeraContext;
+
+impl ToTeraContext for Job<DO> {
+    fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
+        let mut context = self.default_tera_context(target)?;
+
+        let cpu_limits = match validate_k8s_required_cpu_and_burstable(
+            &ListenersHelper::new(&self.listeners),
+            self.context.execution_id(),
+            &self.id,
+            self.total_cpus(),
+            self.cpu_burst(),
+            event_details.clone(),
+            self.logger(),
+        ) {
+            Ok(l) => l,
+            Err(e) => {
+                return Err(EngineError::new_k8s_validate_required_cpu_and_burstable_error(
+                    event_details,
+                    self.total_cpus(),
+                    self.cpu_burst(),
+                    e,
+                ));
+            }
+        };
+        context.insert("cpu_burst", &cpu_limits.cpu_limit);
+
+        // This is specific to digital ocean as it is them that create the registry secret
+        // we don't have the hand on it
+        context.insert("registry_secret", "do-container-registry-secret-for-cluster");
+
+        Ok(context)
+    }
+}
@@ -1,5 +1,6 @@
 mod application;
 mod database;
+mod job;
 mod router;
 
 use crate::errors::CommandError;
@@ -0,0 +1,942 @@
+use crate::build_platform::Build;
+use crate::cloud_provider::models::{EnvironmentVariable, EnvironmentVariableDataTemplate};
+use crate::cloud_provider::pod_security::PodSecuritySettings;
+use crate::cloud_provider::service::{
+    delete_stateless_service, deploy_stateless_service_chart, helm_uninstall_release, send_progress_on_long_task,
+    Action, Create, Delete, Helm, Pause, Service, ServiceType, StatelessService,
+};
+use crate::cloud_provider::utilities::{print_action, sanitize_name};
+use crate::cloud_provider::DeploymentTarget;
+use crate::cmd::kubectl::{kubectl_exec_is_job_ready_with_retry, kubectl_exec_logs, kubectl_exec_suspend_cronjob};
+use crate::errors::{EngineError, ErrorMessageVerbosity};
+use crate::events::{EngineEvent, EnvironmentStep, EventDetails, EventMessage, Stage, ToTransmitter, Transmitter};
+use crate::io_models::{
+    Context, JobConcurrencyPolicy, JobSchedule, Listen, Listener, Listeners, ListenersHelper, ProgressInfo,
+    ProgressLevel, ProgressScope, QoveryIdentifier,
+};
+use crate::logger::Logger;
+use crate::models::types::{CloudProvider, ToTeraContext};
+use crate::utilities::to_short_id;
+use function_name::named;
+use std::marker::PhantomData;
+use tera::Context as TeraContext;
+use uuid::Uuid;
+
+/// Extra time given to a run once job to be scheduled and report its status after its maximum duration.
+const JOB_STATUS_CHECK_MARGIN_IN_SEC: u32 = 60;
+
+#[derive(thiserror::Error, Debug)]
+pub enum JobError {
+    #[error("Job invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JobSettings {
+    pub schedule: JobSchedule,
+    pub concurrency_policy: JobConcurrencyPolicy,
+    pub max_nb_restart: u32,
+    pub max_duration_in_sec: u32,
+    pub command: Vec<String>,
+    pub security: PodSecuritySettings,
+}
+
+pub struct Job<T: CloudProvider> {
+    _marker: PhantomData<T>,
+    pub(super) context: Context,
+    pub(super) id: String,
+    pub(super) long_id: Uuid,
+    pub(super) action: Action,
+    pub(super) name: String,
+    pub(super) settings: JobSettings,
+    pub(super) total_cpus: String,
+    pub(super) cpu_burst: String,
+    pub(super) total_ram_in_mib: u32,
+    pub(super) build: Build,
+    pub(super) environment_variables: Vec<EnvironmentVariable>,
+    pub(super) listeners: Listeners,
+    pub(super) logger: Box<dyn Logger>,
+}
+
+// Here we define the common behavior among all providers
+impl<T: CloudProvider> Job<T> {
+    pub fn new(
+        context: Context,
+        long_id: Uuid,
+        action: Action,
+        name: &str,
+        settings: JobSettings,
+        total_cpus: String,
+        cpu_burst: String,
+        total_ram_in_mib: u32,
+        build: Build,
+        environment_variables: Vec<EnvironmentVariable>,
+        listeners: Listeners,
+        logger: Box<dyn Logger>,
+    ) -> Result<Self, JobError> {
+        validate_job_settings(&settings)?;
+
+        Ok(Self {
+            _marker: PhantomData,
+            context,
+            id: to_short_id(&long_id),
+            long_id,
+            action,
+            name: name.to_string(),
+            settings,
+            total_cpus,
+            cpu_burst,
+            total_ram_in_mib,
+            build,
+            environment_variables,
+            listeners,
+            logger,
+        })
+    }
+
+    pub(super) fn default_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
+        let kubernetes = target.kubernetes;
+        let environment = target.environment;
+        let mut context = TeraContext::new();
+        context.insert("id", self.id());
+        context.insert("long_id", &self.long_id);
+        context.insert("owner_id", environment.owner_id.as_str());
+        context.insert("project_id", environment.project_id.as_str());
+        context.insert("organization_id", environment.organization_id.as_str());
+        context.insert("environment_id", environment.id.as_str());
+        context.insert("region", kubernetes.region().as_str());
+        context.insert("zone", kubernetes.zone());
+        context.insert("name", self.name());
+        context.insert("sanitized_name", &self.sanitized_name());
+        context.insert("namespace", environment.namespace());
+        context.insert("cluster_name", kubernetes.name());
+        context.insert("total_cpus", &self.total_cpus());
+        context.insert("total_ram_in_mib", &self.total_ram_in_mib());
+
+        context.insert("version", &self.commit_id());
+
+        let commit_id = self.build.image.commit_id.as_str();
+        context.insert("helm_app_version", &commit_id[..7]);
+        context.insert("image_name_with_tag", &self.build.image.full_image_name_with_tag());
+
+        let environment_variables = self
+            .environment_variables
+            .iter()
+            .map(|ev| EnvironmentVariableDataTemplate {
+                key: ev.key.clone(),
+                value: ev.value.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        context.insert("environment_variables", &environment_variables);
+
+        match &self.settings.schedule {
+            JobSchedule::OnDeploy => context.insert("is_cron_job", &false),
+            JobSchedule::Cron { schedule } => {
+                context.insert("is_cron_job", &true);
+                context.insert("cron_schedule", schedule);
+            }
+        }
+        context.insert("concurrency_policy", self.settings.concurrency_policy.to_k8s_format());
+        context.insert("max_nb_restart", &self.settings.max_nb_restart);
+        context.insert("max_duration_in_sec", &self.settings.max_duration_in_sec);
+        context.insert("command", &self.settings.command);
+        target.api_capabilities.insert_into_tera_context(&mut context);
+
+        let security = &self.settings.security;
+        let violations = kubernetes.pod_security_policy().violations(security);
+        if !violations.is_empty() {
+            return Err(EngineError::new_pod_security_policy_violation(
+                self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration)),
+                self.name().to_string(),
+                violations,
+            ));
+        }
+        context.insert("pod_security_context", &security.pod_security_context());
+        context.insert("container_security_context", &security.container_security_context());
+        context.insert("writable_paths", &security.writable_paths);
+
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", self.build().image.registry_host());
+
+        if self.context.resource_expiration_in_seconds().is_some() {
+            context.insert("resource_expiration_in_seconds", &self.context.resource_expiration_in_seconds())
+        }
+
+        Ok(context)
+    }
+
+    pub fn is_cron_job(&self) -> bool {
+        matches!(self.settings.schedule, JobSchedule::Cron { .. })
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn service_type(&self) -> ServiceType {
+        ServiceType::Job
+    }
+
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn commit_id(&self) -> String {
+        self.build.image.commit_id.clone()
+    }
+
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn total_cpus(&self) -> String {
+        self.total_cpus.to_string()
+    }
+
+    pub fn cpu_burst(&self) -> String {
+        self.cpu_burst.to_string()
+    }
+
+    pub fn total_ram_in_mib(&self) -> u32 {
+        self.total_ram_in_mib
+    }
+
+    pub fn logger(&self) -> &dyn Logger {
+        &*self.logger
+    }
+
+    pub fn selector(&self) -> Option<String> {
+        Some(format!("jobId={}", self.id()))
+    }
+
+    /// Send the logs of the job pods to the listeners, the job failing doesn't bring its pods down.
+    fn send_failed_job_logs(&self, target: &DeploymentTarget, event_details: EventDetails) {
+        let namespace = target.environment.namespace();
+        let selector = self.selector().unwrap_or_default();
+        let logs = target
+            .kubernetes
+            .get_kubeconfig_file_path()
+            .and_then(|kubernetes_config_file_path| {
+                kubectl_exec_logs(
+                    kubernetes_config_file_path.as_str(),
+                    namespace,
+                    selector.as_str(),
+                    target.kubernetes.cloud_provider().credentials_environment_variables(),
+                )
+                .map_err(|e| {
+                    EngineError::new_k8s_get_logs_error(
+                        event_details.clone(),
+                        selector.to_string(),
+                        namespace.to_string(),
+                        e,
+                    )
+                })
+            });
+
+        let message = match logs {
+            Ok(logs) if !logs.is_empty() => format!("Job {} logs:\n{}", self.name(), logs.join("\n")),
+            Ok(_) => format!("Job {} didn't output any logs", self.name()),
+            Err(err) => {
+                self.logger().log(EngineEvent::Warning(
+                    event_details,
+                    EventMessage::new(
+                        format!("Cannot retrieve job {} logs", self.name()),
+                        Some(err.message(ErrorMessageVerbosity::FullDetailsWithoutEnvVars)),
+                    ),
+                ));
+                format!("Job {} logs are not available", self.name())
+            }
+        };
+
+        ListenersHelper::new(&self.listeners).deployment_error(ProgressInfo::new(
+            ProgressScope::Job {
+                id: self.id().to_string(),
+            },
+            ProgressLevel::Error,
+            Some(message),
+            self.context.execution_id(),
+        ));
+    }
+
+    pub fn build(&self) -> &Build {
+        &self.build
+    }
+
+    pub fn build_mut(&mut self) -> &mut Build {
+        &mut self.build
+    }
+
+    pub fn sanitized_name(&self) -> String {
+        sanitize_name("job", self.id())
+    }
+
+    pub(crate) fn get_event_details(&self, stage: Stage) -> EventDetails {
+        let context = self.context();
+        EventDetails::new(
+            None,
+            QoveryIdentifier::from(context.organization_id().to_string()),
+            QoveryIdentifier::from(context.cluster_id().to_string()),
+            QoveryIdentifier::from(context.execution_id().to_string()),
+            None,
+            stage,
+            self.to_transmitter(),
+        )
+    }
+}
+
+pub(super) fn validate_job_settings(settings: &JobSettings) -> Result<(), JobError> {
+    if let JobSchedule::Cron { schedule } = &settings.schedule {
+        if schedule.split_whitespace().count() != 5 {
+            return Err(JobError::InvalidConfig(format!(
+                "cron schedule `{}` must have 5 fields: minute, hour, day of month, month and day of week",
+                schedule
+            )));
+        }
+    }
+
+    if settings.max_duration_in_sec == 0 {
+        return Err(JobError::InvalidConfig(
+            "max duration must be greater than 0 seconds".to_string(),
+        ));
+    }
+
+    settings
+        .security
+        .validate()
+        .map_err(|e| JobError::InvalidConfig(format!("security {}", e)))
+}
+
+// Traits implementations
+impl<T: CloudProvider> ToTransmitter for Job<T> {
+    fn to_transmitter(&self) -> Transmitter {
+        Transmitter::Job(self.id.to_string(), self.name.to_string(), self.commit_id())
+    }
+}
+
+impl<T: CloudProvider> Listen for Job<T> {
+    fn listeners(&self) -> &Listeners {
+        &self.listeners
+    }
+
+    fn add_listener(&mut self, listener: Listener) {
+        self.listeners.push(listener);
+    }
+}
+
+impl<T: CloudProvider> Service for Job<T>
+where
+    Job<T>: ToTeraContext,
+{
+    fn context(&self) -> &Context {
+        self.context()
+    }
+
+    fn service_type(&self) -> ServiceType {
+        self.service_type()
+    }
+
+    fn id(&self) -> &str {
+        self.id()
+    }
+
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn name_with_id_and_version(&self) -> String {
+        format!("{} ({}) commit: {}", self.name(), self.id(), self.commit_id())
+    }
+
+    fn sanitized_name(&self) -> String {
+        self.sanitized_name()
+    }
+
+    fn version(&self) -> String {
+        self.commit_id()
+    }
+
+    fn action(&self) -> &Action {
+        self.action()
+    }
+
+    fn private_port(&self) -> Option<u16> {
+        None
+    }
+
+    fn total_cpus(&self) -> String {
+        self.total_cpus()
+    }
+
+    fn cpu_burst(&self) -> String {
+        self.cpu_burst()
+    }
+
+    fn total_ram_in_mib(&self) -> u32 {
+        self.total_ram_in_mib()
+    }
+
+    fn min_instances(&self) -> u32 {
+        1
+    }
+
+    fn max_instances(&self) -> u32 {
+        1
+    }
+
+    fn publicly_accessible(&self) -> bool {
+        false
+    }
+
+    fn tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
+        self.to_tera_context(target)
+    }
+
+    fn logger(&self) -> &dyn Logger {
+        self.logger()
+    }
+
+    fn selector(&self) -> Option<String> {
+        self.selector()
+    }
+
+    fn long_id(&self) -> &Uuid {
+        &self.long_id
+    }
+}
+
+impl<T: CloudProvider> Helm for Job<T> {
+    fn helm_selector(&self) -> Option<String> {
+        self.selector()
+    }
+
+    fn helm_release_name(&self) -> String {
+        crate::string::cut(format!("job-{}-{}", self.id(), self.id()), 50)
+    }
+
+    fn helm_chart_dir(&self) -> String {
+        format!("{}/common/services/q-job", self.context.lib_root_dir())
+    }
+
+    fn helm_chart_values_dir(&self) -> String {
+        String::new()
+    }
+
+    fn helm_chart_external_name_service_dir(&self) -> String {
+        String::new()
+    }
+}
+
+impl<T: CloudProvider> Create for Job<T>
+where
+    Job<T>: Service,
+{
+    #[named]
+    fn on_create(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        print_action(
+            T::short_name(),
+            "job",
+            function_name!(),
+            self.name(),
+            event_details.clone(),
+            self.logger(),
+        );
+
+        send_progress_on_long_task(self, Action::Create, || {
+            // a job pod template can't be updated, the previous run is removed to start a new one
+            if !self.is_cron_job() {
+                helm_uninstall_release(
+                    target.kubernetes,
+                    target.environment,
+                    self.helm_release_name().as_str(),
+                    event_details.clone(),
+                )?;
+            }
+
+            deploy_stateless_service_chart(target, self)
+        })
+    }
+
+    fn on_create_check(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        if self.is_cron_job() {
+            return Ok(());
+        }
+
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let kubernetes = target.kubernetes;
+        let namespace = target.environment.namespace();
+        let kubernetes_config_file_path = kubernetes.get_kubeconfig_file_path()?;
+
+        let result = send_progress_on_long_task(self, Action::Create, || {
+            match kubectl_exec_is_job_ready_with_retry(
+                kubernetes_config_file_path.as_str(),
+                namespace,
+                self.sanitized_name().as_str(),
+                self.settings.max_duration_in_sec + JOB_STATUS_CHECK_MARGIN_IN_SEC,
+                kubernetes.cloud_provider().credentials_environment_variables(),
+            ) {
+                Ok(Some(true)) => Ok(()),
+                Ok(_) => Err(EngineError::new_k8s_job_failed(
+                    event_details.clone(),
+                    self.sanitized_name(),
+                    namespace.to_string(),
+                    None,
+                )),
+                Err(e) => Err(EngineError::new_k8s_job_failed(
+                    event_details.clone(),
+                    self.sanitized_name(),
+                    namespace.to_string(),
+                    Some(e),
+                )),
+            }
+        });
+
+        if result.is_err() {
+            self.send_failed_job_logs(target, event_details);
+        }
+
+        result
+    }
+
+    #[named]
+    fn on_create_error(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        print_action(
+            T::short_name(),
+            "job",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+
+        Ok(())
+    }
+}
+
+impl<T: CloudProvider> Pause for Job<T>
+where
+    Job<T>: Service,
+{
+    #[named]
+    fn on_pause(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Pause));
+        print_action(
+            T::short_name(),
+            "job",
+            function_name!(),
+            self.name(),
+            event_details.clone(),
+            self.logger(),
+        );
+
+        // a run once job is already completed, only cron jobs have future runs to suspend
+        if !self.is_cron_job() {
+            return Ok(());
+        }
+
+        let kubernetes = target.kubernetes;
+        let namespace = target.environment.namespace();
+        let kubernetes_config_file_path = kubernetes.get_kubeconfig_file_path()?;
+
+        send_progress_on_long_task(self, Action::Pause, || {
+            kubectl_exec_suspend_cronjob(
+                kubernetes_config_file_path.as_str(),
+                kubernetes.cloud_provider().credentials_environment_variables(),
+                namespace,
+                self.sanitized_name().as_str(),
+            )
+            .map_err(|e| {
+                EngineError::new_k8s_cannot_suspend_cronjob(
+                    event_details.clone(),
+                    self.sanitized_name(),
+                    namespace.to_string(),
+                    e,
+                )
+            })
+        })
+    }
+
+    fn on_pause_check(&self) -> Result<(), EngineError> {
+        Ok(())
+    }
+
+    #[named]
+    fn on_pause_error(&self, _target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Pause));
+        print_action(
+            T::short_name(),
+            "job",
+            function_name!(),
+            self.name(),
+            event_details,
+            self.logger(),
+        );
+
+        Ok(())
+    }
+}
+
+impl<T: CloudProvider> Delete for Job<T>
+where
+    Job<T>: Service,
+{
+    #[named]
+    fn on_delete(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Delete));
+        print_action(
+            T::short_name(),
+            "job",
+            function_name!(),
+            self.name(),
+            event_details.clone(),
+            self.logger(),
+        );
+
+        send_progress_on_long_task(self, Action::Delete, || {
+            delete_stateless_service(target, self, event_details.clone())
+        })
+    }
+
+    fn on_delete_check(&self) -> Result<(), EngineError> {
+        Ok(())
+    }
+
+    #[named]
+    fn on_delete_error(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Delete));
+        print_action(
+            T::short_name(),
+            "job",
+            function_name!(),
+            self.name(),
+            event_details.clone(),
+            self.logger(),
+        );
+
+        send_progress_on_long_task(self, Action::Delete, || {
+            delete_stateless_service(target, self, event_details.clone())
+        })
+    }
+}
+
+impl<T: CloudProvider> StatelessService for Job<T>
+where
+    Job<T>: Service,
+{
+    fn as_stateless_service(&self) -> &dyn StatelessService {
+        self
+    }
+}
+
+pub trait JobService: StatelessService {
+    fn get_build(&self) -> &Build;
+    fn get_build_mut(&mut self) -> &mut Build;
+}
+
+impl<T: CloudProvider> JobService for Job<T>
+where
+    Job<T>: Service,
+{
+    fn get_build(&self) -> &Build {
+        self.build()
+    }
+
+    fn get_build_mut(&mut self) -> &mut Build {
+        self.build_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_job_settings, JobSettings};
+    use crate::cloud_provider::pod_security::PodSecuritySettings;
+    use crate::io_models::{JobConcurrencyPolicy, JobSchedule};
+    use tera::Context as TeraContext;
+
+    fn q_job_template(template_name: &str, context: &TeraContext) -> Vec<serde_yaml::Value> {
+        let template = std::fs::read_to_string(format!(
+            "{}/lib/common/services/q-job/templates/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            template_name
+        ))
+        .unwrap();
+        let rendered = tera::Tera::one_off(template.as_str(), context, false).unwrap();
+
+        rendered
+            .split("\n---\n")
+            .filter_map(|document| serde_yaml::from_str::<serde_yaml::Value>(document).ok())
+            .filter(|document| !document.is_null())
+            .collect()
+    }
+
+    fn job_context(schedule: &JobSchedule) -> TeraContext {
+        let mut context = TeraContext::new();
+        context.insert("id", "abcdef");
+        context.insert("long_id", "5e8d1b1a-6fb2-4b1c-9a36-4c8ac5cbd6a2");
+        context.insert("owner_id", "owner");
+        context.insert("environment_id", "env");
+        context.insert("sanitized_name", "job-abcdef");
+        context.insert("namespace", "project-env");
+        context.insert("version", "4a8cd1f9b7bbc4b6c2e2a05e6b25d8b8a5e2fce4");
+        context.insert("image_name_with_tag", "registry.io/job:4a8cd1f");
+        context.insert("total_cpus", "500m");
+        context.insert("cpu_burst", "1");
+        context.insert("total_ram_in_mib", &256);
+        context.insert("environment_variables", &Vec::<String>::new());
+        context.insert("is_registry_secret", &true);
+        context.insert("registry_secret", "registry-token-abcdef");
+        context.insert("concurrency_policy", "Forbid");
+        context.insert("max_nb_restart", &2);
+        context.insert("max_duration_in_sec", &600);
+        context.insert("cronjob_api_version", "batch/v1");
+        match schedule {
+            JobSchedule::OnDeploy => context.insert("is_cron_job", &false),
+            JobSchedule::Cron { schedule } => {
+                context.insert("is_cron_job", &true);
+                context.insert("cron_schedule", schedule);
+            }
+        }
+
+        context
+    }
+
+    #[test]
+    fn test_validate_job_settings() {
+        // setup:
+        struct TestCase<'a> {
+            schedule: JobSchedule,
+            max_duration_in_sec: u32,
+            expected_error: Option<&'a str>,
+            description: &'a str,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                schedule: JobSchedule::OnDeploy,
+                max_duration_in_sec: 300,
+                expected_error: None,
+                description: "run once job",
+            },
+            TestCase {
+                schedule: JobSchedule::Cron {
+                    schedule: "*/15 * * * *".to_string(),
+                },
+                max_duration_in_sec: 300,
+                expected_error: None,
+                description: "cron job",
+            },
+            TestCase {
+                schedule: JobSchedule::Cron {
+                    schedule: "0 */15 * * * *".to_string(),
+                },
+                max_duration_in_sec: 300,
+                expected_error: Some("must have 5 fields"),
+                description: "cron schedule with seconds",
+            },
+            TestCase {
+                schedule: JobSchedule::Cron {
+                    schedule: "@hourly".to_string(),
+                },
+                max_duration_in_sec: 300,
+                expected_error: Some("must have 5 fields"),
+                description: "cron schedule macro",
+            },
+            TestCase {
+                schedule: JobSchedule::OnDeploy,
+                max_duration_in_sec: 0,
+                expected_error: Some("max duration"),
+                description: "no max duration",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_job_settings(&JobSettings {
+                schedule: tc.schedule,
+                concurrency_policy: JobConcurrencyPolicy::default(),
+                max_nb_restart: 0,
+                max_duration_in_sec: tc.max_duration_in_sec,
+                command: vec![],
+                security: PodSecuritySettings::default(),
+            });
+
+            // verify:
+            match tc.expected_error {
+                None => assert!(result.is_ok(), "{}", tc.description),
+                Some(expected_error) => {
+                    assert!(result.unwrap_err().to_string().contains(expected_error), "{}", tc.description)
+                }
+            }
+        }
+
+        let root_without_uid = JobSettings {
+            schedule: JobSchedule::OnDeploy,
+            concurrency_policy: JobConcurrencyPolicy::default(),
+            max_nb_restart: 0,
+            max_duration_in_sec: 300,
+            command: vec![],
+            security: PodSecuritySettings {
+                run_as_non_root: true,
+                ..PodSecuritySettings::default()
+            },
+        };
+        assert!(validate_job_settings(&root_without_uid).is_err());
+    }
+
+    #[test]
+    fn test_job_schedule_deserialization() {
+        // execute:
+        let on_deploy: JobSchedule = serde_json::from_str(r#"{"type": "on_deploy"}"#).unwrap();
+        let cron: JobSchedule = serde_json::from_str(r#"{"type": "cron", "schedule": "0 3 * * *"}"#).unwrap();
+
+        // verify:
+        assert_eq!(JobSchedule::OnDeploy, on_deploy);
+        assert_eq!(
+            JobSchedule::Cron {
+                schedule: "0 3 * * *".to_string()
+            },
+            cron
+        );
+    }
+
+    #[test]
+    fn test_job_rendering() {
+        // setup:
+        let mut context = job_context(&JobSchedule::OnDeploy);
+        context.insert("command", &vec!["rails", "db:migrate"]);
+
+        // execute:
+        let jobs = q_job_template("job.j2.yaml", &context);
+        let cron_jobs = q_job_template("cronjob.j2.yaml", &context);
+
+        // verify:
+        assert!(cron_jobs.is_empty());
+        assert_eq!(1, jobs.len());
+        let job = &jobs[0];
+        assert_eq!("Job", job["kind"].as_str().unwrap());
+        assert_eq!(2, job["spec"]["backoffLimit"].as_u64().unwrap());
+        assert_eq!(600, job["spec"]["activeDeadlineSeconds"].as_u64().unwrap());
+        let pod_template = &job["spec"]["template"];
+        assert_eq!("abcdef", pod_template["metadata"]["labels"]["jobId"].as_str().unwrap());
+        assert_eq!("Never", pod_template["spec"]["restartPolicy"].as_str().unwrap());
+        assert_eq!(
+            vec!["rails", "db:migrate"],
+            pod_template["spec"]["containers"][0]["args"]
+                .as_sequence()
+                .unwrap()
+                .iter()
+                .map(|arg| arg.as_str().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "registry-token-abcdef",
+            pod_template["spec"]["imagePullSecrets"][0]["name"].as_str().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cron_job_rendering() {
+        // setup:
+        let context = job_context(&JobSchedule::Cron {
+            schedule: "*/15 * * * *".to_string(),
+        });
+
+        // execute:
+        let jobs = q_job_template("job.j2.yaml", &context);
+        let cron_jobs = q_job_template("cronjob.j2.yaml", &context);
+
+        // verify:
+        assert!(jobs.is_empty());
+        assert_eq!(1, cron_jobs.len());
+        let cron_job = &cron_jobs[0];
+        assert_eq!("batch/v1", cron_job["apiVersion"].as_str().unwrap());
+        assert_eq!("CronJob", cron_job["kind"].as_str().unwrap());
+        assert_eq!("*/15 * * * *", cron_job["spec"]["schedule"].as_str().unwrap());
+        assert_eq!("Forbid", cron_job["spec"]["concurrencyPolicy"].as_str().unwrap());
+        assert!(!cron_job["spec"]["suspend"].as_bool().unwrap());
+        let job_spec = &cron_job["spec"]["jobTemplate"]["spec"];
+        assert_eq!(600, job_spec["activeDeadlineSeconds"].as_u64().unwrap());
+        assert_eq!("abcdef", job_spec["template"]["metadata"]["labels"]["jobId"].as_str().unwrap());
+        assert!(job_spec["template"]["spec"]["containers"][0]["args"].is_null());
+    }
+
+    #[test]
+    fn test_job_security_contexts_rendering() {
+        // setup:
+        let security = PodSecuritySettings {
+            run_as_non_root: true,
+            run_as_user: Some(1000),
+            read_only_root_filesystem: true,
+            writable_paths: vec!["/tmp".to_string()],
+            ..PodSecuritySettings::default()
+        };
+        let schedules = [
+            (JobSchedule::OnDeploy, "job.j2.yaml", vec!["spec", "template", "spec"]),
+            (
+                JobSchedule::Cron {
+                    schedule: "*/15 * * * *".to_string(),
+                },
+                "cronjob.j2.yaml",
+                vec!["spec", "jobTemplate", "spec", "template", "spec"],
+            ),
+        ];
+
+        for (schedule, template, pod_spec_path) in schedules {
+            let mut context = job_context(&schedule);
+            context.insert("pod_security_context", &security.pod_security_context());
+            context.insert("container_security_context", &security.container_security_context());
+            context.insert("writable_paths", &security.writable_paths);
+
+            // execute:
+            let documents = q_job_template(template, &context);
+
+            // verify:
+            let pod_spec = pod_spec_path.iter().fold(&documents[0], |value, key| &value[*key]);
+            assert_eq!(1000, pod_spec["securityContext"]["runAsUser"].as_u64().unwrap(), "{}", template);
+            let container = &pod_spec["containers"][0];
+            assert!(
+                container["securityContext"]["readOnlyRootFilesystem"]
+                    .as_bool()
+                    .unwrap(),
+                "{}",
+                template
+            );
+            assert_eq!(
+                "/tmp",
+                container["volumeMounts"][0]["mountPath"].as_str().unwrap(),
+                "{}",
+                template
+            );
+            assert!(!pod_spec["volumes"][0]["emptyDir"].is_null(), "{}", template);
+        }
+    }
+
+    #[test]
+    fn test_job_registry_secret_rendering() {
+        // setup:
+        let mut context = job_context(&JobSchedule::OnDeploy);
+
+        // execute:
+        let secrets = q_job_template("secret.j2.yaml", &context);
+        context.insert("container_registry_docker_json_config", "eyJhdXRocyI6e319");
+        let secrets_with_registry = q_job_template("secret.j2.yaml", &context);
+
+        // verify:
+        assert_eq!(1, secrets.len());
+        assert_eq!(2, secrets_with_registry.len());
+        assert_eq!(
+            "registry-token-abcdef",
+            secrets_with_registry[1]["metadata"]["name"].as_str().unwrap()
+        );
+        assert_eq!(
+            "kubernetes.io/dockerconfigjson",
+            secrets_with_registry[1]["type"].as_str().unwrap()
+        );
+    }
+}
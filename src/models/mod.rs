@@ -3,6 +3,7 @@ pub mod aws;
 pub mod database;
 pub(crate) mod database_utils;
 pub mod digital_ocean;
+pub mod job;
 pub mod router;
 pub mod scaleway;
 pub mod types;
@@ -1,26 +1,41 @@
+use crate::cloud_provider::acme::AcmeSolver;
+use crate::cloud_provider::certificate::{validate_certificate, CertificateInfo};
+use crate::cloud_provider::environment::Environment;
 use crate::cloud_provider::helm::ChartInfo;
-use crate::cloud_provider::models::{CustomDomain, CustomDomainDataTemplate, Route, RouteDataTemplate};
+use crate::cloud_provider::models::{
+    CanaryRouteDataTemplate, CustomDomain, CustomDomainCertificateDataTemplate, CustomDomainDataTemplate, Route,
+    RouteDataTemplate, RouteGroupDataTemplate,
+};
 use crate::cloud_provider::service::{
     default_tera_context, delete_stateless_service, deploy_stateless_service_error, send_progress_on_long_task, Action,
     Create, Delete, Helm, Pause, RouterService, Service, ServiceType, StatelessService,
 };
-use crate::cloud_provider::utilities::{check_cname_for, print_action, sanitize_name};
+use crate::cloud_provider::utilities::{print_action, sanitize_name, verify_custom_domain};
 use crate::cloud_provider::DeploymentTarget;
 use crate::cmd::helm;
 use crate::cmd::helm::to_engine_error;
-use crate::errors::EngineError;
+use crate::cmd::kubectl::kubectl_exec_get_ingresses;
+use crate::errors::{EngineError, ErrorMessageVerbosity};
 use crate::events::{EngineEvent, EnvironmentStep, EventMessage, Stage, ToTransmitter, Transmitter};
-use crate::io_models::{Context, Listen, Listener, Listeners};
+use crate::io_models::{
+    Context, Listen, Listener, Listeners, ListenersHelper, ProgressInfo, ProgressLevel, Protocol, RouteBackendProtocol,
+    RouterAdvancedSettings,
+};
 use crate::logger::Logger;
 use crate::models::types::CloudProvider;
 use crate::models::types::ToTeraContext;
 use crate::utilities::to_short_id;
 use function_name::named;
 use std::borrow::Borrow;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::net::IpAddr;
 use tera::Context as TeraContext;
 use uuid::Uuid;
 
+/// Timeout, in seconds, of gRPC and WebSocket routes which keep connections open.
+const LONG_LIVED_CONNECTION_TIMEOUT_SECONDS: u32 = 3600;
+
 #[derive(thiserror::Error, Debug)]
 pub enum RouterError {
     #[error("Router invalid configuration: {0}")]
@@ -38,6 +53,7 @@ pub struct Router<T: CloudProvider> {
     pub(crate) custom_domains: Vec<CustomDomain>,
     pub(crate) sticky_sessions_enabled: bool,
     pub(crate) routes: Vec<Route>,
+    pub(crate) advanced_settings: RouterAdvancedSettings,
     pub(crate) listeners: Listeners,
     pub(crate) logger: Box<dyn Logger>,
     pub(crate) _extra_settings: T::RouterExtraSettings,
@@ -53,10 +69,23 @@ impl<T: CloudProvider> Router<T> {
         custom_domains: Vec<CustomDomain>,
         routes: Vec<Route>,
         sticky_sessions_enabled: bool,
+        advanced_settings: RouterAdvancedSettings,
         extra_settings: T::RouterExtraSettings,
         listeners: Listeners,
         logger: Box<dyn Logger>,
     ) -> Result<Self, RouterError> {
+        validate_advanced_settings(&advanced_settings)?;
+        for route in &routes {
+            validate_route(route)?;
+        }
+        for custom_domain in &custom_domains {
+            if let Some(certificate) = &custom_domain.certificate {
+                validate_certificate(custom_domain.domain.as_str(), certificate).map_err(|e| {
+                    RouterError::InvalidConfig(format!("invalid certificate for {}: {}", custom_domain.domain, e))
+                })?;
+            }
+        }
+
         Ok(Self {
             _marker: PhantomData,
             context,
@@ -68,6 +97,7 @@ impl<T: CloudProvider> Router<T> {
             custom_domains,
             sticky_sessions_enabled,
             routes,
+            advanced_settings,
             listeners,
             logger,
             _extra_settings: extra_settings,
@@ -78,6 +108,227 @@ impl<T: CloudProvider> Router<T> {
         Some(format!("routerId={}", self.id))
     }
 
+    fn basic_auth_secret_name(&self) -> String {
+        format!("router-basic-auth-{}", self.id)
+    }
+
+    /// Warns about user supplied certificates expired or close to their expiration, as nothing renews them.
+    fn check_certificates_expiration(&self)
+    where
+        Self: Service,
+    {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let listeners_helper = ListenersHelper::new(self.listeners());
+
+        for custom_domain in &self.custom_domains {
+            let certificate_info = match &custom_domain.certificate {
+                Some(certificate) => validate_certificate(custom_domain.domain.as_str(), certificate),
+                None => continue,
+            };
+
+            let message = match certificate_info {
+                Ok(CertificateInfo {
+                    validity_period_error: Some(e),
+                    ..
+                }) => format!(
+                    "Certificate of custom domain {} is not valid anymore: {}, please provide a renewed certificate.",
+                    custom_domain.domain, e
+                ),
+                Ok(info) if info.expires_soon() => format!(
+                    "Certificate of custom domain {} expires in {} days, please provide a renewed certificate.",
+                    custom_domain.domain, info.days_before_expiration
+                ),
+                Ok(_) => continue,
+                Err(e) => format!(
+                    "Certificate of custom domain {} is not valid anymore: {}",
+                    custom_domain.domain, e
+                ),
+            };
+
+            listeners_helper.deployment_in_progress(ProgressInfo::new(
+                self.progress_scope(),
+                ProgressLevel::Warn,
+                Some(message.to_string()),
+                self.context.execution_id(),
+            ));
+            self.logger().log(EngineEvent::Warning(
+                event_details.clone(),
+                EventMessage::new_from_safe(message),
+            ));
+        }
+    }
+
+    /// Reports how the traffic of split routes is effectively shared between applications.
+    fn report_traffic_splits(&self, environment: &Environment)
+    where
+        Self: Service,
+    {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let listeners_helper = ListenersHelper::new(self.listeners());
+
+        for route in self.routes.iter().filter(|route| route.canary().is_some()) {
+            let message = match canary_route_data_template(route, environment) {
+                Some(canary) => format!(
+                    "Route {} traffic is split: {}% to {}, {}% to {}.",
+                    route.path,
+                    100 - canary.weight,
+                    route.application_name,
+                    canary.weight,
+                    route.canary().map(|c| c.application_name.as_str()).unwrap_or_default(),
+                ),
+                None => format!("Route {} traffic isn't split: 100% to {}.", route.path, route.application_name),
+            };
+
+            listeners_helper.deployment_in_progress(ProgressInfo::new(
+                self.progress_scope(),
+                ProgressLevel::Info,
+                Some(message.to_string()),
+                self.context.execution_id(),
+            ));
+            self.logger()
+                .log(EngineEvent::Info(event_details.clone(), EventMessage::new_from_safe(message)));
+        }
+    }
+
+    /// Custom domains the router Ingress currently routes.
+    fn routed_custom_domains(&self, target: &DeploymentTarget) -> Vec<String>
+    where
+        Self: Service,
+    {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let ingresses = target
+            .kubernetes
+            .get_kubeconfig_file_path()
+            .map_err(|e| e.to_string())
+            .and_then(|kubeconfig_path| {
+                kubectl_exec_get_ingresses(
+                    kubeconfig_path,
+                    target.environment.namespace(),
+                    self.selector().unwrap_or_default().as_str(),
+                    target.kubernetes.cloud_provider().credentials_environment_variables(),
+                )
+                .map_err(|e| e.message(ErrorMessageVerbosity::SafeOnly))
+            });
+
+        match ingresses {
+            Ok(ingresses) => ingresses
+                .items
+                .into_iter()
+                .flat_map(|ingress| ingress.spec.rules)
+                .filter_map(|rule| rule.host)
+                .filter(|host| self.custom_domains.iter().any(|cd| &cd.domain == host))
+                .collect(),
+            Err(e) => {
+                self.logger().log(EngineEvent::Warning(
+                    event_details,
+                    EventMessage::new(
+                        "Cannot get the router Ingress, all custom domains have to be verified to be routed."
+                            .to_string(),
+                        Some(e),
+                    ),
+                ));
+                vec![]
+            }
+        }
+    }
+
+    /// Returns custom domains whose ownership is verified, only those get an Ingress rule and a certificate.
+    /// Domains routed before the verification existed are kept, only new domains require it.
+    fn verified_custom_domains(&self, target: &DeploymentTarget) -> Vec<&CustomDomain>
+    where
+        Self: Service,
+    {
+        let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
+        let nameservers = target.kubernetes.dns_provider().domain_check_nameservers();
+        let acme_solver = target.kubernetes.acme_settings().solver;
+        let routed_custom_domains = self.routed_custom_domains(target);
+
+        let custom_domains = self.custom_domains.iter().filter(|custom_domain| {
+            // HTTP-01 challenges can't issue wildcard certificates
+            if custom_domain.domain.starts_with("*.")
+                && custom_domain.certificate.is_none()
+                && acme_solver == AcmeSolver::Http01
+            {
+                self.logger().log(EngineEvent::Warning(
+                    event_details.clone(),
+                    EventMessage::new_from_safe(format!(
+                        "Wildcard custom domain {} requires a certificate or the DNS-01 ACME solver, it will not be routed.",
+                        custom_domain.domain
+                    )),
+                ));
+                return false;
+            }
+
+            true
+        });
+
+        // each verification retries for up to a minute, domains are verified concurrently
+        let progress_scope = self.progress_scope();
+        let listeners = self.listeners();
+        let execution_id = self.context.execution_id();
+        let verifications = std::thread::scope(|scope| {
+            custom_domains
+                .map(|custom_domain| {
+                    let is_already_routed = routed_custom_domains.contains(&custom_domain.domain);
+                    let progress_scope = progress_scope.clone();
+                    let nameservers = &nameservers;
+                    let verification = scope.spawn(move || {
+                        verify_custom_domain(
+                            progress_scope,
+                            listeners,
+                            custom_domain,
+                            is_already_routed,
+                            nameservers,
+                            execution_id,
+                        )
+                    });
+                    (custom_domain, is_already_routed, verification)
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|(custom_domain, is_already_routed, verification)| {
+                    (custom_domain, is_already_routed, verification.join())
+                })
+                .collect::<Vec<_>>()
+        });
+
+        verifications
+            .into_iter()
+            .filter(|(custom_domain, is_already_routed, verification)| {
+                let verification = match verification {
+                    Ok(verification) => verification,
+                    Err(_) => {
+                        self.logger().log(EngineEvent::Warning(
+                            event_details.clone(),
+                            EventMessage::new_from_safe(format!(
+                                "Custom domain {} verification failed unexpectedly.",
+                                custom_domain.domain
+                            )),
+                        ));
+                        return *is_already_routed;
+                    }
+                };
+
+                if !verification.is_verified() {
+                    let routing = match is_already_routed {
+                        true => "it stays routed as it already was",
+                        false => "it will not be routed",
+                    };
+                    self.logger().log(EngineEvent::Warning(
+                        event_details.clone(),
+                        EventMessage::new(
+                            format!("Custom domain {} is not verified, {}.", custom_domain.domain, routing),
+                            Some(format!("{:?}", verification.status)),
+                        ),
+                    ));
+                }
+
+                verification.is_verified() || *is_already_routed
+            })
+            .map(|(custom_domain, _, _)| custom_domain)
+            .collect()
+    }
+
     pub(crate) fn default_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError>
     where
         Self: Service,
@@ -87,40 +338,47 @@ impl<T: CloudProvider> Router<T> {
         let environment = target.environment;
         let mut context = default_tera_context(self, kubernetes, environment);
 
-        let applications = environment
-            .stateless_services()
-            .into_iter()
-            .filter(|x| x.service_type() == ServiceType::Application)
-            .collect::<Vec<_>>();
-
-        let custom_domain_data_templates = self
-            .custom_domains
-            .iter()
-            .map(|cd| {
-                let domain_hash = crate::crypto::to_sha1_truncate_16(cd.domain.as_str());
-                CustomDomainDataTemplate {
-                    domain: cd.domain.clone(),
-                    domain_hash,
-                    target_domain: cd.target_domain.clone(),
-                }
-            })
-            .collect::<Vec<_>>();
-
+        let router_annotations = nginx_annotations(&self.advanced_settings, self.basic_auth_secret_name().as_str());
+        let mut canary_route_data_templates = vec![];
         let route_data_templates = self
             .routes
             .iter()
             .filter_map(|r| {
-                match applications
+                let application = environment
+                    .applications
                     .iter()
-                    .find(|app| app.name() == r.application_name.as_str())
-                {
-                    Some(application) => application.private_port().map(|private_port| RouteDataTemplate {
-                        path: r.path.clone(),
-                        application_name: application.sanitized_name(),
-                        application_port: private_port,
-                    }),
-                    _ => None,
+                    .find(|app| app.name() == r.application_name.as_str())?;
+
+                // ingress-nginx only proxies HTTP, gRPC and WebSocket are upgrades of HTTP connections
+                if !matches!(application.public_port_protocol(), Some(Protocol::HTTP)) {
+                    self.logger().log(EngineEvent::Warning(
+                        event_details.clone(),
+                        EventMessage::new_from_safe(format!(
+                            "Route {} is ignored, application {} doesn't expose an HTTP port.",
+                            r.path, r.application_name
+                        )),
+                    ));
+                    return None;
                 }
+
+                if let Some(canary) = r.canary() {
+                    match canary_route_data_template(r, environment) {
+                        Some(canary_route_data_template) => {
+                            canary_route_data_templates.push(canary_route_data_template)
+                        }
+                        None => self.logger().log(EngineEvent::Warning(
+                            event_details.clone(),
+                            EventMessage::new_from_safe(format!(
+                                "Route {} traffic isn't split, application {} doesn't expose an HTTP port.",
+                                r.path, canary.application_name
+                            )),
+                        )),
+                    }
+                }
+
+                application.private_port().map(|private_port| {
+                    route_data_template(r, application.sanitized_name(), private_port, &router_annotations)
+                })
             })
             .collect::<Vec<_>>();
 
@@ -171,24 +429,397 @@ impl<T: CloudProvider> Router<T> {
         context.insert("router_tls_domain", tls_domain.to_string().as_str());
         context.insert("router_default_domain", self.default_domain.as_str());
         context.insert("router_default_domain_hash", router_default_domain_hash.as_str());
-        context.insert("custom_domains", &custom_domain_data_templates);
+        insert_custom_domains(&mut context, self.id(), &self.custom_domains.iter().collect::<Vec<_>>());
+        let (rewrite_route_data_templates, route_data_templates): (Vec<_>, Vec<_>) = route_data_templates
+            .into_iter()
+            .partition(|route| route.rewrite_target.is_some());
+        let (route_data_templates, route_group_data_templates) = route_groups(route_data_templates);
         context.insert("routes", &route_data_templates);
-        context.insert("spec_acme_email", "tls@qovery.com"); // TODO CHANGE ME
-        context.insert("metadata_annotations_cert_manager_cluster_issuer", "letsencrypt-qovery");
-
-        let lets_encrypt_url = match self.context.is_test_cluster() {
-            true => "https://acme-staging-v02.api.letsencrypt.org/directory",
-            false => "https://acme-v02.api.letsencrypt.org/directory",
-        };
-        context.insert("spec_acme_server", lets_encrypt_url);
+        context.insert("route_groups", &route_group_data_templates);
+        context.insert("canary_routes", &canary_route_data_templates);
+        context.insert("rewrite_routes", &rewrite_route_data_templates);
+        kubernetes.acme_settings().insert_into_tera_context(&mut context);
 
         // Nginx
         context.insert("sticky_sessions_enabled", &self.sticky_sessions_enabled);
+        context.insert("router_annotations", &router_annotations);
+        context.insert("router_basic_auth_secret_name", &self.basic_auth_secret_name());
+        context.insert("router_basic_auth_htpasswd", &self.advanced_settings.basic_auth_htpasswd);
+
+        // Ingress manifests depend on APIs served by the cluster
+        target.api_capabilities.insert_into_tera_context(&mut context);
 
         Ok(context)
     }
 }
 
+fn route_data_template(
+    route: &Route,
+    application_name: String,
+    application_port: u16,
+    router_annotations: &BTreeMap<String, String>,
+) -> RouteDataTemplate {
+    let annotations = route_annotations(router_annotations, route.backend_protocol);
+
+    match &route.rewrite {
+        None => RouteDataTemplate {
+            path: route.path.clone(),
+            application_name,
+            application_port,
+            rewrite_target: None,
+            backend_protocol: route.backend_protocol,
+            annotations,
+        },
+        Some(rewrite) => {
+            // the path becomes a regex, the last group captures what follows the route path
+            let prefix = route.path.trim_end_matches('/');
+            let path = match prefix.is_empty() {
+                true => "/()(.*)".to_string(),
+                false => format!("{}(/|$)(.*)", prefix),
+            };
+            let target = rewrite.target.trim_end_matches('/');
+            let rewrite_target = match rewrite.strip_prefix {
+                true => format!("{}/$2", target),
+                false => format!("{}{}/$2", target, prefix),
+            };
+
+            RouteDataTemplate {
+                path,
+                application_name,
+                application_port,
+                rewrite_target: Some(rewrite_target),
+                backend_protocol: route.backend_protocol,
+                annotations,
+            }
+        }
+    }
+}
+
+/// Canary Ingress path of a split route, if the weighted application can be routed.
+fn canary_route_data_template(route: &Route, environment: &Environment) -> Option<CanaryRouteDataTemplate> {
+    let canary = route.canary()?;
+    let application = environment
+        .applications
+        .iter()
+        .find(|app| app.name() == canary.application_name.as_str())
+        .filter(|app| matches!(app.public_port_protocol(), Some(Protocol::HTTP)))?;
+
+    Some(CanaryRouteDataTemplate {
+        path: route.path.clone(),
+        application_name: application.sanitized_name(),
+        application_port: application.private_port()?,
+        weight: canary.weight,
+    })
+}
+
+/// Router annotations completed with the ones required by the route backend protocol.
+fn route_annotations(
+    router_annotations: &BTreeMap<String, String>,
+    backend_protocol: RouteBackendProtocol,
+) -> BTreeMap<String, String> {
+    let mut annotations = router_annotations.clone();
+    if backend_protocol == RouteBackendProtocol::HTTP {
+        return annotations;
+    }
+
+    annotations.insert(
+        "nginx.ingress.kubernetes.io/backend-protocol".to_string(),
+        backend_protocol.to_nginx_format().to_string(),
+    );
+
+    // streams and WebSockets stay idle between messages, they must not be cut by the default timeouts
+    if backend_protocol.is_long_lived() {
+        for timeout in &["proxy-send-timeout", "proxy-read-timeout"] {
+            let key = format!("nginx.ingress.kubernetes.io/{}", timeout);
+            let seconds = annotations
+                .get(&key)
+                .and_then(|seconds| seconds.parse::<u32>().ok())
+                .unwrap_or_default()
+                .max(LONG_LIVED_CONNECTION_TIMEOUT_SECONDS);
+            annotations.insert(key, seconds.to_string());
+        }
+    }
+
+    annotations
+}
+
+/// Splits plain routes between HTTP routes, served by the main Ingress, and one group per other backend protocol,
+/// as the backend protocol annotation applies to all paths of an Ingress.
+fn route_groups(routes: Vec<RouteDataTemplate>) -> (Vec<RouteDataTemplate>, Vec<RouteGroupDataTemplate>) {
+    let (http_routes, mut other_routes): (Vec<_>, Vec<_>) = routes
+        .into_iter()
+        .partition(|route| route.backend_protocol == RouteBackendProtocol::HTTP);
+
+    let mut groups = vec![];
+    for backend_protocol in &[
+        RouteBackendProtocol::HTTPS,
+        RouteBackendProtocol::GRPC,
+        RouteBackendProtocol::GRPCS,
+        RouteBackendProtocol::WS,
+    ] {
+        let (group_routes, remaining_routes): (Vec<_>, Vec<_>) = other_routes
+            .into_iter()
+            .partition(|route| route.backend_protocol == *backend_protocol);
+        other_routes = remaining_routes;
+
+        if let Some(first_route) = group_routes.first() {
+            groups.push(RouteGroupDataTemplate {
+                name: format!("{:?}", backend_protocol).to_lowercase(),
+                annotations: first_route.annotations.clone(),
+                routes: group_routes,
+            });
+        }
+    }
+
+    (http_routes, groups)
+}
+
+fn is_plain_path(path: &str) -> bool {
+    path.starts_with('/')
+        && !path
+            .chars()
+            .any(|c| c.is_whitespace() || "(){}[]*+?^$|\\\"'".contains(c))
+}
+
+fn validate_route(route: &Route) -> Result<(), RouterError> {
+    if let Some(rewrite) = &route.rewrite {
+        if matches!(route.backend_protocol, RouteBackendProtocol::GRPC | RouteBackendProtocol::GRPCS) {
+            return Err(RouterError::InvalidConfig(format!(
+                "route `{}` can't be rewritten, gRPC paths are service methods",
+                route.path
+            )));
+        }
+
+        if !is_plain_path(route.path.as_str()) {
+            return Err(RouterError::InvalidConfig(format!(
+                "route path `{}` should be an absolute path without regex characters to be rewritten",
+                route.path
+            )));
+        }
+
+        if !is_plain_path(rewrite.target.as_str()) {
+            return Err(RouterError::InvalidConfig(format!(
+                "rewrite target `{}` should be an absolute path without regex characters",
+                rewrite.target
+            )));
+        }
+    }
+
+    if !route.weighted_applications.is_empty() {
+        validate_weighted_applications(route)?;
+    }
+
+    Ok(())
+}
+
+/// ingress-nginx applies a single canary Ingress per path, a route is split between two applications at most.
+fn validate_weighted_applications(route: &Route) -> Result<(), RouterError> {
+    let invalid = |reason: &str| {
+        Err(RouterError::InvalidConfig(format!(
+            "route `{}` traffic split is invalid: {}",
+            route.path, reason
+        )))
+    };
+
+    if route.rewrite.is_some() {
+        return invalid("rewritten routes can't be split");
+    }
+
+    if route.weighted_applications.len() > 2 {
+        return invalid("traffic can be split between two applications at most");
+    }
+
+    let routed_application_count = route
+        .weighted_applications
+        .iter()
+        .filter(|weighted_application| weighted_application.application_name == route.application_name)
+        .count();
+    if routed_application_count != 1 {
+        return invalid("the route application must be weighted exactly once");
+    }
+
+    let total_weight = route
+        .weighted_applications
+        .iter()
+        .map(|weighted_application| weighted_application.weight)
+        .sum::<u32>();
+    if total_weight != 100 {
+        return invalid(format!("weights total {} instead of 100", total_weight).as_str());
+    }
+
+    Ok(())
+}
+
+fn is_valid_cidr(cidr: &str) -> bool {
+    let (ip, prefix_length) = match cidr.split_once('/') {
+        Some((ip, prefix_length)) => (ip, Some(prefix_length)),
+        None => (cidr, None),
+    };
+
+    let max_prefix_length = match ip.parse::<IpAddr>() {
+        Ok(IpAddr::V4(_)) => 32,
+        Ok(IpAddr::V6(_)) => 128,
+        Err(_) => return false,
+    };
+
+    match prefix_length {
+        None => true,
+        Some(prefix_length) => matches!(prefix_length.parse::<u8>(), Ok(length) if length <= max_prefix_length),
+    }
+}
+
+fn validate_advanced_settings(settings: &RouterAdvancedSettings) -> Result<(), RouterError> {
+    let invalid_config = |msg: String| Err(RouterError::InvalidConfig(msg));
+
+    if settings.enable_cors {
+        if settings.cors_allow_origins.is_empty() || settings.cors_allow_methods.is_empty() {
+            return invalid_config("CORS allowed origins and methods cannot be empty".to_string());
+        }
+
+        if let Some(origin) = settings.cors_allow_origins.iter().find(|origin| {
+            origin.as_str() != "*" && !(origin.starts_with("http://") || origin.starts_with("https://"))
+                || origin.contains(|c: char| c == ',' || c.is_whitespace())
+        }) {
+            return invalid_config(format!("invalid CORS origin `{}`", origin));
+        }
+
+        let http_methods = ["GET", "HEAD", "PUT", "POST", "DELETE", "PATCH", "OPTIONS"];
+        if let Some(method) = settings
+            .cors_allow_methods
+            .iter()
+            .find(|method| !http_methods.contains(&method.as_str()))
+        {
+            return invalid_config(format!("invalid CORS method `{}`", method));
+        }
+
+        if let Some(header) = settings
+            .cors_allow_headers
+            .iter()
+            .find(|header| header.is_empty() || !header.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+        {
+            return invalid_config(format!("invalid CORS header `{}`", header));
+        }
+    }
+
+    if let Some(cidr) = settings
+        .whitelist_source_ranges
+        .iter()
+        .find(|cidr| !is_valid_cidr(cidr))
+    {
+        return invalid_config(format!("invalid source range `{}`, it should be an IP or a CIDR", cidr));
+    }
+
+    if let Some(htpasswd) = &settings.basic_auth_htpasswd {
+        let is_valid_htpasswd = htpasswd.lines().any(|line| !line.trim().is_empty())
+            && htpasswd
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .all(|line| matches!(line.split_once(':'), Some((user, hash)) if !user.is_empty() && !hash.is_empty()));
+        if !is_valid_htpasswd {
+            return invalid_config("basic auth htpasswd should contain `user:hashed_password` lines".to_string());
+        }
+    }
+
+    if settings.limit_rps == Some(0) || settings.limit_connections == Some(0) {
+        return invalid_config("rate and connection limits should be greater than 0".to_string());
+    }
+
+    if settings.proxy_body_size_mb == 0 {
+        return invalid_config("proxy body size should be greater than 0".to_string());
+    }
+
+    for timeout in &[
+        settings.proxy_connect_timeout_seconds,
+        settings.proxy_send_timeout_seconds,
+        settings.proxy_read_timeout_seconds,
+    ] {
+        if *timeout == 0 || *timeout > 3600 {
+            return invalid_config(format!("proxy timeout `{}` should be between 1 and 3600 seconds", timeout));
+        }
+    }
+
+    Ok(())
+}
+
+/// nginx-ingress annotations rendered on all router Ingresses.
+fn nginx_annotations(settings: &RouterAdvancedSettings, basic_auth_secret_name: &str) -> BTreeMap<String, String> {
+    let mut annotations = BTreeMap::new();
+    let mut annotate = |key: &str, value: String| {
+        annotations.insert(format!("nginx.ingress.kubernetes.io/{}", key), value);
+    };
+
+    annotate("proxy-body-size", format!("{}m", settings.proxy_body_size_mb));
+    annotate("proxy-connect-timeout", settings.proxy_connect_timeout_seconds.to_string());
+    annotate("proxy-send-timeout", settings.proxy_send_timeout_seconds.to_string());
+    annotate("proxy-read-timeout", settings.proxy_read_timeout_seconds.to_string());
+
+    if settings.enable_cors {
+        annotate("enable-cors", "true".to_string());
+        annotate("cors-allow-origin", settings.cors_allow_origins.join(", "));
+        annotate("cors-allow-methods", settings.cors_allow_methods.join(", "));
+        annotate("cors-allow-headers", settings.cors_allow_headers.join(", "));
+    }
+
+    if !settings.whitelist_source_ranges.is_empty() {
+        annotate("whitelist-source-range", settings.whitelist_source_ranges.join(","));
+    }
+
+    if settings.basic_auth_htpasswd.is_some() {
+        annotate("auth-type", "basic".to_string());
+        annotate("auth-secret", basic_auth_secret_name.to_string());
+        annotate("auth-realm", "Authentication Required".to_string());
+    }
+
+    if let Some(limit_rps) = settings.limit_rps {
+        annotate("limit-rps", limit_rps.to_string());
+    }
+
+    if let Some(limit_connections) = settings.limit_connections {
+        annotate("limit-connections", limit_connections.to_string());
+    }
+
+    annotations
+}
+
+/// Inserts all custom domains, used for routing, and splits them between domains getting a certificate from
+/// Let's Encrypt and domains having a user supplied certificate.
+fn insert_custom_domains(context: &mut TeraContext, router_id: &str, custom_domains: &[&CustomDomain]) {
+    let custom_domain_data_template = |cd: &CustomDomain| CustomDomainDataTemplate {
+        domain: cd.domain.clone(),
+        domain_hash: crate::crypto::to_sha1_truncate_16(cd.domain.as_str()),
+        target_domain: cd.target_domain.clone(),
+    };
+
+    let custom_domain_data_templates = custom_domains
+        .iter()
+        .map(|cd| custom_domain_data_template(cd))
+        .collect::<Vec<_>>();
+    let acme_custom_domain_data_templates = custom_domains
+        .iter()
+        .filter(|cd| cd.certificate.is_none())
+        .map(|cd| custom_domain_data_template(cd))
+        .collect::<Vec<_>>();
+    let certificate_custom_domain_data_templates = custom_domains
+        .iter()
+        .filter_map(|cd| {
+            cd.certificate.as_ref().map(|certificate| {
+                let domain_hash = crate::crypto::to_sha1_truncate_16(cd.domain.as_str());
+                CustomDomainCertificateDataTemplate {
+                    domain: cd.domain.clone(),
+                    tls_secret_name: format!("router-tls-{}-{}", router_id, domain_hash),
+                    domain_hash,
+                    certificate_chain_pem: certificate.certificate_chain_pem.clone(),
+                    private_key_pem: certificate.private_key_pem.clone(),
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    context.insert("custom_domains", &custom_domain_data_templates);
+    context.insert("acme_custom_domains", &acme_custom_domain_data_templates);
+    context.insert("certificate_custom_domains", &certificate_custom_domain_data_templates);
+}
+
 impl<T: CloudProvider> ToTransmitter for Router<T> {
     fn to_transmitter(&self) -> Transmitter {
         Transmitter::Router(self.id.to_string(), self.name.to_string())
@@ -330,9 +961,14 @@ where
 
         let kubernetes_config_file_path = kubernetes.get_kubeconfig_file_path()?;
 
+        // custom domains are routed only once their ownership is verified
+        let verified_custom_domains = self.verified_custom_domains(target);
+        self.check_certificates_expiration();
+
         // respect order - getting the context here and not before is mandatory
         // the nginx-ingress must be available to get the external dns target if necessary
-        let context = self.tera_context(target)?;
+        let mut context = self.tera_context(target)?;
+        insert_custom_domains(&mut context, self.id(), &verified_custom_domains);
 
         let from_dir = format!(
             "{}/{}/charts/q-ingress-tls",
@@ -371,11 +1007,15 @@ where
         );
 
         helm.upgrade(&chart, &[])
-            .map_err(|e| EngineError::new_helm_error(event_details.clone(), e))
+            .map_err(|e| EngineError::new_helm_error(event_details.clone(), e))?;
+
+        self.report_traffic_splits(environment);
+
+        Ok(())
     }
 
     #[named]
-    fn on_create_check(&self) -> Result<(), EngineError> {
+    fn on_create_check(&self, target: &DeploymentTarget) -> Result<(), EngineError> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::Deploy));
         print_action(
             T::short_name(),
@@ -386,35 +1026,10 @@ where
             self.logger(),
         );
 
-        // check non custom domains
-        self.check_domains(event_details.clone(), self.logger())?;
+        let nameservers = target.kubernetes.dns_provider().domain_check_nameservers();
 
-        // Wait/Check that custom domain is a CNAME targeting qovery
-        for domain_to_check in self.custom_domains.iter() {
-            match check_cname_for(
-                self.progress_scope(),
-                self.listeners(),
-                &domain_to_check.domain,
-                self.context.execution_id(),
-            ) {
-                Ok(cname) if cname.trim_end_matches('.') == domain_to_check.target_domain.trim_end_matches('.') => {
-                    continue;
-                }
-                Ok(err) | Err(err) => {
-                    // TODO(benjaminch): Handle better this one via a proper error eventually
-                    self.logger().log(EngineEvent::Warning(
-                        event_details.clone(),
-                        EventMessage::new(
-                            format!(
-                                "Invalid CNAME for {}. Might not be an issue if user is using a CDN.",
-                                domain_to_check.domain,
-                            ),
-                            Some(err.to_string()),
-                        ),
-                    ));
-                }
-            }
-        }
+        // custom domains are verified before being routed, see `verified_custom_domains`
+        self.check_domains(&nameservers, event_details, self.logger())?;
 
         Ok(())
     }
@@ -567,3 +1182,775 @@ where
         !self.custom_domains.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        insert_custom_domains, nginx_annotations, route_annotations, route_data_template, route_groups,
+        validate_advanced_settings, validate_route, validate_weighted_applications,
+    };
+    use crate::cloud_provider::acme::{AcmeExternalAccountBinding, AcmeSettings, AcmeSolver, ZEROSSL_URL};
+    use crate::cloud_provider::certificate::tests::new_certificate_chain;
+    use crate::cloud_provider::kubernetes::KubernetesApiCapabilities;
+    use crate::cloud_provider::models::{CanaryRouteDataTemplate, CustomDomain, Route, RouteDataTemplate};
+    use crate::io_models::{
+        CustomDomainCertificate, RouteBackendProtocol, RouteRewrite, RouteWeightedApplication, RouterAdvancedSettings,
+    };
+    use crate::models::types::VersionsNumber;
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+    use tera::Context as TeraContext;
+
+    fn ingress_templates(context: &TeraContext) -> Vec<(String, Vec<serde_yaml::Value>)> {
+        q_ingress_tls_templates("ingress-qovery.j2.yaml", context)
+    }
+
+    fn q_ingress_tls_templates(template_name: &str, context: &TeraContext) -> Vec<(String, Vec<serde_yaml::Value>)> {
+        ["aws", "aws-ec2", "digitalocean", "scaleway"]
+            .iter()
+            .map(|lib_dir| {
+                let template = std::fs::read_to_string(format!(
+                    "{}/lib/{}/charts/q-ingress-tls/templates/{}",
+                    env!("CARGO_MANIFEST_DIR"),
+                    lib_dir,
+                    template_name
+                ))
+                .unwrap();
+                let rendered = tera::Tera::one_off(template.as_str(), context, false).unwrap();
+                let documents = rendered
+                    .split("\n---\n")
+                    .filter_map(|document| serde_yaml::from_str::<serde_yaml::Value>(document).ok())
+                    .filter(|document| !document.is_null())
+                    .collect();
+
+                (lib_dir.to_string(), documents)
+            })
+            .collect()
+    }
+
+    fn ingress_tera_context(routes: Vec<RouteDataTemplate>, rewrite_routes: Vec<RouteDataTemplate>) -> TeraContext {
+        ingress_tera_context_with_custom_domain(routes, rewrite_routes, None)
+    }
+
+    fn ingress_tera_context_with_custom_domain(
+        routes: Vec<RouteDataTemplate>,
+        rewrite_routes: Vec<RouteDataTemplate>,
+        certificate: Option<CustomDomainCertificate>,
+    ) -> TeraContext {
+        let mut context = TeraContext::new();
+        for key in &[
+            "sanitized_name",
+            "namespace",
+            "id",
+            "long_id",
+            "environment_id",
+            "owner_id",
+        ] {
+            context.insert(*key, key);
+        }
+        context.insert("router_default_domain", "router.example.com");
+        AcmeSettings {
+            directory_url: "https://acme.example.com/directory".to_string(),
+            email: "tls@example.com".to_string(),
+            external_account_binding: None,
+            solver: AcmeSolver::Http01,
+            skip_tls_verify: false,
+        }
+        .insert_into_tera_context(&mut context);
+        context.insert("sticky_sessions_enabled", &false);
+        insert_custom_domains(
+            &mut context,
+            "id",
+            &[&CustomDomain {
+                domain: "www.example.com".to_string(),
+                target_domain: "router.example.com".to_string(),
+                verification_token: "token".to_string(),
+                certificate,
+            }],
+        );
+        let (routes, groups) = route_groups(routes);
+        context.insert("routes", &routes);
+        context.insert("route_groups", &groups);
+        context.insert("canary_routes", &Vec::<CanaryRouteDataTemplate>::new());
+        context.insert("rewrite_routes", &rewrite_routes);
+        context.insert(
+            "router_annotations",
+            &nginx_annotations(&RouterAdvancedSettings::default(), "router-basic-auth-id"),
+        );
+        context
+    }
+
+    #[test]
+    fn test_ingress_api_version_rendering() {
+        // setup:
+        let test_cases = vec![("1.18", "networking.k8s.io/v1beta1"), ("1.22", "networking.k8s.io/v1")];
+
+        for (kubernetes_version, expected_api_version) in test_cases {
+            let mut context = ingress_tera_context(
+                vec![RouteDataTemplate {
+                    path: "/".to_string(),
+                    application_name: "app".to_string(),
+                    application_port: 8080,
+                    rewrite_target: None,
+                    backend_protocol: RouteBackendProtocol::HTTP,
+                    annotations: BTreeMap::new(),
+                }],
+                vec![],
+            );
+
+            // execute:
+            KubernetesApiCapabilities::from_version(Some(VersionsNumber::from_str(kubernetes_version).unwrap()))
+                .insert_into_tera_context(&mut context);
+
+            for (lib_dir, ingresses) in ingress_templates(&context) {
+                // verify:
+                assert_eq!(1, ingresses.len(), "{}", lib_dir);
+                let ingress = &ingresses[0];
+                assert_eq!(Some(expected_api_version), ingress["apiVersion"].as_str(), "{}", lib_dir);
+                let rules = ingress["spec"]["rules"].as_sequence().unwrap();
+                assert_eq!(2, rules.len(), "{}", lib_dir);
+                for rule in rules {
+                    let path = &rule["http"]["paths"][0];
+                    if expected_api_version == "networking.k8s.io/v1" {
+                        assert_eq!(Some("nginx-qovery"), ingress["spec"]["ingressClassName"].as_str());
+                        assert!(ingress["metadata"]["annotations"]["kubernetes.io/ingress.class"].is_null());
+                        assert_eq!(Some("Prefix"), path["pathType"].as_str());
+                        assert_eq!(Some("app"), path["backend"]["service"]["name"].as_str());
+                        assert_eq!(Some(8080), path["backend"]["service"]["port"]["number"].as_u64());
+                    } else {
+                        assert!(ingress["spec"]["ingressClassName"].is_null());
+                        assert_eq!(
+                            Some("nginx-qovery"),
+                            ingress["metadata"]["annotations"]["kubernetes.io/ingress.class"].as_str()
+                        );
+                        assert_eq!(Some("app"), path["backend"]["serviceName"].as_str());
+                        assert_eq!(Some(8080), path["backend"]["servicePort"].as_u64());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rewrite_routes_rendering() {
+        // setup:
+        let route = |path: &str, rewrite: Option<RouteRewrite>| Route {
+            path: path.to_string(),
+            application_name: "app".to_string(),
+            rewrite,
+            backend_protocol: RouteBackendProtocol::HTTP,
+            weighted_applications: vec![],
+        };
+        let rewrite_route = route_data_template(
+            &route(
+                "/api/",
+                Some(RouteRewrite {
+                    target: "/".to_string(),
+                    strip_prefix: true,
+                }),
+            ),
+            "api".to_string(),
+            3000,
+            &nginx_annotations(&RouterAdvancedSettings::default(), "router-basic-auth-id"),
+        );
+        let mut context = ingress_tera_context(vec![], vec![rewrite_route]);
+        KubernetesApiCapabilities::from_version(None).insert_into_tera_context(&mut context);
+
+        // execute:
+        let rendered = ingress_templates(&context);
+
+        // verify:
+        for (lib_dir, ingresses) in rendered {
+            assert_eq!(1, ingresses.len(), "{}", lib_dir);
+            let annotations = &ingresses[0]["metadata"]["annotations"];
+            assert_eq!(Some("sanitized_name-rewrite-1"), ingresses[0]["metadata"]["name"].as_str());
+            assert_eq!(Some("/$2"), annotations["nginx.ingress.kubernetes.io/rewrite-target"].as_str());
+            assert_eq!(Some("true"), annotations["nginx.ingress.kubernetes.io/use-regex"].as_str());
+            assert_eq!(
+                Some("100m"),
+                annotations["nginx.ingress.kubernetes.io/proxy-body-size"].as_str()
+            );
+            // no plain route Ingress, the rewrite Ingress requests certificates
+            assert_eq!(Some("id"), annotations["cert-manager.io/issuer"].as_str());
+            let path = &ingresses[0]["spec"]["rules"][0]["http"]["paths"][0];
+            assert_eq!(Some("/api(/|$)(.*)"), path["path"].as_str());
+            assert_eq!(Some("ImplementationSpecific"), path["pathType"].as_str());
+        }
+    }
+
+    #[test]
+    fn test_certificate_custom_domains_rendering() {
+        // setup:
+        let certificate = new_certificate_chain(&["www.example.com"], 90);
+        let routes = vec![RouteDataTemplate {
+            path: "/".to_string(),
+            application_name: "app".to_string(),
+            application_port: 8080,
+            rewrite_target: None,
+            backend_protocol: RouteBackendProtocol::HTTP,
+            annotations: BTreeMap::new(),
+        }];
+        let mut context = ingress_tera_context_with_custom_domain(routes, vec![], Some(certificate.clone()));
+        KubernetesApiCapabilities::from_version(None).insert_into_tera_context(&mut context);
+
+        // execute:
+        let ingresses = ingress_templates(&context);
+        let certificates = q_ingress_tls_templates("custom-domain-certificates.j2.yaml", &context);
+        let issuers = q_ingress_tls_templates("cert-issuer.j2.yaml", &context);
+
+        // verify:
+        for (lib_dir, ingresses) in ingresses {
+            // the domain is still routed but cert-manager must not issue a certificate for it
+            let annotations = &ingresses[0]["metadata"]["annotations"];
+            assert!(annotations["cert-manager.io/issuer"].is_null(), "{}", lib_dir);
+            assert_eq!(
+                Some("letsencrypt-qovery"),
+                annotations["cert-manager.io/cluster-issuer"].as_str()
+            );
+            assert!(ingresses[0]["spec"]["tls"]
+                .as_sequence()
+                .map_or(true, |tls| tls.is_empty()));
+            assert_eq!(Some("www.example.com"), ingresses[0]["spec"]["rules"][1]["host"].as_str());
+        }
+        for (lib_dir, documents) in certificates {
+            assert_eq!(2, documents.len(), "{}", lib_dir);
+            let secret = &documents[0];
+            let secret_name = secret["metadata"]["name"].as_str().unwrap();
+            assert!(secret_name.starts_with("router-tls-id-"), "{}", lib_dir);
+            assert_eq!(Some("kubernetes.io/tls"), secret["type"].as_str());
+            assert_eq!(
+                Some(certificate.certificate_chain_pem.as_str()),
+                secret["stringData"]["tls.crt"].as_str()
+            );
+            assert_eq!(
+                Some(certificate.private_key_pem.as_str()),
+                secret["stringData"]["tls.key"].as_str()
+            );
+
+            let ingress = &documents[1];
+            assert_eq!(Some("Ingress"), ingress["kind"].as_str());
+            assert!(ingress["metadata"]["annotations"]["cert-manager.io/issuer"].is_null());
+            assert_eq!(Some(secret_name), ingress["spec"]["tls"][0]["secretName"].as_str());
+            assert_eq!(Some("www.example.com"), ingress["spec"]["rules"][0]["host"].as_str());
+        }
+        for (lib_dir, documents) in issuers {
+            assert!(documents.is_empty(), "{}", lib_dir);
+        }
+    }
+
+    #[test]
+    fn test_acme_settings_rendering() {
+        // setup:
+        struct TestCase<'a> {
+            solver: AcmeSolver,
+            expected_issuer_annotation: Option<&'a str>,
+            expected_cluster_issuer_annotation: Option<&'a str>,
+            expected_documents: usize,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                solver: AcmeSolver::Http01,
+                expected_issuer_annotation: Some("id"),
+                expected_cluster_issuer_annotation: None,
+                // external account binding secret and router Issuer
+                expected_documents: 2,
+            },
+            TestCase {
+                solver: AcmeSolver::Dns01,
+                expected_issuer_annotation: None,
+                expected_cluster_issuer_annotation: Some("letsencrypt-qovery"),
+                expected_documents: 0,
+            },
+        ];
+
+        for tc in test_cases {
+            let routes = vec![RouteDataTemplate {
+                path: "/".to_string(),
+                application_name: "app".to_string(),
+                application_port: 8080,
+                rewrite_target: None,
+                backend_protocol: RouteBackendProtocol::HTTP,
+                annotations: BTreeMap::new(),
+            }];
+            let mut context = ingress_tera_context(routes, vec![]);
+            KubernetesApiCapabilities::from_version(None).insert_into_tera_context(&mut context);
+            AcmeSettings {
+                directory_url: ZEROSSL_URL.to_string(),
+                email: "tls@example.com".to_string(),
+                external_account_binding: Some(AcmeExternalAccountBinding {
+                    key_id: "kid".to_string(),
+                    hmac_key: "aG1hYw".to_string(),
+                }),
+                solver: tc.solver,
+                skip_tls_verify: true,
+            }
+            .insert_into_tera_context(&mut context);
+
+            // execute:
+            let ingresses = ingress_templates(&context);
+            let issuers = q_ingress_tls_templates("cert-issuer.j2.yaml", &context);
+
+            // verify:
+            for (lib_dir, ingresses) in ingresses {
+                let annotations = &ingresses[0]["metadata"]["annotations"];
+                assert_eq!(
+                    tc.expected_issuer_annotation,
+                    annotations["cert-manager.io/issuer"].as_str(),
+                    "{}",
+                    lib_dir
+                );
+                assert_eq!(
+                    tc.expected_cluster_issuer_annotation,
+                    annotations["cert-manager.io/cluster-issuer"].as_str(),
+                    "{}",
+                    lib_dir
+                );
+                // the ACME certificate is requested for the custom domain whatever the solver
+                assert_eq!(Some("www.example.com"), ingresses[0]["spec"]["tls"][0]["hosts"][0].as_str());
+            }
+            for (lib_dir, documents) in issuers {
+                assert_eq!(tc.expected_documents, documents.len(), "{}", lib_dir);
+                if let Some(issuer) = documents
+                    .iter()
+                    .find(|document| document["kind"].as_str() == Some("Issuer"))
+                {
+                    let acme = &issuer["spec"]["acme"];
+                    assert_eq!(Some(ZEROSSL_URL), acme["server"].as_str());
+                    assert_eq!(Some(true), acme["skipTLSVerify"].as_bool());
+                    assert_eq!(Some("kid"), acme["externalAccountBinding"]["keyID"].as_str());
+                    assert_eq!(
+                        Some("acme-id-eab"),
+                        acme["externalAccountBinding"]["keySecretRef"]["name"].as_str()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_route_annotations() {
+        // setup:
+        let router_annotations = nginx_annotations(&RouterAdvancedSettings::default(), "router-basic-auth-id");
+        let annotation = |annotations: &BTreeMap<String, String>, key: &str| {
+            annotations
+                .get(&format!("nginx.ingress.kubernetes.io/{}", key))
+                .cloned()
+        };
+
+        // execute:
+        let http = route_annotations(&router_annotations, RouteBackendProtocol::HTTP);
+        let https = route_annotations(&router_annotations, RouteBackendProtocol::HTTPS);
+        let grpc = route_annotations(&router_annotations, RouteBackendProtocol::GRPC);
+        let ws = route_annotations(&router_annotations, RouteBackendProtocol::WS);
+
+        // verify:
+        assert_eq!(router_annotations, http);
+        assert_eq!(Some("HTTPS".to_string()), annotation(&https, "backend-protocol"));
+        assert_eq!(Some("60".to_string()), annotation(&https, "proxy-read-timeout"));
+        assert_eq!(Some("GRPC".to_string()), annotation(&grpc, "backend-protocol"));
+        assert_eq!(Some("3600".to_string()), annotation(&grpc, "proxy-read-timeout"));
+        assert_eq!(Some("3600".to_string()), annotation(&grpc, "proxy-send-timeout"));
+        assert_eq!(Some("60".to_string()), annotation(&grpc, "proxy-connect-timeout"));
+        assert_eq!(Some("HTTP".to_string()), annotation(&ws, "backend-protocol"));
+        assert_eq!(Some("3600".to_string()), annotation(&ws, "proxy-read-timeout"));
+    }
+
+    #[test]
+    fn test_backend_protocol_rendering() {
+        // setup:
+        let router_annotations = nginx_annotations(&RouterAdvancedSettings::default(), "router-basic-auth-id");
+        let route = |path: &str, backend_protocol: RouteBackendProtocol| {
+            route_data_template(
+                &Route {
+                    path: path.to_string(),
+                    application_name: "app".to_string(),
+                    rewrite: None,
+                    backend_protocol,
+                    weighted_applications: vec![],
+                },
+                "app".to_string(),
+                8080,
+                &router_annotations,
+            )
+        };
+        let routes = vec![
+            route("/", RouteBackendProtocol::HTTP),
+            route("/helloworld.Greeter", RouteBackendProtocol::GRPC),
+            route("/ws", RouteBackendProtocol::WS),
+            route("/events", RouteBackendProtocol::WS),
+        ];
+        let mut context = ingress_tera_context(routes, vec![]);
+        KubernetesApiCapabilities::from_version(None).insert_into_tera_context(&mut context);
+
+        // execute:
+        let rendered = ingress_templates(&context);
+
+        // verify:
+        for (lib_dir, ingresses) in rendered {
+            let names = ingresses
+                .iter()
+                .map(|ingress| ingress["metadata"]["name"].as_str().unwrap_or_default())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec!["sanitized_name", "sanitized_name-grpc", "sanitized_name-ws"],
+                names,
+                "{}",
+                lib_dir
+            );
+
+            let main_annotations = &ingresses[0]["metadata"]["annotations"];
+            assert!(main_annotations["nginx.ingress.kubernetes.io/backend-protocol"].is_null());
+            assert_eq!(Some("id"), main_annotations["cert-manager.io/issuer"].as_str());
+
+            let grpc_annotations = &ingresses[1]["metadata"]["annotations"];
+            assert_eq!(
+                Some("GRPC"),
+                grpc_annotations["nginx.ingress.kubernetes.io/backend-protocol"].as_str()
+            );
+            assert_eq!(
+                Some("3600"),
+                grpc_annotations["nginx.ingress.kubernetes.io/proxy-read-timeout"].as_str()
+            );
+            // only one Ingress requests the certificates
+            assert!(grpc_annotations["cert-manager.io/issuer"].is_null());
+            assert_eq!(
+                Some("/helloworld.Greeter"),
+                ingresses[1]["spec"]["rules"][0]["http"]["paths"][0]["path"].as_str()
+            );
+
+            let ws_paths = ingresses[2]["spec"]["rules"][1]["http"]["paths"].as_sequence().unwrap();
+            assert_eq!(2, ws_paths.len());
+            assert_eq!(Some("www.example.com"), ingresses[2]["spec"]["rules"][1]["host"].as_str());
+        }
+    }
+
+    #[test]
+    fn test_validate_weighted_applications() {
+        // setup:
+        let weighted = |weights: &[(&str, u32)]| {
+            weights
+                .iter()
+                .map(|(application_name, weight)| RouteWeightedApplication {
+                    application_name: application_name.to_string(),
+                    weight: *weight,
+                })
+                .collect::<Vec<_>>()
+        };
+        let route = |weighted_applications: Vec<RouteWeightedApplication>, rewrite: Option<RouteRewrite>| Route {
+            path: "/".to_string(),
+            application_name: "stable".to_string(),
+            rewrite,
+            backend_protocol: RouteBackendProtocol::HTTP,
+            weighted_applications,
+        };
+
+        let test_cases = vec![
+            (route(weighted(&[("stable", 80), ("next", 20)]), None), true, "two applications"),
+            (route(weighted(&[("stable", 100)]), None), true, "route application only"),
+            (route(weighted(&[("stable", 80), ("next", 30)]), None), false, "total above 100"),
+            (route(weighted(&[("stable", 50), ("next", 40)]), None), false, "total below 100"),
+            (
+                route(weighted(&[("next", 50), ("other", 50)]), None),
+                false,
+                "route application missing",
+            ),
+            (
+                route(weighted(&[("stable", 50), ("next", 25), ("other", 25)]), None),
+                false,
+                "more than two applications",
+            ),
+            (
+                route(
+                    weighted(&[("stable", 50), ("next", 50)]),
+                    Some(RouteRewrite {
+                        target: "/".to_string(),
+                        strip_prefix: true,
+                    }),
+                ),
+                false,
+                "rewritten route",
+            ),
+        ];
+
+        for (route, expected_valid, description) in test_cases {
+            // execute:
+            let result = validate_weighted_applications(&route);
+
+            // verify:
+            assert_eq!(expected_valid, result.is_ok(), "case: {}", description);
+        }
+
+        let split_route = route(weighted(&[("stable", 80), ("next", 20)]), None);
+        assert_eq!(Some(20), split_route.canary().map(|canary| canary.weight));
+        assert!(route(weighted(&[("stable", 100)]), None).canary().is_none());
+    }
+
+    #[test]
+    fn test_canary_routes_rendering() {
+        // setup:
+        let routes = vec![RouteDataTemplate {
+            path: "/".to_string(),
+            application_name: "stable".to_string(),
+            application_port: 8080,
+            rewrite_target: None,
+            backend_protocol: RouteBackendProtocol::HTTP,
+            annotations: BTreeMap::new(),
+        }];
+        let mut context = ingress_tera_context(routes, vec![]);
+        KubernetesApiCapabilities::from_version(None).insert_into_tera_context(&mut context);
+        context.insert(
+            "canary_routes",
+            &vec![CanaryRouteDataTemplate {
+                path: "/".to_string(),
+                application_name: "next".to_string(),
+                application_port: 3000,
+                weight: 20,
+            }],
+        );
+
+        // execute:
+        let rendered = ingress_templates(&context);
+
+        // verify:
+        for (lib_dir, ingresses) in rendered {
+            assert_eq!(2, ingresses.len(), "{}", lib_dir);
+            let canary = &ingresses[1];
+            let annotations = &canary["metadata"]["annotations"];
+            assert_eq!(Some("sanitized_name-canary-1"), canary["metadata"]["name"].as_str());
+            assert_eq!(Some("true"), annotations["nginx.ingress.kubernetes.io/canary"].as_str());
+            assert_eq!(Some("20"), annotations["nginx.ingress.kubernetes.io/canary-weight"].as_str());
+            assert!(annotations["cert-manager.io/issuer"].is_null());
+            assert!(canary["spec"]["tls"].is_null());
+
+            // same hosts and path as the main Ingress, with the weighted application as backend
+            for (rule_index, host) in ["router.example.com", "www.example.com"].iter().enumerate() {
+                let rule = &canary["spec"]["rules"][rule_index];
+                assert_eq!(Some(*host), rule["host"].as_str());
+                assert_eq!(Some("/"), rule["http"]["paths"][0]["path"].as_str());
+                assert_eq!(Some("next"), rule["http"]["paths"][0]["backend"]["service"]["name"].as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_route_data_template() {
+        // setup:
+        let test_cases = vec![
+            ("/app", None, "/app", None),
+            ("/app", Some(("/", true)), "/app(/|$)(.*)", Some("/$2")),
+            ("/app/", Some(("/v1/", true)), "/app(/|$)(.*)", Some("/v1/$2")),
+            ("/app", Some(("/v1", false)), "/app(/|$)(.*)", Some("/v1/app/$2")),
+            ("/", Some(("/v1", false)), "/()(.*)", Some("/v1/$2")),
+        ];
+
+        for (path, rewrite, expected_path, expected_rewrite_target) in test_cases {
+            let route = Route {
+                path: path.to_string(),
+                application_name: "app".to_string(),
+                rewrite: rewrite.map(|(target, strip_prefix)| RouteRewrite {
+                    target: target.to_string(),
+                    strip_prefix,
+                }),
+                backend_protocol: RouteBackendProtocol::HTTP,
+                weighted_applications: vec![],
+            };
+
+            // execute:
+            let template = route_data_template(&route, "app".to_string(), 80, &BTreeMap::new());
+
+            // verify:
+            assert_eq!(expected_path, template.path, "{} {:?}", path, rewrite);
+            assert_eq!(
+                expected_rewrite_target,
+                template.rewrite_target.as_deref(),
+                "{} {:?}",
+                path,
+                rewrite
+            );
+            assert!(validate_route(&route).is_ok());
+        }
+
+        let invalid_route = Route {
+            path: "/app(.*)".to_string(),
+            application_name: "app".to_string(),
+            rewrite: Some(RouteRewrite {
+                target: "/".to_string(),
+                strip_prefix: true,
+            }),
+            backend_protocol: RouteBackendProtocol::HTTP,
+            weighted_applications: vec![],
+        };
+        assert!(validate_route(&invalid_route).is_err());
+
+        let grpc_rewrite_route = Route {
+            path: "/api".to_string(),
+            application_name: "app".to_string(),
+            rewrite: Some(RouteRewrite {
+                target: "/".to_string(),
+                strip_prefix: true,
+            }),
+            backend_protocol: RouteBackendProtocol::GRPC,
+            weighted_applications: vec![],
+        };
+        assert!(validate_route(&grpc_rewrite_route).is_err());
+    }
+
+    struct TestCase<'a> {
+        input: RouterAdvancedSettings,
+        expected_valid: bool,
+        description: &'a str,
+    }
+
+    #[test]
+    fn test_validate_advanced_settings() {
+        // setup:
+        let test_cases = vec![
+            TestCase {
+                input: RouterAdvancedSettings::default(),
+                expected_valid: true,
+                description: "default settings",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    enable_cors: true,
+                    cors_allow_origins: vec!["https://example.com".to_string()],
+                    ..Default::default()
+                },
+                expected_valid: true,
+                description: "CORS with an origin",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    enable_cors: true,
+                    cors_allow_origins: vec!["example.com".to_string()],
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "CORS origin without scheme",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    enable_cors: true,
+                    cors_allow_methods: vec!["FETCH".to_string()],
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "unknown CORS method",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    enable_cors: true,
+                    cors_allow_headers: vec!["X-Header; evil".to_string()],
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "invalid CORS header",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    whitelist_source_ranges: vec![
+                        "10.0.0.0/8".to_string(),
+                        "1.2.3.4".to_string(),
+                        "2001:db8::/32".to_string(),
+                    ],
+                    ..Default::default()
+                },
+                expected_valid: true,
+                description: "valid source ranges",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    whitelist_source_ranges: vec!["10.0.0.0/33".to_string()],
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "invalid CIDR prefix length",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    basic_auth_htpasswd: Some("user:$apr1$salt$hash\n".to_string()),
+                    ..Default::default()
+                },
+                expected_valid: true,
+                description: "valid htpasswd",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    basic_auth_htpasswd: Some("user".to_string()),
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "htpasswd without password",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    limit_rps: Some(0),
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "zero rate limit",
+            },
+            TestCase {
+                input: RouterAdvancedSettings {
+                    proxy_read_timeout_seconds: 0,
+                    ..Default::default()
+                },
+                expected_valid: false,
+                description: "zero proxy timeout",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = validate_advanced_settings(&tc.input);
+
+            // verify:
+            assert_eq!(tc.expected_valid, result.is_ok(), "case: {}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_nginx_annotations() {
+        // setup:
+        let settings = RouterAdvancedSettings {
+            enable_cors: true,
+            cors_allow_origins: vec!["https://a.com".to_string(), "https://b.com".to_string()],
+            cors_allow_methods: vec!["GET".to_string(), "POST".to_string()],
+            cors_allow_headers: vec!["Authorization".to_string()],
+            whitelist_source_ranges: vec!["10.0.0.0/8".to_string(), "1.2.3.4/32".to_string()],
+            basic_auth_htpasswd: Some("user:hash".to_string()),
+            limit_rps: Some(10),
+            limit_connections: Some(5),
+            proxy_body_size_mb: 50,
+            proxy_connect_timeout_seconds: 5,
+            proxy_send_timeout_seconds: 30,
+            proxy_read_timeout_seconds: 120,
+        };
+
+        // execute:
+        let annotations = nginx_annotations(&settings, "router-basic-auth-id");
+
+        // verify:
+        let expected_annotations = vec![
+            ("auth-realm", "Authentication Required"),
+            ("auth-secret", "router-basic-auth-id"),
+            ("auth-type", "basic"),
+            ("cors-allow-headers", "Authorization"),
+            ("cors-allow-methods", "GET, POST"),
+            ("cors-allow-origin", "https://a.com, https://b.com"),
+            ("enable-cors", "true"),
+            ("limit-connections", "5"),
+            ("limit-rps", "10"),
+            ("proxy-body-size", "50m"),
+            ("proxy-connect-timeout", "5"),
+            ("proxy-read-timeout", "120"),
+            ("proxy-send-timeout", "30"),
+            ("whitelist-source-range", "10.0.0.0/8,1.2.3.4/32"),
+        ]
+        .into_iter()
+        .map(|(key, value)| (format!("nginx.ingress.kubernetes.io/{}", key), value.to_string()))
+        .collect::<BTreeMap<_, _>>();
+        assert_eq!(expected_annotations, annotations);
+    }
+}
@@ -3,18 +3,31 @@ use crate::cloud_provider::models::StorageDataTemplate;
 use crate::cloud_provider::DeploymentTarget;
 use crate::errors::EngineError;
 use crate::events::{EnvironmentStep, Stage};
-use crate::io_models::ListenersHelper;
+use crate::io_models::{ListenersHelper, Protocol};
 use crate::models::application::Application;
 use crate::models::scaleway::ScwStorageType;
-use crate::models::types::{ToTeraContext, SCW};
+use crate::models::types::{CloudProvider, ToTeraContext, SCW};
 use tera::Context as TeraContext;
 
 impl ToTeraContext for Application<SCW> {
     fn to_tera_context(&self, target: &DeploymentTarget) -> Result<TeraContext, EngineError> {
         let event_details = self.get_event_details(Stage::Environment(EnvironmentStep::LoadConfiguration));
-        let kubernetes = target.kubernetes;
-        let environment = target.environment;
-        let mut context = self.default_tera_context(kubernetes, environment);
+
+        // Scaleway load balancers only forward TCP traffic
+        if let Some(port) = self
+            .ports
+            .iter()
+            .find(|port| port.publicly_accessible && port.expose_with_load_balancer && port.protocol == Protocol::UDP)
+        {
+            return Err(EngineError::new_unsupported_public_port_protocol(
+                event_details,
+                SCW::full_name(),
+                "UDP",
+                port.port,
+            ));
+        }
+
+        let mut context = self.default_tera_context(target)?;
 
         // container registry credentials
         context.insert("registry_secret_name", &format!("registry-token-{}", &self.id));
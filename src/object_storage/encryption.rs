@@ -0,0 +1,640 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use crypto::aead::{AeadDecryptor, AeadEncryptor};
+use crypto::aes::KeySize;
+use crypto::aes_gcm::AesGcm;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::io_models::Context;
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::utilities::fill_buffer;
+
+// The encryption envelope is stored in the object user metadata, the object content is the bare cipher text.
+// Objects without these metadata are plaintext, so objects written before encryption was enabled are still readable.
+const ALGORITHM_METADATA_KEY: &str = "qovery-encryption-algorithm";
+const KEY_ID_METADATA_KEY: &str = "qovery-encryption-key-id";
+const WRAPPED_DATA_KEY_METADATA_KEY: &str = "qovery-encryption-wrapped-data-key";
+const WRAPPED_DATA_KEY_NONCE_METADATA_KEY: &str = "qovery-encryption-wrapped-data-key-nonce";
+const WRAPPED_DATA_KEY_TAG_METADATA_KEY: &str = "qovery-encryption-wrapped-data-key-tag";
+const NONCE_PREFIX_METADATA_KEY: &str = "qovery-encryption-nonce-prefix";
+const SEGMENT_SIZE_METADATA_KEY: &str = "qovery-encryption-segment-size";
+// STREAM construction over AES-GCM: content is encrypted by segments, each one with its own tag, so objects
+// never have to be loaded in memory. The segment nonce is the object nonce prefix, the segment counter and a
+// last segment flag, so segments cannot be reordered, dropped or truncated.
+const ENCRYPTION_ALGORITHM: &str = "AES-256-GCM-STREAM";
+const SEGMENT_SIZE_IN_BYTES: usize = 64 * 1024;
+const KEY_SIZE_IN_BYTES: usize = 32;
+const NONCE_SIZE_IN_BYTES: usize = 12;
+const NONCE_PREFIX_SIZE_IN_BYTES: usize = 7;
+const TAG_SIZE_IN_BYTES: usize = 16;
+
+/// Master key used to encrypt objects data keys, `key` is base64 encoded and must be 32 bytes long.
+#[derive(Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct EncryptionKey {
+    pub id: String,
+    pub key: String,
+}
+
+impl Debug for EncryptionKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // never expose the key itself in logs
+        f.debug_struct("EncryptionKey")
+            .field("id", &self.id)
+            .field("key", &"***")
+            .finish()
+    }
+}
+
+/// Object storage envelope encryption settings.
+/// New objects are encrypted with `current_key`, `previous_keys` are only used to read objects written before a key rotation.
+#[derive(Debug, Serialize, Deserialize, Clone, Eq, PartialEq, Hash)]
+pub struct ObjectStorageEncryption {
+    pub current_key: EncryptionKey,
+    pub previous_keys: Vec<EncryptionKey>,
+}
+
+impl ObjectStorageEncryption {
+    fn find_key(&self, key_id: &str) -> Option<&EncryptionKey> {
+        std::iter::once(&self.current_key)
+            .chain(self.previous_keys.iter())
+            .find(|k| k.id == key_id)
+    }
+}
+
+struct EnvelopeHeader {
+    algorithm: String,
+    key_id: String,
+    wrapped_data_key: String,
+    wrapped_data_key_nonce: String,
+    wrapped_data_key_tag: String,
+    nonce_prefix: String,
+    segment_size: String,
+}
+
+impl EnvelopeHeader {
+    fn into_metadata(self) -> HashMap<String, String> {
+        HashMap::from([
+            (ALGORITHM_METADATA_KEY.to_string(), self.algorithm),
+            (KEY_ID_METADATA_KEY.to_string(), self.key_id),
+            (WRAPPED_DATA_KEY_METADATA_KEY.to_string(), self.wrapped_data_key),
+            (WRAPPED_DATA_KEY_NONCE_METADATA_KEY.to_string(), self.wrapped_data_key_nonce),
+            (WRAPPED_DATA_KEY_TAG_METADATA_KEY.to_string(), self.wrapped_data_key_tag),
+            (NONCE_PREFIX_METADATA_KEY.to_string(), self.nonce_prefix),
+            (SEGMENT_SIZE_METADATA_KEY.to_string(), self.segment_size),
+        ])
+    }
+
+    fn from_metadata(metadata: &HashMap<String, String>) -> Result<EnvelopeHeader, String> {
+        let value = |key: &str| match metadata.get(key) {
+            Some(value) => Ok(value.to_string()),
+            None => Err(format!("encrypted object metadata `{}` is missing", key)),
+        };
+
+        Ok(EnvelopeHeader {
+            algorithm: value(ALGORITHM_METADATA_KEY)?,
+            key_id: value(KEY_ID_METADATA_KEY)?,
+            wrapped_data_key: value(WRAPPED_DATA_KEY_METADATA_KEY)?,
+            wrapped_data_key_nonce: value(WRAPPED_DATA_KEY_NONCE_METADATA_KEY)?,
+            wrapped_data_key_tag: value(WRAPPED_DATA_KEY_TAG_METADATA_KEY)?,
+            nonce_prefix: value(NONCE_PREFIX_METADATA_KEY)?,
+            segment_size: value(SEGMENT_SIZE_METADATA_KEY)?,
+        })
+    }
+}
+
+/// Splits a reader into segments, telling which one is the last: it's the only one that may be short or empty.
+struct Segments<'a> {
+    reader: &'a mut dyn Read,
+    segment_size: usize,
+    next_segment: Option<Vec<u8>>,
+    is_done: bool,
+}
+
+impl<'a> Segments<'a> {
+    fn new(reader: &'a mut dyn Read, segment_size: usize) -> Segments<'a> {
+        Segments {
+            reader,
+            segment_size,
+            next_segment: None,
+            is_done: false,
+        }
+    }
+
+    fn read_segment(&mut self) -> std::io::Result<Vec<u8>> {
+        let mut segment = vec![0u8; self.segment_size];
+        let size = fill_buffer(self.reader, &mut segment)?;
+        segment.truncate(size);
+
+        Ok(segment)
+    }
+
+    fn next(&mut self) -> std::io::Result<Option<(Vec<u8>, bool)>> {
+        if self.is_done {
+            return Ok(None);
+        }
+
+        let segment = match self.next_segment.take() {
+            Some(segment) => segment,
+            None => self.read_segment()?,
+        };
+
+        // a full segment is the last one only if nothing follows it
+        let next_segment = match segment.len() < self.segment_size {
+            true => vec![],
+            false => self.read_segment()?,
+        };
+        if next_segment.is_empty() {
+            self.is_done = true;
+            return Ok(Some((segment, true)));
+        }
+
+        self.next_segment = Some(next_segment);
+        Ok(Some((segment, false)))
+    }
+}
+
+fn segment_nonce(nonce_prefix: &[u8], counter: u32, is_last_segment: bool) -> Vec<u8> {
+    let mut nonce = nonce_prefix.to_vec();
+    nonce.extend_from_slice(&counter.to_be_bytes());
+    nonce.push(is_last_segment as u8);
+
+    nonce
+}
+
+fn next_counter(counter: u32) -> std::io::Result<u32> {
+    counter
+        .checked_add(1)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "object has too many encryption segments"))
+}
+
+/// Reader encrypting the wrapped reader content segment by segment, as it's read.
+pub struct EncryptingReader<'a> {
+    segments: Segments<'a>,
+    data_key: Vec<u8>,
+    nonce_prefix: Vec<u8>,
+    aad: Vec<u8>,
+    counter: u32,
+    encrypted_segment: Vec<u8>,
+    position: usize,
+}
+
+impl Read for EncryptingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.encrypted_segment.len() {
+            let (plaintext, is_last_segment) = match self.segments.next()? {
+                Some(segment) => segment,
+                None => return Ok(0),
+            };
+
+            let nonce = segment_nonce(&self.nonce_prefix, self.counter, is_last_segment);
+            let (mut cipher_text, tag) = aes_gcm_encrypt(&self.data_key, &nonce, &self.aad, &plaintext);
+            cipher_text.extend_from_slice(&tag);
+            self.encrypted_segment = cipher_text;
+            self.position = 0;
+            self.counter = next_counter(self.counter)?;
+        }
+
+        let size = buf.len().min(self.encrypted_segment.len() - self.position);
+        buf[..size].copy_from_slice(&self.encrypted_segment[self.position..self.position + size]);
+        self.position += size;
+
+        Ok(size)
+    }
+}
+
+/// Encrypted object, `metadata` has to be stored along the object as user metadata and `reader` is its content.
+pub struct EncryptedObject<'a> {
+    pub metadata: HashMap<String, String>,
+    pub reader: EncryptingReader<'a>,
+}
+
+fn random_bytes(size: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; size];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn decode_base64(value: &str, field: &str) -> Result<Vec<u8>, String> {
+    base64::decode(value).map_err(|e| format!("invalid `{}` value: {}", field, e))
+}
+
+fn decode_master_key(encryption_key: &EncryptionKey) -> Result<Vec<u8>, String> {
+    let key = decode_base64(encryption_key.key.as_str(), "key")?;
+    if key.len() != KEY_SIZE_IN_BYTES {
+        return Err(format!(
+            "encryption key `{}` should be {} bytes long",
+            encryption_key.id, KEY_SIZE_IN_BYTES
+        ));
+    }
+
+    Ok(key)
+}
+
+fn aes_gcm_encrypt(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let mut cipher_text = vec![0u8; plaintext.len()];
+    let mut tag = vec![0u8; TAG_SIZE_IN_BYTES];
+    AesGcm::new(KeySize::KeySize256, key, nonce, aad).encrypt(plaintext, &mut cipher_text, &mut tag);
+
+    (cipher_text, tag)
+}
+
+fn aes_gcm_decrypt(key: &[u8], nonce: &[u8], aad: &[u8], cipher_text: &[u8], tag: &[u8]) -> Result<Vec<u8>, String> {
+    if nonce.len() != NONCE_SIZE_IN_BYTES || tag.len() != TAG_SIZE_IN_BYTES {
+        return Err("invalid nonce or tag size".to_string());
+    }
+
+    let mut plaintext = vec![0u8; cipher_text.len()];
+    match AesGcm::new(KeySize::KeySize256, key, nonce, aad).decrypt(cipher_text, &mut plaintext, tag) {
+        true => Ok(plaintext),
+        false => Err("authentication failed, content or key is invalid".to_string()),
+    }
+}
+
+// cipher text is bound to its location, so an encrypted object cannot be swapped with another one
+fn object_aad(bucket_name: &str, object_key: &str) -> Vec<u8> {
+    format!("{}/{}", bucket_name, object_key).into_bytes()
+}
+
+pub fn is_encrypted(metadata: &HashMap<String, String>) -> bool {
+    metadata.contains_key(ALGORITHM_METADATA_KEY)
+}
+
+/// Encrypts `reader` content with a new random data key, itself encrypted with the current master key.
+pub fn encrypt<'a>(
+    encryption: &ObjectStorageEncryption,
+    bucket_name: &str,
+    object_key: &str,
+    reader: &'a mut dyn Read,
+) -> Result<EncryptedObject<'a>, String> {
+    let master_key = decode_master_key(&encryption.current_key)?;
+
+    let data_key = random_bytes(KEY_SIZE_IN_BYTES);
+    let nonce_prefix = random_bytes(NONCE_PREFIX_SIZE_IN_BYTES);
+
+    let wrapped_data_key_nonce = random_bytes(NONCE_SIZE_IN_BYTES);
+    let (wrapped_data_key, wrapped_data_key_tag) = aes_gcm_encrypt(
+        &master_key,
+        &wrapped_data_key_nonce,
+        encryption.current_key.id.as_bytes(),
+        &data_key,
+    );
+
+    let header = EnvelopeHeader {
+        algorithm: ENCRYPTION_ALGORITHM.to_string(),
+        key_id: encryption.current_key.id.to_string(),
+        wrapped_data_key: base64::encode(wrapped_data_key),
+        wrapped_data_key_nonce: base64::encode(wrapped_data_key_nonce),
+        wrapped_data_key_tag: base64::encode(wrapped_data_key_tag),
+        nonce_prefix: base64::encode(&nonce_prefix),
+        segment_size: SEGMENT_SIZE_IN_BYTES.to_string(),
+    };
+
+    Ok(EncryptedObject {
+        metadata: header.into_metadata(),
+        reader: EncryptingReader {
+            segments: Segments::new(reader, SEGMENT_SIZE_IN_BYTES),
+            data_key,
+            nonce_prefix,
+            aad: object_aad(bucket_name, object_key),
+            counter: 0,
+            encrypted_segment: vec![],
+            position: 0,
+        },
+    })
+}
+
+/// Decrypts an object according to its user metadata into `writer`, plaintext objects are copied untouched.
+/// On error, `writer` may have received the plaintext of the segments before the faulty one.
+pub fn decrypt(
+    encryption: Option<&ObjectStorageEncryption>,
+    bucket_name: &str,
+    object_key: &str,
+    metadata: &HashMap<String, String>,
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+) -> Result<(), String> {
+    if !is_encrypted(metadata) {
+        return std::io::copy(reader, writer).map(|_| ()).map_err(|e| e.to_string());
+    }
+
+    let encryption = match encryption {
+        Some(encryption) => encryption,
+        None => return Err("object is encrypted but no encryption key is configured".to_string()),
+    };
+
+    let header = EnvelopeHeader::from_metadata(metadata)?;
+
+    if header.algorithm != ENCRYPTION_ALGORITHM {
+        return Err(format!("unsupported encryption algorithm `{}`", header.algorithm));
+    }
+
+    let encryption_key = match encryption.find_key(header.key_id.as_str()) {
+        Some(encryption_key) => encryption_key,
+        None => return Err(format!("encryption key `{}` is not configured", header.key_id)),
+    };
+
+    let data_key = aes_gcm_decrypt(
+        &decode_master_key(encryption_key)?,
+        &decode_base64(header.wrapped_data_key_nonce.as_str(), "wrapped_data_key_nonce")?,
+        header.key_id.as_bytes(),
+        &decode_base64(header.wrapped_data_key.as_str(), "wrapped_data_key")?,
+        &decode_base64(header.wrapped_data_key_tag.as_str(), "wrapped_data_key_tag")?,
+    )?;
+
+    let nonce_prefix = decode_base64(header.nonce_prefix.as_str(), "nonce_prefix")?;
+    if nonce_prefix.len() != NONCE_PREFIX_SIZE_IN_BYTES {
+        return Err("invalid nonce prefix size".to_string());
+    }
+    let segment_size = match header.segment_size.parse::<usize>() {
+        Ok(segment_size) if segment_size > 0 => segment_size,
+        _ => return Err(format!("invalid segment size `{}`", header.segment_size)),
+    };
+
+    let aad = object_aad(bucket_name, object_key);
+    let mut segments = Segments::new(reader, segment_size + TAG_SIZE_IN_BYTES);
+    let mut counter: u32 = 0;
+    while let Some((encrypted_segment, is_last_segment)) = segments.next().map_err(|e| e.to_string())? {
+        if encrypted_segment.len() < TAG_SIZE_IN_BYTES {
+            return Err("encrypted segment is truncated".to_string());
+        }
+
+        let (cipher_text, tag) = encrypted_segment.split_at(encrypted_segment.len() - TAG_SIZE_IN_BYTES);
+        let nonce = segment_nonce(&nonce_prefix, counter, is_last_segment);
+        let plaintext = aes_gcm_decrypt(&data_key, &nonce, &aad, cipher_text, tag)?;
+        writer.write_all(&plaintext).map_err(|e| e.to_string())?;
+        counter = next_counter(counter).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Returns reader content encrypted when encryption is enabled in the context, `None` otherwise.
+pub fn encrypt_if_enabled<'a>(
+    context: &Context,
+    bucket_name: &str,
+    object_key: &str,
+    reader: &'a mut dyn Read,
+) -> Result<Option<EncryptedObject<'a>>, ObjectStorageError> {
+    let encryption = match context.object_storage_encryption() {
+        Some(encryption) => encryption,
+        None => return Ok(None),
+    };
+
+    encrypt(encryption, bucket_name, object_key, reader)
+        .map(Some)
+        .map_err(|raw_error_message| ObjectStorageError::CannotEncryptObject {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message,
+        })
+}
+
+/// Replaces a downloaded encrypted object by its plaintext, `metadata` are the object user metadata.
+/// Plaintext objects are left untouched.
+pub fn decrypt_file(
+    context: &Context,
+    bucket_name: &str,
+    object_key: &str,
+    metadata: Option<&HashMap<String, String>>,
+    file_path: &Path,
+) -> Result<(), ObjectStorageError> {
+    let cannot_decrypt = |raw_error_message: String| ObjectStorageError::CannotDecryptObject {
+        bucket_name: bucket_name.to_string(),
+        file_name: object_key.to_string(),
+        raw_error_message,
+    };
+
+    let metadata = match metadata {
+        Some(metadata) if is_encrypted(metadata) => metadata,
+        _ => return Ok(()),
+    };
+
+    // decrypt next to the object, then move the plaintext over it
+    let mut plaintext_path = file_path.as_os_str().to_os_string();
+    plaintext_path.push(".plaintext");
+    let plaintext_path = Path::new(&plaintext_path);
+
+    let decrypted = File::open(file_path)
+        .and_then(|encrypted_file| Ok((encrypted_file, File::create(plaintext_path)?)))
+        .map_err(|e| e.to_string())
+        .and_then(|(mut encrypted_file, plaintext_file)| {
+            let mut writer = BufWriter::new(plaintext_file);
+            decrypt(
+                context.object_storage_encryption(),
+                bucket_name,
+                object_key,
+                metadata,
+                &mut encrypted_file,
+                &mut writer,
+            )?;
+            writer.flush().map_err(|e| e.to_string())
+        })
+        .and_then(|_| std::fs::rename(plaintext_path, file_path).map_err(|e| e.to_string()));
+
+    if let Err(e) = decrypted {
+        // do not keep an undecipherable object around, `get` cache would serve it
+        let _ = std::fs::remove_file(plaintext_path);
+        let _ = std::fs::remove_file(file_path);
+        return Err(cannot_decrypt(e));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encryption_key(id: &str, byte: u8) -> EncryptionKey {
+        EncryptionKey {
+            id: id.to_string(),
+            key: base64::encode(vec![byte; KEY_SIZE_IN_BYTES]),
+        }
+    }
+
+    fn encryption(current_key: EncryptionKey, previous_keys: Vec<EncryptionKey>) -> ObjectStorageEncryption {
+        ObjectStorageEncryption {
+            current_key,
+            previous_keys,
+        }
+    }
+
+    fn encrypt_content(
+        encryption: &ObjectStorageEncryption,
+        object_key: &str,
+        plaintext: &[u8],
+    ) -> (HashMap<String, String>, Vec<u8>) {
+        let mut reader = plaintext;
+        let mut encrypted = encrypt(encryption, "bucket", object_key, &mut reader).unwrap();
+        let mut cipher_text = vec![];
+        encrypted.reader.read_to_end(&mut cipher_text).unwrap();
+
+        (encrypted.metadata, cipher_text)
+    }
+
+    fn decrypt_content(
+        encryption: Option<&ObjectStorageEncryption>,
+        object_key: &str,
+        metadata: &HashMap<String, String>,
+        content: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        let mut plaintext = vec![];
+        decrypt(encryption, "bucket", object_key, metadata, &mut &content[..], &mut plaintext)?;
+
+        Ok(plaintext)
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        // setup:
+        let encryption = encryption(encryption_key("key-1", 1), vec![]);
+        let plaintext = b"apiVersion: v1\nkind: Config".to_vec();
+
+        // execute:
+        let (metadata, cipher_text) = encrypt_content(&encryption, "kubeconfig.yaml", &plaintext);
+        let (other_metadata, other_cipher_text) = encrypt_content(&encryption, "kubeconfig.yaml", &plaintext);
+        let decrypted = decrypt_content(Some(&encryption), "kubeconfig.yaml", &metadata, &cipher_text).unwrap();
+
+        // verify:
+        assert!(is_encrypted(&metadata));
+        assert_eq!(Some(&"key-1".to_string()), metadata.get(KEY_ID_METADATA_KEY));
+        assert_ne!(plaintext, cipher_text[..plaintext.len()]);
+        // a single segment, followed by its tag
+        assert_eq!(plaintext.len() + TAG_SIZE_IN_BYTES, cipher_text.len());
+        // each object gets its own data key and nonce
+        assert_ne!(metadata, other_metadata);
+        assert_ne!(cipher_text, other_cipher_text);
+        assert_eq!(plaintext, decrypted);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_segments() {
+        // setup:
+        let encryption = encryption(encryption_key("key-1", 1), vec![]);
+        let test_cases = vec![
+            (vec![], 1, "empty object"),
+            (vec![42u8; SEGMENT_SIZE_IN_BYTES], 1, "exactly one segment"),
+            (vec![42u8; 2 * SEGMENT_SIZE_IN_BYTES], 2, "exactly two segments"),
+            (vec![42u8; 2 * SEGMENT_SIZE_IN_BYTES + 10], 3, "last segment is short"),
+        ];
+
+        for (plaintext, segments_count, description) in test_cases {
+            // execute:
+            let (metadata, cipher_text) = encrypt_content(&encryption, "backup.tar", &plaintext);
+            let decrypted = decrypt_content(Some(&encryption), "backup.tar", &metadata, &cipher_text);
+
+            // verify:
+            assert_eq!(
+                plaintext.len() + segments_count * TAG_SIZE_IN_BYTES,
+                cipher_text.len(),
+                "{}",
+                description
+            );
+            assert_eq!(Ok(plaintext), decrypted, "{}", description);
+        }
+    }
+
+    #[test]
+    fn test_decrypt_tampered_segments() {
+        // setup:
+        let encryption = encryption(encryption_key("key-1", 1), vec![]);
+        let encrypted_segment_size = SEGMENT_SIZE_IN_BYTES + TAG_SIZE_IN_BYTES;
+        let plaintext = [vec![1u8; SEGMENT_SIZE_IN_BYTES], vec![2u8; SEGMENT_SIZE_IN_BYTES]].concat();
+        let (metadata, cipher_text) = encrypt_content(&encryption, "backup.tar", &plaintext);
+        let (first_segment, second_segment) = cipher_text.split_at(encrypted_segment_size);
+
+        // execute & verify:
+        // dropping the last segment is detected, the first one was not flagged as the last one
+        assert!(decrypt_content(Some(&encryption), "backup.tar", &metadata, first_segment).is_err());
+        // segments are bound to their position
+        assert!(decrypt_content(
+            Some(&encryption),
+            "backup.tar",
+            &metadata,
+            &[second_segment, first_segment].concat()
+        )
+        .is_err());
+        assert!(decrypt_content(Some(&encryption), "backup.tar", &metadata, &cipher_text[..10]).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_plaintext_object() {
+        // setup:
+        let plaintext = b"apiVersion: v1".to_vec();
+        let other_metadata = HashMap::from([("content-owner".to_string(), "qovery".to_string())]);
+
+        // execute & verify:
+        assert_eq!(
+            Ok(plaintext.clone()),
+            decrypt_content(None, "kubeconfig.yaml", &HashMap::new(), &plaintext)
+        );
+        assert_eq!(
+            Ok(plaintext.clone()),
+            decrypt_content(
+                Some(&encryption(encryption_key("key-1", 1), vec![])),
+                "kubeconfig.yaml",
+                &other_metadata,
+                &plaintext
+            )
+        );
+    }
+
+    #[test]
+    fn test_decrypt_after_key_rotation() {
+        // setup:
+        let old_encryption = encryption(encryption_key("key-1", 1), vec![]);
+        let (metadata, cipher_text) = encrypt_content(&old_encryption, "kubeconfig.yaml", b"content");
+        let rotated_encryption = encryption(encryption_key("key-2", 2), vec![encryption_key("key-1", 1)]);
+        let forgotten_encryption = encryption(encryption_key("key-2", 2), vec![]);
+
+        // execute & verify:
+        assert_eq!(
+            Ok(b"content".to_vec()),
+            decrypt_content(Some(&rotated_encryption), "kubeconfig.yaml", &metadata, &cipher_text)
+        );
+        assert_eq!(
+            Err("encryption key `key-1` is not configured".to_string()),
+            decrypt_content(Some(&forgotten_encryption), "kubeconfig.yaml", &metadata, &cipher_text)
+        );
+    }
+
+    #[test]
+    fn test_decrypt_errors() {
+        // setup:
+        let encryption = encryption(encryption_key("key-1", 1), vec![]);
+        let (metadata, cipher_text) = encrypt_content(&encryption, "kubeconfig.yaml", b"content");
+        let mut tampered = cipher_text.clone();
+        tampered[0] ^= 1;
+        let mut truncated_metadata = metadata.clone();
+        truncated_metadata.remove(SEGMENT_SIZE_METADATA_KEY);
+        let wrong_key = ObjectStorageEncryption {
+            current_key: encryption_key("key-1", 2),
+            previous_keys: vec![],
+        };
+
+        // execute & verify:
+        assert!(decrypt_content(None, "kubeconfig.yaml", &metadata, &cipher_text).is_err());
+        assert!(decrypt_content(Some(&encryption), "kubeconfig.yaml", &metadata, &tampered).is_err());
+        assert!(decrypt_content(Some(&wrong_key), "kubeconfig.yaml", &metadata, &cipher_text).is_err());
+        assert_eq!(
+            Err("encrypted object metadata `qovery-encryption-segment-size` is missing".to_string()),
+            decrypt_content(Some(&encryption), "kubeconfig.yaml", &truncated_metadata, &cipher_text)
+        );
+        // moved objects cannot be decrypted
+        assert!(decrypt_content(Some(&encryption), "other.yaml", &metadata, &cipher_text).is_err());
+    }
+
+    #[test]
+    fn test_encryption_key_debug_hides_key() {
+        // execute:
+        let debug = format!("{:?}", encryption_key("key-1", 1));
+
+        // verify:
+        assert_eq!("EncryptionKey { id: \"key-1\", key: \"***\" }", debug);
+    }
+}
@@ -7,6 +7,11 @@ pub enum ObjectStorageError {
         bucket_name: String,
         raw_error_message: String,
     },
+    #[error("Invalid endpoint error for `{endpoint_url:?}`: {raw_error_message:?}.")]
+    InvalidEndpoint {
+        endpoint_url: String,
+        raw_error_message: String,
+    },
     #[error("Cannot create bucket error for `{bucket_name:?}`: {raw_error_message:?}.")]
     CannotCreateBucket {
         bucket_name: String,
@@ -58,4 +63,45 @@ pub enum ObjectStorageError {
         bucket_name: String,
         raw_error_message: String,
     },
+    #[error("Cannot list objects error for `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotListObjects {
+        bucket_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot get object file `{file_name:?}` metadata error in `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotGetObjectMetadata {
+        bucket_name: String,
+        file_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot delete object file `{file_name:?}` error in `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotDeleteObject {
+        bucket_name: String,
+        file_name: String,
+        raw_error_message: String,
+    },
+    #[error("Invalid object key `{file_name:?}` error in `{bucket_name:?}`: {raw_error_message:?}.")]
+    InvalidObjectKey {
+        bucket_name: String,
+        file_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot generate presigned url for `{file_name:?}` error in `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotGeneratePresignedUrl {
+        bucket_name: String,
+        file_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot encrypt object file `{file_name:?}` error in `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotEncryptObject {
+        bucket_name: String,
+        file_name: String,
+        raw_error_message: String,
+    },
+    #[error("Cannot decrypt object file `{file_name:?}` error in `{bucket_name:?}`: {raw_error_message:?}.")]
+    CannotDecryptObject {
+        bucket_name: String,
+        file_name: String,
+        raw_error_message: String,
+    },
 }
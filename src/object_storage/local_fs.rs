@@ -0,0 +1,726 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use crypto::digest::Digest;
+use crypto::md5::Md5;
+use walkdir::WalkDir;
+
+use crate::io_models::{Context, StringPath};
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::{encryption, Kind, ObjectMetadata, ObjectStorage, PresignedUrlMethod};
+
+// bucket names cannot start with a dot, so these directories never collide with a bucket
+const TEMPORARY_UPLOADS_DIR: &str = ".uploads";
+const USER_METADATA_DIR: &str = ".metadata";
+
+/// Object storage backed by a local directory, each bucket is a sub directory of `root_dir`.
+/// Useful for tests and air-gapped setups where no cloud object storage is reachable.
+pub struct LocalFs {
+    context: Context,
+    id: String,
+    name: String,
+    root_dir: PathBuf,
+}
+
+impl LocalFs {
+    pub fn new(context: Context, id: String, name: String, root_dir: PathBuf) -> Self {
+        LocalFs {
+            context,
+            id,
+            name,
+            root_dir,
+        }
+    }
+
+    fn is_bucket_name_valid(bucket_name: &str) -> Result<(), ObjectStorageError> {
+        let invalid_bucket_name = |raw_error_message: &str| ObjectStorageError::InvalidBucketName {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: raw_error_message.to_string(),
+        };
+
+        if bucket_name.is_empty() {
+            return Err(invalid_bucket_name("bucket name cannot be empty"));
+        }
+
+        if bucket_name.len() > 63 {
+            return Err(invalid_bucket_name("bucket name cannot be longer than 63 chars"));
+        }
+
+        if !bucket_name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.')
+        {
+            return Err(invalid_bucket_name(
+                "bucket name can only contain lowercase letters, digits, '-' and '.'",
+            ));
+        }
+
+        // prevents `.`, `..` and hidden directories
+        if !bucket_name.starts_with(|c: char| c.is_ascii_alphanumeric())
+            || !bucket_name.ends_with(|c: char| c.is_ascii_alphanumeric())
+        {
+            return Err(invalid_bucket_name("bucket name should start and end with a letter or a digit"));
+        }
+
+        Ok(())
+    }
+
+    fn is_object_key_valid(bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        let invalid_object_key = |raw_error_message: &str| ObjectStorageError::InvalidObjectKey {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message: raw_error_message.to_string(),
+        };
+
+        if object_key.is_empty() {
+            return Err(invalid_object_key("object key cannot be empty"));
+        }
+
+        if object_key.contains('\\') || object_key.contains('\0') {
+            return Err(invalid_object_key("object key cannot contain '\\' or null chars"));
+        }
+
+        // each `/` separated segment becomes a directory, none of them can escape the bucket directory
+        if object_key
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+        {
+            return Err(invalid_object_key(
+                "object key cannot be absolute nor contain empty, '.' or '..' segments",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn bucket_path(&self, bucket_name: &str) -> Result<PathBuf, ObjectStorageError> {
+        LocalFs::is_bucket_name_valid(bucket_name)?;
+
+        Ok(self.root_dir.join(bucket_name))
+    }
+
+    fn object_path(&self, bucket_name: &str, object_key: &str) -> Result<PathBuf, ObjectStorageError> {
+        LocalFs::is_object_key_valid(bucket_name, object_key)?;
+
+        Ok(object_key
+            .split('/')
+            .fold(self.bucket_path(bucket_name)?, |path, segment| path.join(segment)))
+    }
+
+    // user metadata of `bucket/some/key` are stored as json in `.metadata/bucket/some/key.json`
+    fn user_metadata_path(&self, bucket_name: &str, object_key: &str) -> Result<PathBuf, ObjectStorageError> {
+        LocalFs::is_bucket_name_valid(bucket_name)?;
+        LocalFs::is_object_key_valid(bucket_name, object_key)?;
+
+        Ok(self
+            .root_dir
+            .join(USER_METADATA_DIR)
+            .join(bucket_name)
+            .join(format!("{}.json", object_key)))
+    }
+
+    fn user_metadata(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+    ) -> Result<Option<HashMap<String, String>>, ObjectStorageError> {
+        let cannot_get_metadata = |raw_error_message: String| ObjectStorageError::CannotGetObjectMetadata {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message,
+        };
+
+        match std::fs::read(self.user_metadata_path(bucket_name, object_key)?) {
+            Ok(content) => serde_json::from_slice(&content)
+                .map(Some)
+                .map_err(|e| cannot_get_metadata(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(cannot_get_metadata(e.to_string())),
+        }
+    }
+
+    pub fn bucket_exists(&self, bucket_name: &str) -> bool {
+        match self.bucket_path(bucket_name) {
+            Ok(path) => path.is_dir(),
+            Err(_) => false,
+        }
+    }
+
+    fn write_user_metadata(user_metadata_path: &Path, metadata: &HashMap<String, String>) -> std::io::Result<()> {
+        if let Some(parent_dir) = user_metadata_path.parent() {
+            std::fs::create_dir_all(parent_dir)?;
+        }
+
+        std::fs::write(user_metadata_path, serde_json::to_vec(metadata)?)
+    }
+
+    fn remove_user_metadata(user_metadata_path: &Path) -> std::io::Result<()> {
+        match std::fs::remove_file(user_metadata_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn object_metadata(
+        bucket_name: &str,
+        object_key: &str,
+        object_path: &Path,
+    ) -> Result<ObjectMetadata, ObjectStorageError> {
+        let cannot_get_metadata = |e: std::io::Error| ObjectStorageError::CannotGetObjectMetadata {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message: e.to_string(),
+        };
+
+        let metadata = std::fs::metadata(object_path).map_err(cannot_get_metadata)?;
+        if !metadata.is_file() {
+            return Err(cannot_get_metadata(std::io::Error::from(std::io::ErrorKind::NotFound)));
+        }
+
+        // etag is the md5 of the content, as S3 does for single part uploads
+        let mut file = File::open(object_path).map_err(cannot_get_metadata)?;
+        let mut hasher = Md5::new();
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            match file.read(&mut buffer).map_err(cannot_get_metadata)? {
+                0 => break,
+                n => hasher.input(&buffer[..n]),
+            }
+        }
+
+        Ok(ObjectMetadata {
+            key: object_key.to_string(),
+            size: metadata.len() as i64,
+            etag: Some(format!("\"{}\"", hasher.result_str())),
+            last_modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+        })
+    }
+}
+
+impl ObjectStorage for LocalFs {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::LocalFs
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn is_valid(&self) -> Result<(), ObjectStorageError> {
+        Ok(())
+    }
+
+    fn create_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        let bucket_path = self.bucket_path(bucket_name)?;
+
+        std::fs::create_dir_all(bucket_path).map_err(|e| ObjectStorageError::CannotCreateBucket {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })
+    }
+
+    fn delete_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        let bucket_path = self.bucket_path(bucket_name)?;
+
+        match std::fs::remove_dir_all(bucket_path)
+            .and_then(|_| std::fs::remove_dir_all(self.root_dir.join(USER_METADATA_DIR).join(bucket_name)))
+        {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(ObjectStorageError::CannotDeleteBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn get(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        use_cache: bool,
+    ) -> Result<(StringPath, File), ObjectStorageError> {
+        let object_path = self.object_path(bucket_name, object_key)?;
+
+        let workspace_directory = crate::fs::workspace_directory(
+            self.context().workspace_root_dir(),
+            self.context().execution_id(),
+            format!("object-storage/local_fs/{}", self.name()),
+        )
+        .map_err(|err| ObjectStorageError::CannotGetWorkspace {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: err.to_string(),
+        })?;
+
+        let file_path = format!("{}/{}/{}", workspace_directory, bucket_name, object_key);
+
+        if use_cache {
+            // does config file already exists?
+            if let Ok(file) = File::open(file_path.as_str()) {
+                return Ok((file_path, file));
+            }
+        }
+
+        if !object_path.is_file() {
+            return Err(ObjectStorageError::CannotGetObjectFile {
+                bucket_name: bucket_name.to_string(),
+                file_name: object_key.to_string(),
+                raw_error_message: "object doesn't exist".to_string(),
+            });
+        }
+
+        // copy the object into the workspace, so callers can't alter the stored object
+        let path = Path::new(file_path.as_str());
+        if let Some(parent_dir) = path.parent() {
+            let _ = std::fs::create_dir_all(parent_dir);
+        }
+
+        if let Err(e) = std::fs::copy(&object_path, path) {
+            return Err(ObjectStorageError::CannotCreateFile {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            });
+        }
+
+        let metadata = self.user_metadata(bucket_name, object_key)?;
+        encryption::decrypt_file(self.context(), bucket_name, object_key, metadata.as_ref(), path)?;
+
+        match File::open(path) {
+            Ok(file) => Ok((file_path, file)),
+            Err(e) => Err(ObjectStorageError::CannotOpenFile {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError> {
+        let mut file = File::open(file_path).map_err(|e| ObjectStorageError::CannotReadFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        self.put_from_reader(bucket_name, object_key, &mut file)
+    }
+
+    fn put_from_reader(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        reader: &mut dyn Read,
+    ) -> Result<(), ObjectStorageError> {
+        let object_path = self.object_path(bucket_name, object_key)?;
+        let cannot_upload_error = |e: std::io::Error| ObjectStorageError::CannotUploadFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        };
+
+        if !self.bucket_exists(bucket_name) {
+            return Err(cannot_upload_error(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "bucket doesn't exist",
+            )));
+        }
+
+        // write into a temporary file first, then move it so readers never see a partial object
+        let uploads_dir = self.root_dir.join(TEMPORARY_UPLOADS_DIR);
+        std::fs::create_dir_all(&uploads_dir).map_err(cannot_upload_error)?;
+        let temporary_path = uploads_dir.join(uuid::Uuid::new_v4().to_string());
+
+        let user_metadata_path = self.user_metadata_path(bucket_name, object_key)?;
+        let (user_metadata, mut reader): (Option<HashMap<String, String>>, Box<dyn Read + '_>) =
+            match encryption::encrypt_if_enabled(self.context(), bucket_name, object_key, reader)? {
+                Some(encrypted_object) => (Some(encrypted_object.metadata), Box::new(encrypted_object.reader)),
+                None => (None, Box::new(reader)),
+            };
+
+        let mut temporary_file = File::create(&temporary_path).map_err(cannot_upload_error)?;
+        let upload_result = std::io::copy(&mut reader, &mut temporary_file)
+            .and_then(|_| temporary_file.flush())
+            .and_then(|_| match object_path.parent() {
+                Some(parent_dir) => std::fs::create_dir_all(parent_dir),
+                None => Ok(()),
+            })
+            .and_then(|_| match &user_metadata {
+                Some(user_metadata) => LocalFs::write_user_metadata(&user_metadata_path, user_metadata),
+                None => LocalFs::remove_user_metadata(&user_metadata_path),
+            })
+            .and_then(|_| std::fs::rename(&temporary_path, &object_path));
+
+        if let Err(e) = upload_result {
+            let _ = std::fs::remove_file(&temporary_path);
+            return Err(cannot_upload_error(e));
+        }
+
+        Ok(())
+    }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<ObjectMetadata>, ObjectStorageError> {
+        let bucket_path = self.bucket_path(bucket_name)?;
+        let cannot_list_objects = |raw_error_message: String| ObjectStorageError::CannotListObjects {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message,
+        };
+
+        if !bucket_path.is_dir() {
+            return Err(cannot_list_objects("bucket doesn't exist".to_string()));
+        }
+
+        let mut objects = vec![];
+        for entry in WalkDir::new(&bucket_path).sort_by_file_name() {
+            let entry = entry.map_err(|e| cannot_list_objects(e.to_string()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let object_key = match entry.path().strip_prefix(&bucket_path) {
+                Ok(relative_path) => relative_path
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<String>>()
+                    .join("/"),
+                Err(e) => return Err(cannot_list_objects(e.to_string())),
+            };
+
+            if let Some(prefix) = prefix {
+                if !object_key.starts_with(prefix) {
+                    continue;
+                }
+            }
+
+            objects.push(LocalFs::object_metadata(bucket_name, object_key.as_str(), entry.path())?);
+        }
+
+        Ok(objects)
+    }
+
+    fn head_object(&self, bucket_name: &str, object_key: &str) -> Result<ObjectMetadata, ObjectStorageError> {
+        let object_path = self.object_path(bucket_name, object_key)?;
+
+        LocalFs::object_metadata(bucket_name, object_key, object_path.as_path())
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        let object_path = self.object_path(bucket_name, object_key)?;
+        let user_metadata_path = self.user_metadata_path(bucket_name, object_key)?;
+
+        match std::fs::remove_file(object_path).and_then(|_| LocalFs::remove_user_metadata(&user_metadata_path)) {
+            Ok(_) => Ok(()),
+            // same as S3, deleting a missing object is not an error
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(ObjectStorageError::CannotDeleteObject {
+                bucket_name: bucket_name.to_string(),
+                file_name: object_key.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn presigned_url(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        _method: PresignedUrlMethod,
+        _expires_in: Duration,
+    ) -> Result<String, ObjectStorageError> {
+        Err(ObjectStorageError::CannotGeneratePresignedUrl {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message: "presigned urls are not supported by local filesystem object storage".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::docker::Docker;
+    use crate::io_models::Metadata;
+    use crate::object_storage::encryption::{EncryptionKey, ObjectStorageEncryption};
+    use tempfile::TempDir;
+
+    fn local_fs(root_dir: &TempDir) -> LocalFs {
+        local_fs_with_metadata(root_dir, None)
+    }
+
+    fn local_fs_with_metadata(root_dir: &TempDir, metadata: Option<Metadata>) -> LocalFs {
+        let workspace_dir = root_dir.path().join("workspace").to_str().unwrap().to_string();
+
+        LocalFs::new(
+            Context::new(
+                "organization_id".to_string(),
+                "cluster_id".to_string(),
+                "execution_id".to_string(),
+                workspace_dir.to_string(),
+                workspace_dir,
+                true,
+                None,
+                vec![],
+                metadata,
+                Docker::new_with_options(false, None).unwrap(),
+            ),
+            "id".to_string(),
+            "local".to_string(),
+            root_dir.path().join("storage"),
+        )
+    }
+
+    struct TestCase<'a> {
+        input: &'a str,
+        expected_valid: bool,
+        description: &'a str,
+    }
+
+    #[test]
+    fn test_is_bucket_name_valid() {
+        // setup:
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                input: "",
+                expected_valid: false,
+                description: "bucket name is empty",
+            },
+            TestCase {
+                input: "..",
+                expected_valid: false,
+                description: "bucket name is parent dir",
+            },
+            TestCase {
+                input: ".uploads",
+                expected_valid: false,
+                description: "bucket name is hidden dir",
+            },
+            TestCase {
+                input: "bucket/../../etc",
+                expected_valid: false,
+                description: "bucket name contains a path",
+            },
+            TestCase {
+                input: "Bucket",
+                expected_valid: false,
+                description: "bucket name contains uppercase chars",
+            },
+            TestCase {
+                input: "qovery-kubeconfigs-z1234",
+                expected_valid: true,
+                description: "bucket name is valid",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = LocalFs::is_bucket_name_valid(tc.input);
+
+            // verify:
+            assert_eq!(tc.expected_valid, result.is_ok(), "{}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_is_object_key_valid() {
+        // setup:
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                input: "",
+                expected_valid: false,
+                description: "object key is empty",
+            },
+            TestCase {
+                input: "/etc/passwd",
+                expected_valid: false,
+                description: "object key is absolute",
+            },
+            TestCase {
+                input: "logs/../../other-bucket/key",
+                expected_valid: false,
+                description: "object key contains parent dir",
+            },
+            TestCase {
+                input: "logs//key",
+                expected_valid: false,
+                description: "object key contains empty segment",
+            },
+            TestCase {
+                input: "..\\key",
+                expected_valid: false,
+                description: "object key contains backslash",
+            },
+            TestCase {
+                input: "logs/2022/cluster.log",
+                expected_valid: true,
+                description: "object key is valid",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = LocalFs::is_object_key_valid("bucket", tc.input);
+
+            // verify:
+            assert_eq!(tc.expected_valid, result.is_ok(), "{}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_bucket_and_objects_lifecycle() {
+        // setup:
+        let root_dir = TempDir::new().unwrap();
+        let storage = local_fs(&root_dir);
+
+        // execute & verify:
+        assert!(storage.create_bucket("bucket").is_ok());
+        assert!(storage.bucket_exists("bucket"));
+
+        assert!(storage
+            .put_from_reader("bucket", "logs/a.log", &mut "content".as_bytes())
+            .is_ok());
+        assert!(storage
+            .put_from_reader("bucket", "kubeconfig.yaml", &mut "a".as_bytes())
+            .is_ok());
+        assert!(storage.put_from_reader("missing", "key", &mut "a".as_bytes()).is_err());
+
+        let objects = storage.list_objects("bucket", None).unwrap();
+        assert_eq!(
+            vec!["kubeconfig.yaml", "logs/a.log"],
+            objects.iter().map(|o| o.key.as_str()).collect::<Vec<&str>>()
+        );
+        assert_eq!(1, storage.list_objects("bucket", Some("logs/")).unwrap().len());
+
+        let metadata = storage.head_object("bucket", "logs/a.log").unwrap();
+        assert_eq!(7, metadata.size);
+        // md5("content")
+        assert_eq!(Some("\"9a0364b9e99bb480dd25e1f0284c8555\"".to_string()), metadata.etag);
+        assert!(metadata.last_modified.is_some());
+
+        assert!(storage.delete_object("bucket", "logs/a.log").is_ok());
+        assert!(storage.head_object("bucket", "logs/a.log").is_err());
+
+        assert!(storage.delete_bucket("bucket").is_ok());
+        assert!(!storage.bucket_exists("bucket"));
+    }
+
+    #[test]
+    fn test_get_use_cache() {
+        // setup:
+        let root_dir = TempDir::new().unwrap();
+        let storage = local_fs(&root_dir);
+        storage.create_bucket("bucket").unwrap();
+        storage
+            .put_from_reader("bucket", "kubeconfig.yaml", &mut "v1".as_bytes())
+            .unwrap();
+        let (path, _) = storage.get("bucket", "kubeconfig.yaml", false).unwrap();
+
+        storage
+            .put_from_reader("bucket", "kubeconfig.yaml", &mut "v2".as_bytes())
+            .unwrap();
+
+        // execute:
+        let (cached_path, _) = storage.get("bucket", "kubeconfig.yaml", true).unwrap();
+        let cached_content = std::fs::read_to_string(&cached_path).unwrap();
+        let (fresh_path, _) = storage.get("bucket", "kubeconfig.yaml", false).unwrap();
+        let fresh_content = std::fs::read_to_string(&fresh_path).unwrap();
+
+        // verify:
+        assert_eq!(path, cached_path);
+        assert_eq!("v1", cached_content);
+        assert_eq!("v2", fresh_content);
+        assert!(storage.get("bucket", "missing.yaml", false).is_err());
+        assert!(storage.get("bucket", "../../etc/passwd", false).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_objects() {
+        // setup:
+        let encryption_key = |id: &str, byte: u8| EncryptionKey {
+            id: id.to_string(),
+            key: base64::encode(vec![byte; 32]),
+        };
+        let metadata =
+            |encryption: ObjectStorageEncryption| Some(Metadata::new(None, None, None, None, Some(encryption)));
+        let root_dir = TempDir::new().unwrap();
+        let storage = local_fs_with_metadata(
+            &root_dir,
+            metadata(ObjectStorageEncryption {
+                current_key: encryption_key("key-1", 1),
+                previous_keys: vec![],
+            }),
+        );
+        storage.create_bucket("bucket").unwrap();
+
+        // execute:
+        storage
+            .put_from_reader("bucket", "kubeconfig.yaml", &mut "apiVersion: v1".as_bytes())
+            .unwrap();
+        let stored_content = std::fs::read(storage.object_path("bucket", "kubeconfig.yaml").unwrap()).unwrap();
+        let user_metadata = storage.user_metadata("bucket", "kubeconfig.yaml").unwrap();
+        let (_, mut file) = storage.get("bucket", "kubeconfig.yaml", false).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        // verify:
+        assert_ne!(b"apiVersion: v1".to_vec(), stored_content);
+        assert!(encryption::is_encrypted(&user_metadata.unwrap()));
+        assert_eq!("apiVersion: v1", content);
+        // objects are listed without their metadata
+        assert_eq!(1, storage.list_objects("bucket", None).unwrap().len());
+        // without the key, the object cannot be read
+        assert!(matches!(
+            local_fs(&root_dir).get("bucket", "kubeconfig.yaml", false),
+            Err(ObjectStorageError::CannotDecryptObject { .. })
+        ));
+
+        // execute: rotate the key
+        let rotated_storage = local_fs_with_metadata(
+            &root_dir,
+            metadata(ObjectStorageEncryption {
+                current_key: encryption_key("key-2", 2),
+                previous_keys: vec![encryption_key("key-1", 1)],
+            }),
+        );
+        rotated_storage
+            .rotate_encryption_key("bucket", "kubeconfig.yaml")
+            .unwrap();
+
+        // verify: old key is not needed anymore
+        let storage_with_new_key_only = local_fs_with_metadata(
+            &root_dir,
+            metadata(ObjectStorageEncryption {
+                current_key: encryption_key("key-2", 2),
+                previous_keys: vec![],
+            }),
+        );
+        let (_, mut file) = storage_with_new_key_only
+            .get("bucket", "kubeconfig.yaml", false)
+            .unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        assert_eq!("apiVersion: v1", content);
+
+        // verify: overwriting or deleting an object drops its encryption metadata
+        local_fs(&root_dir)
+            .put_from_reader("bucket", "kubeconfig.yaml", &mut "plaintext".as_bytes())
+            .unwrap();
+        assert_eq!(None, storage.user_metadata("bucket", "kubeconfig.yaml").unwrap());
+        let (_, mut file) = storage.get("bucket", "kubeconfig.yaml", false).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        assert_eq!("plaintext", content);
+        storage
+            .put_from_reader("bucket", "kubeconfig.yaml", &mut "apiVersion: v1".as_bytes())
+            .unwrap();
+        storage.delete_object("bucket", "kubeconfig.yaml").unwrap();
+        assert_eq!(None, storage.user_metadata("bucket", "kubeconfig.yaml").unwrap());
+    }
+}
@@ -1,13 +1,20 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::io_models::{Context, StringPath};
 use crate::object_storage::errors::ObjectStorageError;
 use std::fs::File;
+use std::io::Read;
+use std::time::Duration;
 
+pub mod encryption;
 pub mod errors;
+pub mod local_fs;
 pub mod s3;
+pub mod s3_compatible;
 pub mod scaleway_object_storage;
 pub mod spaces;
+mod utilities;
 
 pub trait ObjectStorage {
     fn context(&self) -> &Context;
@@ -27,6 +34,42 @@ pub trait ObjectStorage {
         use_cache: bool,
     ) -> Result<(StringPath, File), ObjectStorageError>;
     fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError>;
+    /// Uploads reader content, large objects are sent using a multipart upload.
+    fn put_from_reader(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        reader: &mut dyn Read,
+    ) -> Result<(), ObjectStorageError>;
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<ObjectMetadata>, ObjectStorageError>;
+    fn head_object(&self, bucket_name: &str, object_key: &str) -> Result<ObjectMetadata, ObjectStorageError>;
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError>;
+    fn presigned_url(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+    ) -> Result<String, ObjectStorageError>;
+    /// Re-uploads an object so it gets encrypted with the current encryption key.
+    fn rotate_encryption_key(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        let (_, mut file) = self.get(bucket_name, object_key, false)?;
+        self.put_from_reader(bucket_name, object_key, &mut file)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectMetadata {
+    pub key: String,
+    pub size: i64,
+    pub etag: Option<String>,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PresignedUrlMethod {
+    Get,
+    Put,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -35,4 +78,6 @@ pub enum Kind {
     S3,
     Spaces,
     ScalewayOs,
+    S3Compatible,
+    LocalFs,
 }
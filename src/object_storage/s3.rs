@@ -1,22 +1,24 @@
 use chrono::{DateTime, Utc};
 use retry::delay::Fixed;
 use std::fs::File;
+use std::io::Read;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::cloud_provider::aws::regions::AwsRegion;
-use rusoto_core::credential::StaticProvider;
+use rusoto_core::credential::{AwsCredentials, StaticProvider};
 use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
 use rusoto_s3::{
     CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectsRequest,
     GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ObjectIdentifier, PutBucketTaggingRequest,
-    PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging, S3 as RusotoS3,
+    PutBucketVersioningRequest, S3Client, Tag, Tagging, S3 as RusotoS3,
 };
 use tokio::io;
 
 use crate::io_models::{Context, StringPath};
 use crate::object_storage::errors::ObjectStorageError;
-use crate::object_storage::{Kind, ObjectStorage};
+use crate::object_storage::{encryption, utilities, Kind, ObjectMetadata, ObjectStorage, PresignedUrlMethod};
 use crate::runtime::block_on;
 
 pub struct S3 {
@@ -57,9 +59,13 @@ impl S3 {
         StaticProvider::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None)
     }
 
+    fn get_region(&self) -> RusotoRegion {
+        RusotoRegion::from_str(&self.region.to_aws_format())
+            .unwrap_or_else(|_| panic!("S3 region `{}` doesn't seems to be valid.", self.region.to_aws_format()))
+    }
+
     fn get_s3_client(&self) -> S3Client {
-        let region = RusotoRegion::from_str(&self.region.to_aws_format())
-            .unwrap_or_else(|_| panic!("S3 region `{}` doesn't seems to be valid.", self.region.to_aws_format()));
+        let region = self.get_region();
         let client = Client::new_with(
             self.get_credentials(),
             HttpClient::new().expect("unable to create new Http client"),
@@ -276,6 +282,7 @@ impl ObjectStorage for S3 {
             ..Default::default()
         })) {
             Ok(mut res) => {
+                let metadata = res.metadata.take();
                 let body = res.body.take();
                 let mut body = body.unwrap().into_async_read();
 
@@ -294,6 +301,7 @@ impl ObjectStorage for S3 {
                 ) {
                     Ok(mut created_file) => match block_on(io::copy(&mut body, &mut created_file)) {
                         Ok(_) => {
+                            encryption::decrypt_file(self.context(), bucket_name, object_key, metadata.as_ref(), path)?;
                             let file = File::open(path).unwrap();
                             Ok((file_path, file))
                         }
@@ -317,32 +325,78 @@ impl ObjectStorage for S3 {
     }
 
     fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError> {
-        let _ = S3::is_bucket_name_valid(bucket_name)?;
+        let mut file = File::open(file_path).map_err(|e| ObjectStorageError::CannotReadFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
 
-        let s3_client = self.get_s3_client();
+        self.put_from_reader(bucket_name, object_key, &mut file)
+    }
 
-        match block_on(s3_client.put_object(PutObjectRequest {
-            bucket: bucket_name.to_string(),
-            key: object_key.to_string(),
-            body: Some(StreamingBody::from(match std::fs::read(file_path) {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(ObjectStorageError::CannotReadFile {
-                        bucket_name: bucket_name.to_string(),
-                        raw_error_message: e.to_string(),
-                    })
-                }
-            })),
-            expected_bucket_owner: None,
-            ..Default::default()
-        })) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ObjectStorageError::CannotUploadFile {
-                bucket_name: bucket_name.to_string(),
-                raw_error_message: e.to_string(),
-            }),
+    fn put_from_reader(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        reader: &mut dyn Read,
+    ) -> Result<(), ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client();
+        match encryption::encrypt_if_enabled(self.context(), bucket_name, object_key, reader)? {
+            Some(mut encrypted_object) => utilities::put_object_from_reader(
+                &s3_client,
+                bucket_name,
+                object_key,
+                Some(encrypted_object.metadata),
+                &mut encrypted_object.reader,
+            ),
+            None => utilities::put_object_from_reader(&s3_client, bucket_name, object_key, None, reader),
         }
     }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<ObjectMetadata>, ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+
+        utilities::list_objects(&self.get_s3_client(), bucket_name, prefix)
+    }
+
+    fn head_object(&self, bucket_name: &str, object_key: &str) -> Result<ObjectMetadata, ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+
+        utilities::head_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+
+        utilities::delete_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn presigned_url(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+    ) -> Result<String, ObjectStorageError> {
+        S3::is_bucket_name_valid(bucket_name)?;
+        utilities::check_presigned_url_encryption(
+            &self.get_s3_client(),
+            self.context(),
+            bucket_name,
+            object_key,
+            method,
+        )?;
+
+        Ok(utilities::presigned_url(
+            &self.get_region(),
+            &AwsCredentials::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None),
+            bucket_name,
+            object_key,
+            method,
+            expires_in,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,536 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+use rusoto_core::request::DispatchSignedRequestFuture;
+use rusoto_core::signature::SignedRequest;
+use rusoto_core::{Client, DispatchSignedRequest, HttpClient, Region};
+use rusoto_credential::{AwsCredentials, StaticProvider};
+use rusoto_s3::{
+    CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectsRequest,
+    GetObjectRequest, HeadBucketRequest, ObjectIdentifier, S3Client, S3,
+};
+use serde::{Deserialize, Serialize};
+use tokio::io;
+
+use crate::io_models::{Context, StringPath};
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::{encryption, utilities, Kind, ObjectMetadata, ObjectStorage, PresignedUrlMethod};
+use crate::runtime::block_on;
+
+/// Settings of an S3 compatible server, used as `Kubernetes::config_file_store` instead of the cloud provider one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct S3CompatibleSettings {
+    pub endpoint_url: String,
+    pub region: String,
+    pub path_style: bool,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// Object storage for any S3 compatible server exposed on a custom endpoint (MinIO, Ceph, Wasabi, ...).
+///
+/// With `path_style`, buckets are addressed in the path (`https://endpoint/bucket/key`), otherwise in the
+/// host name (`https://bucket.endpoint/key`).
+pub struct S3Compatible {
+    context: Context,
+    id: String,
+    name: String,
+    endpoint_url: String,
+    region: String,
+    path_style: bool,
+    access_key_id: String,
+    secret_access_key: String,
+}
+
+impl S3Compatible {
+    pub fn new(
+        context: Context,
+        id: String,
+        name: String,
+        endpoint_url: String,
+        region: String,
+        path_style: bool,
+        access_key_id: String,
+        secret_access_key: String,
+    ) -> Self {
+        S3Compatible {
+            context,
+            id,
+            name,
+            endpoint_url,
+            region,
+            path_style,
+            access_key_id,
+            secret_access_key,
+        }
+    }
+
+    pub fn from_settings(context: Context, id: String, name: String, settings: &S3CompatibleSettings) -> Self {
+        S3Compatible::new(
+            context,
+            id,
+            name,
+            settings.endpoint_url.to_string(),
+            settings.region.to_string(),
+            settings.path_style,
+            settings.access_key_id.to_string(),
+            settings.secret_access_key.to_string(),
+        )
+    }
+
+    fn get_region(&self) -> Region {
+        Region::Custom {
+            name: self.region.to_string(),
+            endpoint: self.endpoint_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn get_credentials(&self) -> StaticProvider {
+        StaticProvider::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None)
+    }
+
+    fn get_s3_client(&self) -> S3Client {
+        // rusoto builds path-style requests only, virtual hosted-style ones are rewritten and signed on dispatch
+        let client = match self.path_style {
+            true => Client::new_with(self.get_credentials(), HttpClient::new().unwrap()),
+            false => Client::new_not_signing(VirtualHostedStyleDispatcher {
+                http_client: HttpClient::new().unwrap(),
+                credentials: AwsCredentials::new(
+                    self.access_key_id.clone(),
+                    self.secret_access_key.clone(),
+                    None,
+                    None,
+                ),
+            }),
+        };
+
+        S3Client::new_with_client(client, self.get_region())
+    }
+
+    fn is_bucket_name_valid(bucket_name: &str) -> Result<(), ObjectStorageError> {
+        if bucket_name.is_empty() {
+            return Err(ObjectStorageError::InvalidBucketName {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: "bucket name cannot be empty".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn is_endpoint_valid(endpoint_url: &str, path_style: bool) -> Result<(), ObjectStorageError> {
+        let url = url::Url::parse(endpoint_url).map_err(|e| ObjectStorageError::InvalidEndpoint {
+            endpoint_url: endpoint_url.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        // virtual hosted-style prefixes the host with the bucket name, it needs a domain name
+        if !path_style && !matches!(url.host(), Some(url::Host::Domain(_))) {
+            return Err(ObjectStorageError::InvalidEndpoint {
+                endpoint_url: endpoint_url.to_string(),
+                raw_error_message: "virtual hosted-style addressing requires a domain name endpoint, use path-style"
+                    .to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn bucket_exists(&self, bucket_name: &str) -> bool {
+        let s3_client = self.get_s3_client();
+
+        block_on(s3_client.head_bucket(HeadBucketRequest {
+            bucket: bucket_name.to_string(),
+            expected_bucket_owner: None,
+        }))
+        .is_ok()
+    }
+
+    fn empty_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let objects_to_be_deleted = utilities::list_objects(&self.get_s3_client(), bucket_name, None).map_err(|e| {
+            ObjectStorageError::CannotEmptyBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            }
+        })?;
+
+        // delete objects request is limited to 1000 keys
+        for objects in objects_to_be_deleted.chunks(1000) {
+            if let Err(e) = block_on(
+                self.get_s3_client().delete_objects(DeleteObjectsRequest {
+                    bucket: bucket_name.to_string(),
+                    delete: Delete {
+                        objects: objects
+                            .iter()
+                            .map(|o| ObjectIdentifier {
+                                key: o.key.to_string(),
+                                version_id: None,
+                            })
+                            .collect(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            ) {
+                return Err(ObjectStorageError::CannotEmptyBucket {
+                    bucket_name: bucket_name.to_string(),
+                    raw_error_message: e.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ObjectStorage for S3Compatible {
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::S3Compatible
+    }
+
+    fn id(&self) -> &str {
+        self.id.as_str()
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn is_valid(&self) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_endpoint_valid(self.endpoint_url.as_str(), self.path_style)
+    }
+
+    fn create_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        // check if bucket already exists, if so, no need to recreate it
+        if self.bucket_exists(bucket_name) {
+            return Ok(());
+        }
+
+        match block_on(self.get_s3_client().create_bucket(CreateBucketRequest {
+            bucket: bucket_name.to_string(),
+            create_bucket_configuration: Some(CreateBucketConfiguration {
+                location_constraint: Some(self.region.to_string()),
+            }),
+            ..Default::default()
+        })) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ObjectStorageError::CannotCreateBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn delete_bucket(&self, bucket_name: &str) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        // make sure to delete all bucket content before trying to delete the bucket
+        self.empty_bucket(bucket_name)?;
+
+        match block_on(self.get_s3_client().delete_bucket(DeleteBucketRequest {
+            bucket: bucket_name.to_string(),
+            expected_bucket_owner: None,
+        })) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ObjectStorageError::CannotDeleteBucket {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn get(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        use_cache: bool,
+    ) -> Result<(StringPath, File), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let workspace_directory = crate::fs::workspace_directory(
+            self.context().workspace_root_dir(),
+            self.context().execution_id(),
+            format!("object-storage/s3_compatible/{}", self.name()),
+        )
+        .map_err(|err| ObjectStorageError::CannotGetWorkspace {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: err.to_string(),
+        })?;
+
+        let file_path = format!("{}/{}/{}", workspace_directory, bucket_name, object_key);
+
+        if use_cache {
+            // does config file already exists?
+            if let Ok(file) = File::open(file_path.as_str()) {
+                return Ok((file_path, file));
+            }
+        }
+
+        let mut res = block_on(self.get_s3_client().get_object(GetObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            ..Default::default()
+        }))
+        .map_err(|e| ObjectStorageError::CannotGetObjectFile {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        let metadata = res.metadata.take();
+        let mut body = match res.body.take() {
+            Some(body) => body.into_async_read(),
+            None => {
+                return Err(ObjectStorageError::CannotGetObjectFile {
+                    bucket_name: bucket_name.to_string(),
+                    file_name: object_key.to_string(),
+                    raw_error_message: "object body is empty".to_string(),
+                })
+            }
+        };
+
+        // create parent dir
+        let path = Path::new(file_path.as_str());
+        if let Some(parent_dir) = path.parent() {
+            let _ = block_on(tokio::fs::create_dir_all(parent_dir));
+        }
+
+        // create file
+        let mut created_file = block_on(
+            tokio::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(path),
+        )
+        .map_err(|e| ObjectStorageError::CannotOpenFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        if let Err(e) = block_on(io::copy(&mut body, &mut created_file)) {
+            return Err(ObjectStorageError::CannotCreateFile {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            });
+        }
+
+        encryption::decrypt_file(self.context(), bucket_name, object_key, metadata.as_ref(), path)?;
+
+        match File::open(path) {
+            Ok(file) => Ok((file_path, file)),
+            Err(e) => Err(ObjectStorageError::CannotOpenFile {
+                bucket_name: bucket_name.to_string(),
+                raw_error_message: e.to_string(),
+            }),
+        }
+    }
+
+    fn put(&self, bucket_name: &str, object_key: &str, file_path: &str) -> Result<(), ObjectStorageError> {
+        let mut file = File::open(file_path).map_err(|e| ObjectStorageError::CannotReadFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        self.put_from_reader(bucket_name, object_key, &mut file)
+    }
+
+    fn put_from_reader(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        reader: &mut dyn Read,
+    ) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client();
+        match encryption::encrypt_if_enabled(self.context(), bucket_name, object_key, reader)? {
+            Some(mut encrypted_object) => utilities::put_object_from_reader(
+                &s3_client,
+                bucket_name,
+                object_key,
+                Some(encrypted_object.metadata),
+                &mut encrypted_object.reader,
+            ),
+            None => utilities::put_object_from_reader(&s3_client, bucket_name, object_key, None, reader),
+        }
+    }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<ObjectMetadata>, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        utilities::list_objects(&self.get_s3_client(), bucket_name, prefix)
+    }
+
+    fn head_object(&self, bucket_name: &str, object_key: &str) -> Result<ObjectMetadata, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        utilities::head_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+
+        utilities::delete_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn presigned_url(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+    ) -> Result<String, ObjectStorageError> {
+        S3Compatible::is_bucket_name_valid(bucket_name)?;
+        utilities::check_presigned_url_encryption(
+            &self.get_s3_client(),
+            self.context(),
+            bucket_name,
+            object_key,
+            method,
+        )?;
+
+        let credentials = AwsCredentials::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None);
+        if self.path_style {
+            return Ok(utilities::presigned_url(
+                &self.get_region(),
+                &credentials,
+                bucket_name,
+                object_key,
+                method,
+                expires_in,
+            ));
+        }
+
+        let http_method = match method {
+            PresignedUrlMethod::Get => "GET",
+            PresignedUrlMethod::Put => "PUT",
+        };
+        let mut request = SignedRequest::new(
+            http_method,
+            "s3",
+            &self.get_region(),
+            format!("/{}/{}", bucket_name, object_key).as_str(),
+        );
+        to_virtual_hosted_style(&mut request);
+
+        Ok(request.generate_presigned_url(&credentials, &expires_in, false))
+    }
+}
+
+/// Dispatches the path-style requests built by rusoto as virtual hosted-style ones, signed once rewritten.
+struct VirtualHostedStyleDispatcher {
+    http_client: HttpClient,
+    credentials: AwsCredentials,
+}
+
+impl DispatchSignedRequest for VirtualHostedStyleDispatcher {
+    fn dispatch(&self, mut request: SignedRequest, timeout: Option<Duration>) -> DispatchSignedRequestFuture {
+        to_virtual_hosted_style(&mut request);
+        request.sign(&self.credentials);
+
+        self.http_client.dispatch(request, timeout)
+    }
+}
+
+/// Moves the bucket name from the request path (`/bucket/key`) to its host name (`bucket.endpoint`).
+fn to_virtual_hosted_style(request: &mut SignedRequest) {
+    let path = request.path.trim_start_matches('/').to_string();
+    let (bucket_name, object_key) = path.split_once('/').unwrap_or((path.as_str(), ""));
+    if bucket_name.is_empty() {
+        return;
+    }
+
+    let hostname = format!("{}.{}", bucket_name, request.hostname());
+    request.set_hostname(Some(hostname));
+    request.path = format!("/{}", object_key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestCase<'a> {
+        endpoint_url_input: &'a str,
+        path_style_input: bool,
+        expected_output: Result<(), ObjectStorageError>,
+        description: &'a str,
+    }
+
+    #[test]
+    fn test_is_endpoint_valid() {
+        // setup:
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                endpoint_url_input: "not an url",
+                path_style_input: true,
+                expected_output: Err(ObjectStorageError::InvalidEndpoint {
+                    endpoint_url: "not an url".to_string(),
+                    raw_error_message: "relative URL without a base".to_string(),
+                }),
+                description: "endpoint is not an url",
+            },
+            TestCase {
+                endpoint_url_input: "http://127.0.0.1:9000",
+                path_style_input: false,
+                expected_output: Err(ObjectStorageError::InvalidEndpoint {
+                    endpoint_url: "http://127.0.0.1:9000".to_string(),
+                    raw_error_message:
+                        "virtual hosted-style addressing requires a domain name endpoint, use path-style".to_string(),
+                }),
+                description: "virtual hosted-style addressing on an ip address",
+            },
+            TestCase {
+                endpoint_url_input: "http://localhost:9000",
+                path_style_input: true,
+                expected_output: Ok(()),
+                description: "path-style endpoint is valid",
+            },
+            TestCase {
+                endpoint_url_input: "https://s3.wasabisys.com",
+                path_style_input: false,
+                expected_output: Ok(()),
+                description: "virtual hosted-style endpoint is valid",
+            },
+        ];
+
+        for tc in test_cases {
+            // execute:
+            let result = S3Compatible::is_endpoint_valid(tc.endpoint_url_input, tc.path_style_input);
+
+            // verify:
+            assert_eq!(tc.expected_output, result, "{}", tc.description);
+        }
+    }
+
+    #[test]
+    fn test_to_virtual_hosted_style() {
+        // setup:
+        let region = Region::Custom {
+            name: "us-east-1".to_string(),
+            endpoint: "https://s3.wasabisys.com".to_string(),
+        };
+        let mut object_request = SignedRequest::new("GET", "s3", &region, "/my-bucket/my/object.yaml");
+        let mut bucket_request = SignedRequest::new("PUT", "s3", &region, "/my-bucket");
+
+        // execute:
+        to_virtual_hosted_style(&mut object_request);
+        to_virtual_hosted_style(&mut bucket_request);
+
+        // verify:
+        assert_eq!("my-bucket.s3.wasabisys.com", object_request.hostname());
+        assert_eq!("/my/object.yaml", object_request.path);
+        assert_eq!("my-bucket.s3.wasabisys.com", bucket_request.hostname());
+        assert_eq!("/", bucket_request.path);
+    }
+}
@@ -1,19 +1,21 @@
 use chrono::{DateTime, Utc};
 use std::fs::File;
+use std::io::Read;
 use std::path::Path;
+use std::time::Duration;
 
 use crate::io_models::{Context, StringPath};
-use crate::object_storage::{Kind, ObjectStorage};
+use crate::object_storage::{encryption, utilities, Kind, ObjectMetadata, ObjectStorage, PresignedUrlMethod};
 
 use crate::models::scaleway::ScwZone;
 use crate::object_storage::errors::ObjectStorageError;
 use crate::runtime::block_on;
 use rusoto_core::{Client, HttpClient, Region as RusotoRegion};
-use rusoto_credential::StaticProvider;
+use rusoto_credential::{AwsCredentials, StaticProvider};
 use rusoto_s3::{
     CreateBucketConfiguration, CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectsRequest,
     GetObjectRequest, HeadBucketRequest, ListObjectsRequest, ObjectIdentifier, PutBucketTaggingRequest,
-    PutBucketVersioningRequest, PutObjectRequest, S3Client, StreamingBody, Tag, Tagging, S3,
+    PutBucketVersioningRequest, S3Client, Tag, Tagging, S3,
 };
 use tokio::io;
 
@@ -60,11 +62,15 @@ impl ScalewayOS {
         }
     }
 
-    fn get_s3_client(&self) -> S3Client {
-        let region = RusotoRegion::Custom {
+    fn get_region(&self) -> RusotoRegion {
+        RusotoRegion::Custom {
             name: self.zone.region().to_string(),
             endpoint: self.get_endpoint_url_for_region(),
-        };
+        }
+    }
+
+    fn get_s3_client(&self) -> S3Client {
+        let region = self.get_region();
 
         let client = Client::new_with(self.get_credentials(), HttpClient::new().unwrap());
 
@@ -308,6 +314,7 @@ impl ObjectStorage for ScalewayOS {
             ..Default::default()
         })) {
             Ok(mut res) => {
+                let metadata = res.metadata.take();
                 let body = res.body.take();
                 let mut body = body.unwrap().into_async_read();
 
@@ -326,6 +333,7 @@ impl ObjectStorage for ScalewayOS {
                 ) {
                     Ok(mut created_file) => match block_on(io::copy(&mut body, &mut created_file)) {
                         Ok(_) => {
+                            encryption::decrypt_file(self.context(), bucket_name, object_key, metadata.as_ref(), path)?;
                             let file = File::open(path).unwrap();
                             Ok((file_path, file))
                         }
@@ -352,29 +360,78 @@ impl ObjectStorage for ScalewayOS {
         // TODO(benjamin): switch to `scaleway-api-rs` once object storage will be supported (https://github.com/Qovery/scaleway-api-rs/issues/12).
         let _ = ScalewayOS::is_bucket_name_valid(bucket_name)?;
 
-        let s3_client = self.get_s3_client();
+        let mut file = File::open(file_path).map_err(|e| ObjectStorageError::CannotReadFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
 
-        match block_on(s3_client.put_object(PutObjectRequest {
-            bucket: bucket_name.to_string(),
-            key: object_key.to_string(),
-            body: Some(StreamingBody::from(match std::fs::read(file_path) {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(ObjectStorageError::CannotReadFile {
-                        bucket_name: bucket_name.to_string(),
-                        raw_error_message: e.to_string(),
-                    })
-                }
-            })),
-            ..Default::default()
-        })) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ObjectStorageError::CannotUploadFile {
-                bucket_name: bucket_name.to_string(),
-                raw_error_message: e.to_string(),
-            }),
+        self.put_from_reader(bucket_name, object_key, &mut file)
+    }
+
+    fn put_from_reader(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        reader: &mut dyn Read,
+    ) -> Result<(), ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client();
+        match encryption::encrypt_if_enabled(self.context(), bucket_name, object_key, reader)? {
+            Some(mut encrypted_object) => utilities::put_object_from_reader(
+                &s3_client,
+                bucket_name,
+                object_key,
+                Some(encrypted_object.metadata),
+                &mut encrypted_object.reader,
+            ),
+            None => utilities::put_object_from_reader(&s3_client, bucket_name, object_key, None, reader),
         }
     }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<ObjectMetadata>, ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+
+        utilities::list_objects(&self.get_s3_client(), bucket_name, prefix)
+    }
+
+    fn head_object(&self, bucket_name: &str, object_key: &str) -> Result<ObjectMetadata, ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+
+        utilities::head_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+
+        utilities::delete_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn presigned_url(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+    ) -> Result<String, ObjectStorageError> {
+        ScalewayOS::is_bucket_name_valid(bucket_name)?;
+        utilities::check_presigned_url_encryption(
+            &self.get_s3_client(),
+            self.context(),
+            bucket_name,
+            object_key,
+            method,
+        )?;
+
+        Ok(utilities::presigned_url(
+            &self.get_region(),
+            &AwsCredentials::new(self.access_key.clone(), self.secret_token.clone(), None, None),
+            bucket_name,
+            object_key,
+            method,
+            expires_in,
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -1,20 +1,23 @@
+use std::collections::HashMap;
 use std::fs::File;
+use std::io::Read;
 use std::path::Path;
+use std::time::Duration;
 
 use retry::delay::Fibonacci;
 use retry::{Error, OperationResult};
 use rusoto_core::{Client, HttpClient, Region};
-use rusoto_credential::StaticProvider;
+use rusoto_credential::{AwsCredentials, StaticProvider};
 use rusoto_s3::{
     CreateBucketRequest, Delete, DeleteBucketRequest, DeleteObjectsRequest, GetObjectRequest, HeadBucketRequest,
-    ListObjectsRequest, ObjectIdentifier, PutObjectRequest, S3Client, StreamingBody, S3,
+    ListObjectsRequest, ObjectIdentifier, S3Client, S3,
 };
 use tokio::io;
 
 use crate::io_models::{Context, StringPath};
 use crate::models::digital_ocean::DoRegion;
 use crate::object_storage::errors::ObjectStorageError;
-use crate::object_storage::{Kind, ObjectStorage};
+use crate::object_storage::{encryption, utilities, Kind, ObjectMetadata, ObjectStorage, PresignedUrlMethod};
 use crate::runtime;
 use crate::runtime::block_on;
 
@@ -62,11 +65,15 @@ impl Spaces {
         StaticProvider::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None)
     }
 
-    fn get_s3_client(&self) -> S3Client {
-        let region = Region::Custom {
+    fn get_region(&self) -> Region {
+        Region::Custom {
             name: self.region.to_string(),
             endpoint: self.get_endpoint_url_for_region(),
-        };
+        }
+    }
+
+    fn get_s3_client(&self) -> S3Client {
+        let region = self.get_region();
 
         let credentials = self.get_credentials();
         let client = Client::new_with(credentials, HttpClient::new().unwrap());
@@ -152,7 +159,7 @@ impl Spaces {
         bucket_name: T,
         object_key: S,
         download_into_file_path: X,
-    ) -> Result<File, ObjectStorageError>
+    ) -> Result<(File, Option<HashMap<String, String>>), ObjectStorageError>
     where
         T: Into<String>,
         S: Into<String>,
@@ -179,6 +186,7 @@ impl Spaces {
 
         match object {
             Ok(mut obj_bod) => {
+                let metadata = obj_bod.metadata.take();
                 let body = obj_bod.body.take();
                 let mut body = body.unwrap().into_async_read();
 
@@ -192,7 +200,7 @@ impl Spaces {
 
                 match file {
                     Ok(mut created_file) => match io::copy(&mut body, &mut created_file).await {
-                        Ok(_) => Ok(File::open(download_into_file_path.as_ref()).unwrap()),
+                        Ok(_) => Ok((File::open(download_into_file_path.as_ref()).unwrap(), metadata)),
                         Err(e) => Err(ObjectStorageError::CannotReadFile {
                             bucket_name: bucket_name.to_string(),
                             raw_error_message: e.to_string(),
@@ -322,7 +330,16 @@ impl ObjectStorage for Spaces {
         });
 
         let file = match result {
-            Ok(_) => File::open(file_path.as_str()),
+            Ok((_, metadata)) => {
+                encryption::decrypt_file(
+                    self.context(),
+                    bucket_name,
+                    object_key,
+                    metadata.as_ref(),
+                    Path::new(file_path.as_str()),
+                )?;
+                File::open(file_path.as_str())
+            }
             Err(err) => {
                 return match err {
                     Error::Operation { error, .. } => Err(error),
@@ -348,27 +365,76 @@ impl ObjectStorage for Spaces {
         // TODO(benjamin): switch to `digitalocean-api-rs` once we'll made the auo-generated lib
         let _ = Spaces::is_bucket_name_valid(bucket_name)?;
 
-        let s3_client = self.get_s3_client();
+        let mut file = File::open(file_path).map_err(|e| ObjectStorageError::CannotReadFile {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
 
-        match block_on(s3_client.put_object(PutObjectRequest {
-            bucket: bucket_name.to_string(),
-            key: object_key.to_string(),
-            body: Some(StreamingBody::from(match std::fs::read(file_path) {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(ObjectStorageError::CannotReadFile {
-                        bucket_name: bucket_name.to_string(),
-                        raw_error_message: e.to_string(),
-                    })
-                }
-            })),
-            ..Default::default()
-        })) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ObjectStorageError::CannotUploadFile {
-                bucket_name: bucket_name.to_string(),
-                raw_error_message: e.to_string(),
-            }),
+        self.put_from_reader(bucket_name, object_key, &mut file)
+    }
+
+    fn put_from_reader(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        reader: &mut dyn Read,
+    ) -> Result<(), ObjectStorageError> {
+        Spaces::is_bucket_name_valid(bucket_name)?;
+
+        let s3_client = self.get_s3_client();
+        match encryption::encrypt_if_enabled(self.context(), bucket_name, object_key, reader)? {
+            Some(mut encrypted_object) => utilities::put_object_from_reader(
+                &s3_client,
+                bucket_name,
+                object_key,
+                Some(encrypted_object.metadata),
+                &mut encrypted_object.reader,
+            ),
+            None => utilities::put_object_from_reader(&s3_client, bucket_name, object_key, None, reader),
         }
     }
+
+    fn list_objects(&self, bucket_name: &str, prefix: Option<&str>) -> Result<Vec<ObjectMetadata>, ObjectStorageError> {
+        Spaces::is_bucket_name_valid(bucket_name)?;
+
+        utilities::list_objects(&self.get_s3_client(), bucket_name, prefix)
+    }
+
+    fn head_object(&self, bucket_name: &str, object_key: &str) -> Result<ObjectMetadata, ObjectStorageError> {
+        Spaces::is_bucket_name_valid(bucket_name)?;
+
+        utilities::head_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn delete_object(&self, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+        Spaces::is_bucket_name_valid(bucket_name)?;
+
+        utilities::delete_object(&self.get_s3_client(), bucket_name, object_key)
+    }
+
+    fn presigned_url(
+        &self,
+        bucket_name: &str,
+        object_key: &str,
+        method: PresignedUrlMethod,
+        expires_in: Duration,
+    ) -> Result<String, ObjectStorageError> {
+        Spaces::is_bucket_name_valid(bucket_name)?;
+        utilities::check_presigned_url_encryption(
+            &self.get_s3_client(),
+            self.context(),
+            bucket_name,
+            object_key,
+            method,
+        )?;
+
+        Ok(utilities::presigned_url(
+            &self.get_region(),
+            &AwsCredentials::new(self.access_key_id.clone(), self.secret_access_key.clone(), None, None),
+            bucket_name,
+            object_key,
+            method,
+            expires_in,
+        ))
+    }
 }
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusoto_core::Region;
+use rusoto_credential::AwsCredentials;
+use rusoto_s3::util::{PreSignedRequest, PreSignedRequestOption};
+use rusoto_s3::{
+    AbortMultipartUploadRequest, CompleteMultipartUploadRequest, CompletedMultipartUpload, CompletedPart,
+    CreateMultipartUploadRequest, DeleteObjectRequest, GetObjectRequest, HeadObjectRequest, ListObjectsV2Request,
+    PutObjectRequest, S3Client, StreamingBody, UploadPartRequest, S3,
+};
+
+use crate::io_models::Context;
+use crate::object_storage::encryption;
+use crate::object_storage::errors::ObjectStorageError;
+use crate::object_storage::{ObjectMetadata, PresignedUrlMethod};
+use crate::runtime::block_on;
+
+// S3 multipart upload requires every part but the last one to be at least 5 MiB
+pub const MULTIPART_UPLOAD_PART_SIZE_IN_BYTES: usize = 8 * 1024 * 1024;
+
+/// Reads from `reader` until `buffer` is full or the reader is exhausted, returns the number of bytes read.
+pub(crate) fn fill_buffer(reader: &mut dyn Read, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut read = 0;
+    while read < buffer.len() {
+        match reader.read(&mut buffer[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(read)
+}
+
+/// Parses dates returned by S3 compatible APIs: RFC 3339 for listings and RFC 2822 for object headers.
+fn parse_s3_date(raw_date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw_date)
+        .or_else(|_| DateTime::parse_from_rfc2822(raw_date))
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+}
+
+pub fn list_objects(
+    s3_client: &S3Client,
+    bucket_name: &str,
+    prefix: Option<&str>,
+) -> Result<Vec<ObjectMetadata>, ObjectStorageError> {
+    let mut objects = vec![];
+    let mut continuation_token: Option<String> = None;
+
+    loop {
+        let res = block_on(s3_client.list_objects_v2(ListObjectsV2Request {
+            bucket: bucket_name.to_string(),
+            prefix: prefix.map(|p| p.to_string()),
+            continuation_token: continuation_token.clone(),
+            ..Default::default()
+        }))
+        .map_err(|e| ObjectStorageError::CannotListObjects {
+            bucket_name: bucket_name.to_string(),
+            raw_error_message: e.to_string(),
+        })?;
+
+        objects.extend(res.contents.unwrap_or_default().into_iter().filter_map(|object| {
+            let last_modified = object.last_modified.as_deref().and_then(parse_s3_date);
+            let size = object.size.unwrap_or(0);
+            let etag = object.e_tag;
+            object.key.map(|key| ObjectMetadata {
+                key,
+                size,
+                etag,
+                last_modified,
+            })
+        }));
+
+        match res.next_continuation_token {
+            Some(token) if res.is_truncated.unwrap_or(false) => continuation_token = Some(token),
+            _ => break,
+        }
+    }
+
+    Ok(objects)
+}
+
+pub fn head_object(
+    s3_client: &S3Client,
+    bucket_name: &str,
+    object_key: &str,
+) -> Result<ObjectMetadata, ObjectStorageError> {
+    match block_on(s3_client.head_object(HeadObjectRequest {
+        bucket: bucket_name.to_string(),
+        key: object_key.to_string(),
+        ..Default::default()
+    })) {
+        Ok(res) => Ok(ObjectMetadata {
+            key: object_key.to_string(),
+            size: res.content_length.unwrap_or(0),
+            etag: res.e_tag,
+            last_modified: res.last_modified.as_deref().and_then(parse_s3_date),
+        }),
+        Err(e) => Err(ObjectStorageError::CannotGetObjectMetadata {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message: e.to_string(),
+        }),
+    }
+}
+
+/// Returns object user metadata, `None` when the object cannot be found.
+fn head_object_user_metadata(
+    s3_client: &S3Client,
+    bucket_name: &str,
+    object_key: &str,
+) -> Option<HashMap<String, String>> {
+    block_on(s3_client.head_object(HeadObjectRequest {
+        bucket: bucket_name.to_string(),
+        key: object_key.to_string(),
+        ..Default::default()
+    }))
+    .ok()
+    .map(|res| res.metadata.unwrap_or_default())
+}
+
+pub fn delete_object(s3_client: &S3Client, bucket_name: &str, object_key: &str) -> Result<(), ObjectStorageError> {
+    match block_on(s3_client.delete_object(DeleteObjectRequest {
+        bucket: bucket_name.to_string(),
+        key: object_key.to_string(),
+        ..Default::default()
+    })) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(ObjectStorageError::CannotDeleteObject {
+            bucket_name: bucket_name.to_string(),
+            file_name: object_key.to_string(),
+            raw_error_message: e.to_string(),
+        }),
+    }
+}
+
+/// Uploads `reader` content with its user `metadata`, objects bigger than one part are sent using a multipart upload.
+pub fn put_object_from_reader(
+    s3_client: &S3Client,
+    bucket_name: &str,
+    object_key: &str,
+    metadata: Option<HashMap<String, String>>,
+    reader: &mut dyn Read,
+) -> Result<(), ObjectStorageError> {
+    let cannot_read_error = |e: std::io::Error| ObjectStorageError::CannotReadFile {
+        bucket_name: bucket_name.to_string(),
+        raw_error_message: e.to_string(),
+    };
+    let cannot_upload_error = |raw_error_message: String| ObjectStorageError::CannotUploadFile {
+        bucket_name: bucket_name.to_string(),
+        raw_error_message,
+    };
+
+    let mut buffer = vec![0; MULTIPART_UPLOAD_PART_SIZE_IN_BYTES];
+    let read = fill_buffer(reader, &mut buffer).map_err(cannot_read_error)?;
+
+    // small object, a single request is enough
+    if read < MULTIPART_UPLOAD_PART_SIZE_IN_BYTES {
+        buffer.truncate(read);
+        return match block_on(s3_client.put_object(PutObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            content_length: Some(read as i64),
+            body: Some(StreamingBody::from(buffer)),
+            metadata,
+            ..Default::default()
+        })) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(cannot_upload_error(e.to_string())),
+        };
+    }
+
+    let upload_id = match block_on(s3_client.create_multipart_upload(CreateMultipartUploadRequest {
+        bucket: bucket_name.to_string(),
+        key: object_key.to_string(),
+        metadata,
+        ..Default::default()
+    })) {
+        Ok(res) => match res.upload_id {
+            Some(upload_id) => upload_id,
+            None => return Err(cannot_upload_error("multipart upload id is missing".to_string())),
+        },
+        Err(e) => return Err(cannot_upload_error(e.to_string())),
+    };
+
+    let mut upload_parts = |buffer: &mut Vec<u8>, mut read: usize| -> Result<Vec<CompletedPart>, ObjectStorageError> {
+        let mut completed_parts = vec![];
+        let mut part_number = 1;
+
+        while read > 0 {
+            let res = block_on(s3_client.upload_part(UploadPartRequest {
+                bucket: bucket_name.to_string(),
+                key: object_key.to_string(),
+                upload_id: upload_id.to_string(),
+                part_number,
+                content_length: Some(read as i64),
+                body: Some(StreamingBody::from(buffer[..read].to_vec())),
+                ..Default::default()
+            }))
+            .map_err(|e| cannot_upload_error(e.to_string()))?;
+
+            completed_parts.push(CompletedPart {
+                e_tag: res.e_tag,
+                part_number: Some(part_number),
+            });
+
+            part_number += 1;
+            read = fill_buffer(reader, buffer).map_err(cannot_read_error)?;
+        }
+
+        Ok(completed_parts)
+    };
+
+    let completed_parts = match upload_parts(&mut buffer, read) {
+        Ok(parts) => parts,
+        Err(e) => {
+            // not blocking if abort fails, incomplete uploads are cleaned by bucket lifecycle
+            let _ = block_on(s3_client.abort_multipart_upload(AbortMultipartUploadRequest {
+                bucket: bucket_name.to_string(),
+                key: object_key.to_string(),
+                upload_id: upload_id.to_string(),
+                ..Default::default()
+            }));
+            return Err(e);
+        }
+    };
+
+    match block_on(s3_client.complete_multipart_upload(CompleteMultipartUploadRequest {
+        bucket: bucket_name.to_string(),
+        key: object_key.to_string(),
+        upload_id,
+        multipart_upload: Some(CompletedMultipartUpload {
+            parts: Some(completed_parts),
+        }),
+        ..Default::default()
+    })) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(cannot_upload_error(e.to_string())),
+    }
+}
+
+/// Presigned urls bypass the engine encryption: an encrypted object would be downloaded as cipher text
+/// and an uploaded object would be stored in plaintext, so they are refused in those cases.
+pub fn check_presigned_url_encryption(
+    s3_client: &S3Client,
+    context: &Context,
+    bucket_name: &str,
+    object_key: &str,
+    method: PresignedUrlMethod,
+) -> Result<(), ObjectStorageError> {
+    let raw_error_message = match method {
+        PresignedUrlMethod::Get => match head_object_user_metadata(s3_client, bucket_name, object_key) {
+            Some(metadata) if encryption::is_encrypted(&metadata) => {
+                "object is encrypted, it would be downloaded as cipher text"
+            }
+            _ => return Ok(()),
+        },
+        PresignedUrlMethod::Put => match context.object_storage_encryption() {
+            Some(_) => "object storage encryption is enabled, the object would be uploaded in plaintext",
+            None => return Ok(()),
+        },
+    };
+
+    Err(ObjectStorageError::CannotGeneratePresignedUrl {
+        bucket_name: bucket_name.to_string(),
+        file_name: object_key.to_string(),
+        raw_error_message: raw_error_message.to_string(),
+    })
+}
+
+pub fn presigned_url(
+    region: &Region,
+    credentials: &AwsCredentials,
+    bucket_name: &str,
+    object_key: &str,
+    method: PresignedUrlMethod,
+    expires_in: Duration,
+) -> String {
+    let option = PreSignedRequestOption { expires_in };
+
+    match method {
+        PresignedUrlMethod::Get => GetObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            ..Default::default()
+        }
+        .get_presigned_url(region, credentials, &option),
+        PresignedUrlMethod::Put => PutObjectRequest {
+            bucket: bucket_name.to_string(),
+            key: object_key.to_string(),
+            ..Default::default()
+        }
+        .get_presigned_url(region, credentials, &option),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_fill_buffer() {
+        // setup:
+        let content = vec![42u8; 10];
+        let mut reader = content.as_slice();
+        let mut buffer = vec![0u8; 4];
+
+        // execute & verify:
+        assert_eq!(4, fill_buffer(&mut reader, &mut buffer).unwrap());
+        assert_eq!(4, fill_buffer(&mut reader, &mut buffer).unwrap());
+        assert_eq!(2, fill_buffer(&mut reader, &mut buffer).unwrap());
+        assert_eq!(0, fill_buffer(&mut reader, &mut buffer).unwrap());
+    }
+
+    #[test]
+    fn test_presigned_url_uses_path_style() {
+        // setup:
+        let region = Region::Custom {
+            name: "us-east-1".to_string(),
+            endpoint: "http://localhost:9000".to_string(),
+        };
+        let credentials = AwsCredentials::new("access_key", "secret_key", None, None);
+
+        // execute:
+        let url = presigned_url(
+            &region,
+            &credentials,
+            "bucket",
+            "object.yaml",
+            PresignedUrlMethod::Get,
+            Duration::from_secs(60),
+        );
+
+        // verify:
+        assert!(url.starts_with("http://localhost:9000/bucket/object.yaml?"), "{}", url);
+        assert!(url.contains("X-Amz-Expires=60"), "{}", url);
+    }
+
+    #[test]
+    fn test_parse_s3_date() {
+        // setup:
+        let expected = Utc.ymd(2022, 5, 12).and_hms(17, 50, 30);
+
+        // execute & verify:
+        assert_eq!(Some(expected), parse_s3_date("2022-05-12T17:50:30.000Z"));
+        assert_eq!(Some(expected), parse_s3_date("Thu, 12 May 2022 17:50:30 GMT"));
+        assert_eq!(None, parse_s3_date("not a date"));
+    }
+}
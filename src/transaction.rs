@@ -1,4 +1,4 @@
-use crate::build_platform::BuildError;
+use crate::build_platform::{Build, BuildError};
 use crate::cloud_provider::environment::Environment;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -14,7 +14,6 @@ use crate::io_models::{
     EnvironmentError, ListenersHelper, ProgressInfo, ProgressLevel, ProgressScope, QoveryIdentifier,
 };
 use crate::logger::Logger;
-use crate::models::application::ApplicationService;
 
 pub struct Transaction<'a> {
     engine: &'a EngineConfig,
@@ -129,20 +128,13 @@ impl<'a> Transaction<'a> {
         Ok(())
     }
 
-    fn build_and_push_applications(
+    fn build_and_push_images(
         &self,
-        applications: &mut [Box<dyn ApplicationService>],
+        mut builds_to_push: Vec<(ProgressScope, &mut Build)>,
         option: &DeploymentOption,
     ) -> Result<(), EngineError> {
-        // do the same for applications
-        let mut apps_to_build = applications
-            .iter_mut()
-            // build only applications that are set with Action: Create
-            .filter(|app| *app.action() == Action::Create)
-            .collect::<Vec<_>>();
-
         // If nothing to build, do nothing
-        if apps_to_build.is_empty() {
+        if builds_to_push.is_empty() {
             return Ok(());
         }
 
@@ -172,9 +164,9 @@ impl<'a> Transaction<'a> {
         let cr_registry = self.engine.container_registry();
         let _ = cr_registry.create_registry().map_err(cr_to_engine_error)?;
 
-        for app in apps_to_build.iter_mut() {
+        for (progress_scope, build) in builds_to_push.iter_mut() {
             // If image already exist in the registry, skip the build
-            if !option.force_build && cr_registry.does_image_exists(&app.get_build().image) {
+            if !option.force_build && cr_registry.does_image_exists(&build.image) {
                 continue;
             }
 
@@ -182,17 +174,14 @@ impl<'a> Transaction<'a> {
             let _ = self
                 .engine
                 .container_registry()
-                .create_repository(app.get_build().image.repository_name())
+                .create_repository(build.image.repository_name())
                 .map_err(cr_to_engine_error)?;
 
             // Ok now everything is setup, we can try to build the app
-            let build_result = self
-                .engine
-                .build_platform()
-                .build(app.get_build_mut(), &self.is_transaction_aborted);
+            let build_result = self.engine.build_platform().build(build, &self.is_transaction_aborted);
 
             // logging
-            let image_name = app.get_build().image.full_image_name_with_tag();
+            let image_name = build.image.full_image_name_with_tag();
             let msg = match &build_result {
                 Ok(_) => format!("✅ Container image {} is built and ready to use", &image_name),
                 Err(BuildError::Aborted(_)) => format!("🚫 Container image {} build has been canceled", &image_name),
@@ -200,9 +189,7 @@ impl<'a> Transaction<'a> {
             };
 
             let progress_info = ProgressInfo::new(
-                ProgressScope::Application {
-                    id: app.id().to_string(),
-                },
+                progress_scope.clone(),
                 match build_result.is_ok() {
                     true => ProgressLevel::Info,
                     false => ProgressLevel::Error,
@@ -323,9 +310,34 @@ impl<'a> Transaction<'a> {
                         return TransactionResult::Canceled;
                     }
 
-                    // build applications
-                    let applications = &mut (environment.as_ref().borrow_mut()).applications;
-                    match self.build_and_push_applications(applications, &option) {
+                    // build applications and jobs, only the ones that are set with Action: Create
+                    let mut environment = environment.as_ref().borrow_mut();
+                    let environment = &mut *environment;
+                    let mut builds_to_push = environment
+                        .applications
+                        .iter_mut()
+                        .filter(|app| *app.action() == Action::Create)
+                        .map(|app| {
+                            let progress_scope = ProgressScope::Application {
+                                id: app.id().to_string(),
+                            };
+                            (progress_scope, app.get_build_mut())
+                        })
+                        .collect::<Vec<_>>();
+                    builds_to_push.extend(
+                        environment
+                            .jobs
+                            .iter_mut()
+                            .filter(|job| *job.action() == Action::Create)
+                            .map(|job| {
+                                let progress_scope = ProgressScope::Job {
+                                    id: job.id().to_string(),
+                                };
+                                (progress_scope, job.get_build_mut())
+                            }),
+                    );
+
+                    match self.build_and_push_images(builds_to_push, &option) {
                         Ok(apps) => apps,
                         Err(engine_err) => {
                             self.logger.log(EngineEvent::Error(
@@ -7,6 +7,7 @@ use qovery_engine::cloud_provider::aws::regions::AwsRegion;
 use qovery_engine::cloud_provider::aws::AWS;
 use qovery_engine::cloud_provider::kubernetes::Kind as KubernetesKind;
 use qovery_engine::cloud_provider::models::NodeGroups;
+use qovery_engine::cloud_provider::pod_security::PodSecurityPolicy;
 use qovery_engine::cloud_provider::qovery::EngineLocation::ClientSide;
 use qovery_engine::cloud_provider::{CloudProvider, TerraformStateCredentials};
 use qovery_engine::container_registry::ecr::ECR;
@@ -246,6 +247,9 @@ impl Cluster<AWS, Options> for AWS {
             qovery_nats_user: secrets.QOVERY_NATS_USERNAME.unwrap(),
             qovery_nats_password: secrets.QOVERY_NATS_PASSWORD.unwrap(),
             tls_email_report: secrets.LETS_ENCRYPT_EMAIL_REPORT.unwrap(),
+            acme: None,
+            pod_security_policy: PodSecurityPolicy::default(),
+            config_file_store: None,
             qovery_grpc_url: secrets.QOVERY_GRPC_URL.unwrap(),
             qovery_cluster_secret_token: secrets.QOVERY_CLUSTER_SECRET_TOKEN.unwrap(),
         }
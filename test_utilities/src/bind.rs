@@ -0,0 +1,45 @@
+use crate::utilities::{generate_id, FuncTestsSecrets};
+use qovery_engine::dns_provider::rfc2136::{Rfc2136, TsigAlgorithm};
+use qovery_engine::io_models::{Context, Domain};
+use std::net::Ipv4Addr;
+
+// default BIND server settings, see `tests/bind/config`
+pub const BIND_DEFAULT_NAMESERVER: &str = "127.0.0.1";
+pub const BIND_DEFAULT_PORT: &str = "5353";
+pub const BIND_DEFAULT_ZONE: &str = "qovery.test";
+pub const BIND_DEFAULT_TSIG_KEY_NAME: &str = "qovery-engine";
+pub const BIND_DEFAULT_TSIG_SECRET: &str = "VX7v1nIUk5ySTgS2oDgmUnqPIUt4NSYMO+gpyVJE1Ms=";
+
+pub fn bind_dns_provider(context: &Context) -> Rfc2136 {
+    let secrets = FuncTestsSecrets::new();
+    let zone = secrets
+        .RFC2136_ZONE
+        .unwrap_or_else(|| BIND_DEFAULT_ZONE.to_string());
+
+    Rfc2136::new(
+        context.clone(),
+        generate_id().as_str(),
+        "test-bind",
+        Domain::new(zone.to_string()),
+        secrets
+            .RFC2136_NAMESERVER
+            .unwrap_or_else(|| BIND_DEFAULT_NAMESERVER.to_string())
+            .parse::<Ipv4Addr>()
+            .expect("RFC2136_NAMESERVER should be an IPv4 address"),
+        secrets
+            .RFC2136_PORT
+            .unwrap_or_else(|| BIND_DEFAULT_PORT.to_string())
+            .parse::<u16>()
+            .expect("RFC2136_PORT should be a port number"),
+        zone.as_str(),
+        secrets
+            .RFC2136_TSIG_KEY_NAME
+            .unwrap_or_else(|| BIND_DEFAULT_TSIG_KEY_NAME.to_string())
+            .as_str(),
+        secrets
+            .RFC2136_TSIG_SECRET
+            .unwrap_or_else(|| BIND_DEFAULT_TSIG_SECRET.to_string())
+            .as_str(),
+        TsigAlgorithm::HmacSha256,
+    )
+}
@@ -8,7 +8,7 @@ use qovery_engine::cloud_provider::utilities::sanitize_name;
 use qovery_engine::dns_provider::DnsProvider;
 use qovery_engine::io_models::{
     Action, Application, CloneForTest, Context, Database, DatabaseKind, DatabaseMode, EnvironmentRequest,
-    GitCredentials, Port, Protocol, Route, Router, Storage, StorageType,
+    GitCredentials, Port, Protocol, Route, RouteBackendProtocol, Router, Storage, StorageType,
 };
 
 use crate::aws::{AWS_KUBERNETES_VERSION, AWS_TEST_REGION};
@@ -282,6 +282,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                     size_in_gib: 10,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }],
                 environment_vars: btreemap! {
                      "PG_DBNAME".to_string() => base64::encode(database_name.clone()),
@@ -298,6 +299,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }],
                 total_cpus: "100m".to_string(),
@@ -306,6 +308,9 @@ pub fn environment_3_apps_3_routers_3_databases(
                 max_instances: 2,
                 cpu_burst: "100m".to_string(),
                 advanced_settings: Default::default(),
+                lifecycle_hooks: Default::default(),
+                mounted_files: vec![],
+                scheduling: Default::default(),
             },
             Application {
                 long_id: Uuid::new_v4(),
@@ -329,6 +334,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                     size_in_gib: 10,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }],
                 environment_vars: btreemap! {
                      "PG_DBNAME".to_string() => base64::encode(database_name_2.clone()),
@@ -345,6 +351,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }],
                 total_cpus: "100m".to_string(),
@@ -353,6 +360,9 @@ pub fn environment_3_apps_3_routers_3_databases(
                 max_instances: 2,
                 cpu_burst: "100m".to_string(),
                 advanced_settings: Default::default(),
+                lifecycle_hooks: Default::default(),
+                mounted_files: vec![],
+                scheduling: Default::default(),
             },
             Application {
                 long_id: Uuid::new_v4(),
@@ -376,6 +386,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                     size_in_gib: 10,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }],
                 environment_vars: btreemap! {
                     "IS_DOCUMENTDB".to_string() => base64::encode("false"),
@@ -394,6 +405,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }],
                 total_cpus: "100m".to_string(),
@@ -402,6 +414,9 @@ pub fn environment_3_apps_3_routers_3_databases(
                 max_instances: 2,
                 cpu_burst: "100m".to_string(),
                 advanced_settings: Default::default(),
+                lifecycle_hooks: Default::default(),
+                mounted_files: vec![],
+                scheduling: Default::default(),
             },
         ],
         routers: vec![
@@ -415,8 +430,12 @@ pub fn environment_3_apps_3_routers_3_databases(
                 routes: vec![Route {
                     path: "/app1".to_string(),
                     application_name: app_name_1,
+                    rewrite: None,
+                    backend_protocol: RouteBackendProtocol::HTTP,
+                    weighted_applications: vec![],
                 }],
                 sticky_sessions_enabled: false,
+                advanced_settings: Default::default(),
             },
             Router {
                 long_id: Uuid::new_v4(),
@@ -428,8 +447,12 @@ pub fn environment_3_apps_3_routers_3_databases(
                 routes: vec![Route {
                     path: "/app2".to_string(),
                     application_name: app_name_2,
+                    rewrite: None,
+                    backend_protocol: RouteBackendProtocol::HTTP,
+                    weighted_applications: vec![],
                 }],
                 sticky_sessions_enabled: false,
+                advanced_settings: Default::default(),
             },
             Router {
                 long_id: Uuid::new_v4(),
@@ -441,8 +464,12 @@ pub fn environment_3_apps_3_routers_3_databases(
                 routes: vec![Route {
                     path: "/app3".to_string(),
                     application_name: app_name_3,
+                    rewrite: None,
+                    backend_protocol: RouteBackendProtocol::HTTP,
+                    weighted_applications: vec![],
                 }],
                 sticky_sessions_enabled: false,
+                advanced_settings: Default::default(),
             },
         ],
         databases: vec![
@@ -467,6 +494,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                 activate_backups: false,
                 publicly_accessible: false,
                 mode: CONTAINER,
+                scheduling: Default::default(),
             },
             Database {
                 kind: DatabaseKind::Postgresql,
@@ -489,6 +517,7 @@ pub fn environment_3_apps_3_routers_3_databases(
                 activate_backups: false,
                 publicly_accessible: false,
                 mode: CONTAINER,
+                scheduling: Default::default(),
             },
             Database {
                 kind: DatabaseKind::Mongodb,
@@ -511,9 +540,12 @@ pub fn environment_3_apps_3_routers_3_databases(
                 activate_backups: false,
                 publicly_accessible: false,
                 mode: CONTAINER,
+                scheduling: Default::default(),
             },
         ],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -554,6 +586,7 @@ pub fn working_minimal_environment(context: &Context, test_domain: &str) -> Envi
                 public_port: Some(443),
                 name: None,
                 publicly_accessible: true,
+                expose_with_load_balancer: false,
                 protocol: Protocol::HTTP,
             }],
             total_cpus: "100m".to_string(),
@@ -562,6 +595,9 @@ pub fn working_minimal_environment(context: &Context, test_domain: &str) -> Envi
             max_instances: 2,
             cpu_burst: "100m".to_string(),
             advanced_settings: Default::default(),
+            lifecycle_hooks: Default::default(),
+            mounted_files: vec![],
+            scheduling: Default::default(),
         }],
         routers: vec![Router {
             long_id: Uuid::new_v4(),
@@ -573,11 +609,17 @@ pub fn working_minimal_environment(context: &Context, test_domain: &str) -> Envi
             routes: vec![Route {
                 path: "/".to_string(),
                 application_name: format!("{}-{}", "simple-app", &suffix),
+                rewrite: None,
+                backend_protocol: RouteBackendProtocol::HTTP,
+                weighted_applications: vec![],
             }],
             sticky_sessions_enabled: false,
+            advanced_settings: Default::default(),
         }],
         databases: vec![],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -616,10 +658,15 @@ pub fn database_test_environment(context: &Context) -> EnvironmentRequest {
             max_instances: 1,
             cpu_burst: "100m".to_string(),
             advanced_settings: Default::default(),
+            lifecycle_hooks: Default::default(),
+            mounted_files: vec![],
+            scheduling: Default::default(),
         }],
         routers: vec![],
         databases: vec![],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -658,10 +705,15 @@ pub fn database_test_environment_on_upgrade(context: &Context) -> EnvironmentReq
             max_instances: 1,
             cpu_burst: "100m".to_string(),
             advanced_settings: Default::default(),
+            lifecycle_hooks: Default::default(),
+            mounted_files: vec![],
+            scheduling: Default::default(),
         }],
         routers: vec![],
         databases: vec![],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -724,6 +776,7 @@ pub fn environnement_2_app_2_routers_1_psql(
             activate_backups: false,
             publicly_accessible: false,
             mode: CONTAINER,
+            scheduling: Default::default(),
         }],
         applications: vec![
             Application {
@@ -748,6 +801,7 @@ pub fn environnement_2_app_2_routers_1_psql(
                     size_in_gib: 10,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }],
                 environment_vars: btreemap! {
                      "PG_DBNAME".to_string() => base64::encode(database_name.clone()),
@@ -764,6 +818,7 @@ pub fn environnement_2_app_2_routers_1_psql(
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }],
                 total_cpus: "100m".to_string(),
@@ -772,6 +827,9 @@ pub fn environnement_2_app_2_routers_1_psql(
                 max_instances: 2,
                 cpu_burst: "100m".to_string(),
                 advanced_settings: Default::default(),
+                lifecycle_hooks: Default::default(),
+                mounted_files: vec![],
+                scheduling: Default::default(),
             },
             Application {
                 long_id: Uuid::new_v4(),
@@ -795,6 +853,7 @@ pub fn environnement_2_app_2_routers_1_psql(
                     size_in_gib: 10,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }],
                 environment_vars: btreemap! {
                      "PG_DBNAME".to_string() => base64::encode(database_name),
@@ -811,6 +870,7 @@ pub fn environnement_2_app_2_routers_1_psql(
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }],
                 total_cpus: "100m".to_string(),
@@ -819,6 +879,9 @@ pub fn environnement_2_app_2_routers_1_psql(
                 max_instances: 2,
                 cpu_burst: "100m".to_string(),
                 advanced_settings: Default::default(),
+                lifecycle_hooks: Default::default(),
+                mounted_files: vec![],
+                scheduling: Default::default(),
             },
         ],
         routers: vec![
@@ -832,8 +895,12 @@ pub fn environnement_2_app_2_routers_1_psql(
                 routes: vec![Route {
                     path: "/".to_string(),
                     application_name: application_name1,
+                    rewrite: None,
+                    backend_protocol: RouteBackendProtocol::HTTP,
+                    weighted_applications: vec![],
                 }],
                 sticky_sessions_enabled: false,
+                advanced_settings: Default::default(),
             },
             Router {
                 long_id: Uuid::new_v4(),
@@ -845,11 +912,17 @@ pub fn environnement_2_app_2_routers_1_psql(
                 routes: vec![Route {
                     path: "/coco".to_string(),
                     application_name: application_name2,
+                    rewrite: None,
+                    backend_protocol: RouteBackendProtocol::HTTP,
+                    weighted_applications: vec![],
                 }],
                 sticky_sessions_enabled: false,
+                advanced_settings: Default::default(),
             },
         ],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -908,6 +981,7 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
                 public_port: Some(443),
                 name: None,
                 publicly_accessible: true,
+                expose_with_load_balancer: false,
                 protocol: Protocol::HTTP,
             }],
             total_cpus: "100m".to_string(),
@@ -916,6 +990,9 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
             max_instances: 2,
             cpu_burst: "100m".to_string(),
             advanced_settings: Default::default(),
+            lifecycle_hooks: Default::default(),
+            mounted_files: vec![],
+            scheduling: Default::default(),
         }],
         routers: vec![Router {
             long_id: Uuid::new_v4(),
@@ -927,11 +1004,17 @@ pub fn echo_app_environment(context: &Context, test_domain: &str) -> Environment
             routes: vec![Route {
                 path: "/".to_string(),
                 application_name: format!("{}-{}", "echo-app", &suffix),
+                rewrite: None,
+                backend_protocol: RouteBackendProtocol::HTTP,
+                weighted_applications: vec![],
             }],
             sticky_sessions_enabled: false,
+            advanced_settings: Default::default(),
         }],
         databases: vec![],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -969,6 +1052,7 @@ pub fn environment_only_http_server(context: &Context) -> EnvironmentRequest {
                 public_port: Some(443),
                 name: None,
                 publicly_accessible: true,
+                expose_with_load_balancer: false,
                 protocol: Protocol::HTTP,
             }],
             total_cpus: "100m".to_string(),
@@ -977,10 +1061,15 @@ pub fn environment_only_http_server(context: &Context) -> EnvironmentRequest {
             max_instances: 2,
             cpu_burst: "100m".to_string(),
             advanced_settings: Default::default(),
+            lifecycle_hooks: Default::default(),
+            mounted_files: vec![],
+            scheduling: Default::default(),
         }],
         routers: vec![],
         databases: vec![],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -1019,6 +1108,7 @@ pub fn environment_only_http_server_router(context: &Context, test_domain: &str)
                 public_port: Some(443),
                 name: None,
                 publicly_accessible: true,
+                expose_with_load_balancer: false,
                 protocol: Protocol::HTTP,
             }],
             total_cpus: "100m".to_string(),
@@ -1027,6 +1117,9 @@ pub fn environment_only_http_server_router(context: &Context, test_domain: &str)
             max_instances: 2,
             cpu_burst: "100m".to_string(),
             advanced_settings: Default::default(),
+            lifecycle_hooks: Default::default(),
+            mounted_files: vec![],
+            scheduling: Default::default(),
         }],
         routers: vec![Router {
             long_id: Uuid::new_v4(),
@@ -1038,11 +1131,17 @@ pub fn environment_only_http_server_router(context: &Context, test_domain: &str)
             routes: vec![Route {
                 path: "/".to_string(),
                 application_name: format!("{}-{}", "mini-http", &suffix),
+                rewrite: None,
+                backend_protocol: RouteBackendProtocol::HTTP,
+                weighted_applications: vec![],
             }],
             sticky_sessions_enabled: false,
+            advanced_settings: Default::default(),
         }],
         databases: vec![],
+        jobs: vec![],
         clone_from_environment_id: None,
+        network_policy: Default::default(),
     }
 }
 
@@ -1150,6 +1249,7 @@ pub fn test_db(
         activate_backups: false,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
+        scheduling: Default::default(),
     };
 
     environment.databases = vec![db];
@@ -1170,6 +1270,7 @@ pub fn test_db(
                 public_port: Some(1234),
                 name: None,
                 publicly_accessible: true,
+                expose_with_load_balancer: false,
                 protocol: Protocol::HTTP,
             }];
             app.dockerfile_path = Some(format!("Dockerfile-{}", version));
@@ -1840,6 +1941,7 @@ pub fn test_db_on_upgrade(
         activate_backups: false,
         publicly_accessible: is_public,
         mode: database_mode.clone(),
+        scheduling: Default::default(),
     };
 
     environment.databases = vec![db];
@@ -1860,6 +1962,7 @@ pub fn test_db_on_upgrade(
                 public_port: Some(1234),
                 name: None,
                 publicly_accessible: true,
+                expose_with_load_balancer: false,
                 protocol: Protocol::HTTP,
             }];
             app.dockerfile_path = Some(format!("Dockerfile-{}", version));
@@ -1,6 +1,7 @@
 use const_format::formatcp;
 use qovery_engine::cloud_provider::aws::kubernetes::VpcQoveryNetworkMode;
 use qovery_engine::cloud_provider::digitalocean::kubernetes::DoksOptions;
+use qovery_engine::cloud_provider::pod_security::PodSecurityPolicy;
 use qovery_engine::cloud_provider::digitalocean::network::vpc::VpcInitKind;
 use qovery_engine::cloud_provider::digitalocean::DO;
 use qovery_engine::cloud_provider::kubernetes::Kind as KubernetesKind;
@@ -160,6 +161,9 @@ impl Cluster<DO, DoksOptions> for DO {
             qovery_nats_password: secrets.QOVERY_NATS_PASSWORD.unwrap(),
             qovery_ssh_key: secrets.QOVERY_SSH_USER.unwrap(),
             tls_email_report: secrets.LETS_ENCRYPT_EMAIL_REPORT.unwrap(),
+            acme: None,
+            pod_security_policy: PodSecurityPolicy::default(),
+            config_file_store: None,
         }
     }
 }
@@ -5,9 +5,11 @@ extern crate maplit;
 
 pub mod aws;
 pub mod aws_ec2;
+pub mod bind;
 pub mod cloudflare;
 pub mod common;
 pub mod digitalocean;
 pub mod edge_aws_rs;
+pub mod minio;
 pub mod scaleway;
 pub mod utilities;
@@ -0,0 +1,30 @@
+use crate::utilities::{generate_id, FuncTestsSecrets};
+use qovery_engine::io_models::Context;
+use qovery_engine::object_storage::s3_compatible::S3Compatible;
+
+// default MinIO server settings, see `docker run -p 9000:9000 minio/minio server /data`
+pub const MINIO_DEFAULT_ENDPOINT: &str = "http://localhost:9000";
+pub const MINIO_DEFAULT_ACCESS_KEY: &str = "minioadmin";
+pub const MINIO_DEFAULT_SECRET_KEY: &str = "minioadmin";
+pub const MINIO_REGION: &str = "us-east-1";
+
+pub fn minio_object_storage(context: &Context) -> S3Compatible {
+    let secrets = FuncTestsSecrets::new();
+
+    S3Compatible::new(
+        context.clone(),
+        generate_id(),
+        "test-minio".to_string(),
+        secrets
+            .MINIO_ENDPOINT
+            .unwrap_or_else(|| MINIO_DEFAULT_ENDPOINT.to_string()),
+        MINIO_REGION.to_string(),
+        true,
+        secrets
+            .MINIO_ACCESS_KEY
+            .unwrap_or_else(|| MINIO_DEFAULT_ACCESS_KEY.to_string()),
+        secrets
+            .MINIO_SECRET_KEY
+            .unwrap_or_else(|| MINIO_DEFAULT_SECRET_KEY.to_string()),
+    )
+}
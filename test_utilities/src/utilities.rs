@@ -54,10 +54,19 @@ use qovery_engine::logger::{Logger, StdIoLogger};
 use qovery_engine::models::scaleway::ScwZone;
 use qovery_engine::runtime::block_on;
 use qovery_engine::utilities::to_short_id;
+use qovery_engine::object_storage::encryption::ObjectStorageEncryption;
 use time::Instant;
 use url::Url;
 
 pub fn context(organization_id: &str, cluster_id: &str) -> Context {
+    context_with_object_storage_encryption(organization_id, cluster_id, None)
+}
+
+pub fn context_with_object_storage_encryption(
+    organization_id: &str,
+    cluster_id: &str,
+    object_storage_encryption: Option<ObjectStorageEncryption>,
+) -> Context {
     let organization_id = organization_id.to_string();
     let cluster_id = cluster_id.to_string();
     let execution_id = execution_id();
@@ -81,6 +90,7 @@ pub fn context(organization_id: &str, cluster_id: &str) -> Context {
         },
         forced_upgrade: Option::from(env::var_os("forced_upgrade").is_some()),
         disable_pleco: Some(true),
+        object_storage_encryption,
     };
 
     let enabled_features = vec![Features::LogsHistory];
@@ -129,6 +139,9 @@ pub struct FuncTestsSecrets {
     pub HTTP_LISTEN_ON: Option<String>,
     pub LETS_ENCRYPT_EMAIL_REPORT: Option<String>,
     pub LIB_ROOT_DIR: Option<String>,
+    pub MINIO_ACCESS_KEY: Option<String>,
+    pub MINIO_ENDPOINT: Option<String>,
+    pub MINIO_SECRET_KEY: Option<String>,
     pub QOVERY_AGENT_CONTROLLER_TOKEN: Option<String>,
     pub QOVERY_API_URL: Option<String>,
     pub QOVERY_ENGINE_CONTROLLER_TOKEN: Option<String>,
@@ -136,6 +149,11 @@ pub struct FuncTestsSecrets {
     pub QOVERY_NATS_USERNAME: Option<String>,
     pub QOVERY_NATS_PASSWORD: Option<String>,
     pub QOVERY_SSH_USER: Option<String>,
+    pub RFC2136_NAMESERVER: Option<String>,
+    pub RFC2136_PORT: Option<String>,
+    pub RFC2136_TSIG_KEY_NAME: Option<String>,
+    pub RFC2136_TSIG_SECRET: Option<String>,
+    pub RFC2136_ZONE: Option<String>,
     pub RUST_LOG: Option<String>,
     pub SCALEWAY_DEFAULT_PROJECT_ID: Option<String>,
     pub SCALEWAY_ACCESS_KEY: Option<String>,
@@ -219,6 +237,9 @@ impl FuncTestsSecrets {
             HTTP_LISTEN_ON: None,
             LETS_ENCRYPT_EMAIL_REPORT: None,
             LIB_ROOT_DIR: None,
+            MINIO_ACCESS_KEY: None,
+            MINIO_ENDPOINT: None,
+            MINIO_SECRET_KEY: None,
             QOVERY_AGENT_CONTROLLER_TOKEN: None,
             QOVERY_API_URL: None,
             QOVERY_ENGINE_CONTROLLER_TOKEN: None,
@@ -226,6 +247,11 @@ impl FuncTestsSecrets {
             QOVERY_NATS_USERNAME: None,
             QOVERY_NATS_PASSWORD: None,
             QOVERY_SSH_USER: None,
+            RFC2136_NAMESERVER: None,
+            RFC2136_PORT: None,
+            RFC2136_TSIG_KEY_NAME: None,
+            RFC2136_TSIG_SECRET: None,
+            RFC2136_ZONE: None,
             RUST_LOG: None,
             SCALEWAY_ACCESS_KEY: None,
             SCALEWAY_DEFAULT_PROJECT_ID: None,
@@ -318,6 +344,9 @@ impl FuncTestsSecrets {
                 secrets.LETS_ENCRYPT_EMAIL_REPORT,
             ),
             LIB_ROOT_DIR: Self::select_secret("LIB_ROOT_DIR", secrets.LIB_ROOT_DIR),
+            MINIO_ACCESS_KEY: Self::select_secret("MINIO_ACCESS_KEY", secrets.MINIO_ACCESS_KEY),
+            MINIO_ENDPOINT: Self::select_secret("MINIO_ENDPOINT", secrets.MINIO_ENDPOINT),
+            MINIO_SECRET_KEY: Self::select_secret("MINIO_SECRET_KEY", secrets.MINIO_SECRET_KEY),
             QOVERY_AGENT_CONTROLLER_TOKEN: Self::select_secret(
                 "QOVERY_AGENT_CONTROLLER_TOKEN",
                 secrets.QOVERY_AGENT_CONTROLLER_TOKEN,
@@ -331,6 +360,11 @@ impl FuncTestsSecrets {
             QOVERY_NATS_USERNAME: Self::select_secret("QOVERY_NATS_USERNAME", secrets.QOVERY_NATS_USERNAME),
             QOVERY_NATS_PASSWORD: Self::select_secret("QOVERY_NATS_PASSWORD", secrets.QOVERY_NATS_PASSWORD),
             QOVERY_SSH_USER: Self::select_secret("QOVERY_SSH_USER", secrets.QOVERY_SSH_USER),
+            RFC2136_NAMESERVER: Self::select_secret("RFC2136_NAMESERVER", secrets.RFC2136_NAMESERVER),
+            RFC2136_PORT: Self::select_secret("RFC2136_PORT", secrets.RFC2136_PORT),
+            RFC2136_TSIG_KEY_NAME: Self::select_secret("RFC2136_TSIG_KEY_NAME", secrets.RFC2136_TSIG_KEY_NAME),
+            RFC2136_TSIG_SECRET: Self::select_secret("RFC2136_TSIG_SECRET", secrets.RFC2136_TSIG_SECRET),
+            RFC2136_ZONE: Self::select_secret("RFC2136_ZONE", secrets.RFC2136_ZONE),
             RUST_LOG: Self::select_secret("RUST_LOG", secrets.RUST_LOG),
             SCALEWAY_ACCESS_KEY: Self::select_secret("SCALEWAY_ACCESS_KEY", secrets.SCALEWAY_ACCESS_KEY),
             SCALEWAY_DEFAULT_PROJECT_ID: Self::select_secret(
@@ -281,6 +281,7 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
             activate_backups: false,
             publicly_accessible: false,
             mode: CONTAINER,
+            scheduling: Default::default(),
         }];
         environment.applications = environment
             .applications
@@ -295,6 +296,7 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }];
                 app.environment_vars = btreemap! {
@@ -393,6 +393,7 @@ fn build_with_buildpacks_and_deploy_a_working_environment() {
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }];
                 app.commit_id = "f59237d603829636138e2f22a0549e33b5dd6e1f".to_string();
@@ -464,6 +465,7 @@ fn build_worker_with_buildpacks_and_deploy_a_working_environment() {
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }];
                 app.commit_id = "f59237d603829636138e2f22a0549e33b5dd6e1f".to_string();
@@ -592,6 +594,7 @@ fn deploy_a_working_environment_with_storage_on_aws_eks() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -675,6 +678,7 @@ fn redeploy_same_app_with_ebs() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
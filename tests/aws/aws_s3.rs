@@ -1,7 +1,8 @@
 use qovery_engine::cloud_provider::aws::regions::AwsRegion;
 use qovery_engine::object_storage::s3::S3;
-use qovery_engine::object_storage::ObjectStorage;
+use qovery_engine::object_storage::{ObjectStorage, PresignedUrlMethod};
 use std::str::FromStr;
+use std::time::Duration;
 use tempfile::NamedTempFile;
 use test_utilities::utilities::{context, generate_id, FuncTestsSecrets};
 
@@ -239,3 +240,192 @@ fn test_get_file() {
         .delete_bucket(bucket_name.as_str())
         .unwrap_or_else(|_| panic!("error deleting S3 bucket {}", bucket_name));
 }
+
+#[cfg(feature = "test-aws-infra")]
+#[test]
+fn test_list_head_and_delete_objects() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let secrets = FuncTestsSecrets::new();
+    let id = generate_id();
+    let name = format!("test-{}", id);
+    let aws_access_key = secrets.AWS_ACCESS_KEY_ID.expect("AWS_ACCESS_KEY_ID is not set");
+    let aws_secret_key = secrets.AWS_SECRET_ACCESS_KEY.expect("AWS_SECRET_ACCESS_KEY is not set");
+    let aws_region_raw = secrets.AWS_DEFAULT_REGION.expect("AWS_DEFAULT_REGION is not set");
+    let aws_region = AwsRegion::from_str(aws_region_raw.as_str())
+        .unwrap_or_else(|_| panic!("AWS region `{}` seems not to be valid", aws_region_raw));
+
+    let aws_os = S3::new(
+        context.clone(),
+        id,
+        name,
+        aws_access_key,
+        aws_secret_key,
+        aws_region,
+        false,
+        context.resource_expiration_in_seconds(),
+    );
+
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-prefix/test-object-{}", generate_id());
+
+    aws_os
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+    aws_os
+        .put_from_reader(bucket_name.as_str(), object_key.as_str(), &mut "content".as_bytes())
+        .unwrap_or_else(|_| panic!("error while putting object {} into bucket {}", object_key, bucket_name));
+
+    // compute:
+    let listed_objects = aws_os.list_objects(bucket_name.as_str(), Some("test-prefix/"));
+    let not_listed_objects = aws_os.list_objects(bucket_name.as_str(), Some("other-prefix/"));
+    let object_metadata = aws_os.head_object(bucket_name.as_str(), object_key.as_str());
+    let delete_result = aws_os.delete_object(bucket_name.as_str(), object_key.as_str());
+
+    // validate:
+    assert_eq!(
+        vec![object_key.to_string()],
+        listed_objects
+            .expect("error while listing objects")
+            .into_iter()
+            .map(|o| o.key)
+            .collect::<Vec<String>>()
+    );
+    assert!(not_listed_objects.expect("error while listing objects").is_empty());
+    assert_eq!(7, object_metadata.expect("error while getting object metadata").size);
+    assert!(delete_result.is_ok());
+    assert!(aws_os.head_object(bucket_name.as_str(), object_key.as_str()).is_err());
+
+    // clean-up:
+    aws_os
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-aws-infra")]
+#[test]
+fn test_put_from_reader_multipart_upload() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let secrets = FuncTestsSecrets::new();
+    let id = generate_id();
+    let name = format!("test-{}", id);
+    let aws_access_key = secrets.AWS_ACCESS_KEY_ID.expect("AWS_ACCESS_KEY_ID is not set");
+    let aws_secret_key = secrets.AWS_SECRET_ACCESS_KEY.expect("AWS_SECRET_ACCESS_KEY is not set");
+    let aws_region_raw = secrets.AWS_DEFAULT_REGION.expect("AWS_DEFAULT_REGION is not set");
+    let aws_region = AwsRegion::from_str(aws_region_raw.as_str())
+        .unwrap_or_else(|_| panic!("AWS region `{}` seems not to be valid", aws_region_raw));
+
+    let aws_os = S3::new(
+        context.clone(),
+        id,
+        name,
+        aws_access_key,
+        aws_secret_key,
+        aws_region,
+        false,
+        context.resource_expiration_in_seconds(),
+    );
+
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+    // bigger than a single upload part
+    let content = vec![42u8; 20 * 1024 * 1024];
+
+    aws_os
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    let result = aws_os.put_from_reader(bucket_name.as_str(), object_key.as_str(), &mut content.as_slice());
+
+    // validate:
+    assert!(result.is_ok());
+    assert_eq!(
+        content.len() as i64,
+        aws_os
+            .head_object(bucket_name.as_str(), object_key.as_str())
+            .expect("error while getting object metadata")
+            .size
+    );
+
+    // clean-up:
+    aws_os
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-aws-infra")]
+#[test]
+fn test_presigned_url() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let secrets = FuncTestsSecrets::new();
+    let id = generate_id();
+    let name = format!("test-{}", id);
+    let aws_access_key = secrets.AWS_ACCESS_KEY_ID.expect("AWS_ACCESS_KEY_ID is not set");
+    let aws_secret_key = secrets.AWS_SECRET_ACCESS_KEY.expect("AWS_SECRET_ACCESS_KEY is not set");
+    let aws_region_raw = secrets.AWS_DEFAULT_REGION.expect("AWS_DEFAULT_REGION is not set");
+    let aws_region = AwsRegion::from_str(aws_region_raw.as_str())
+        .unwrap_or_else(|_| panic!("AWS region `{}` seems not to be valid", aws_region_raw));
+
+    let aws_os = S3::new(
+        context.clone(),
+        id,
+        name,
+        aws_access_key,
+        aws_secret_key,
+        aws_region,
+        false,
+        context.resource_expiration_in_seconds(),
+    );
+
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+
+    aws_os
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    let put_url = aws_os
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Put,
+            Duration::from_secs(300),
+        )
+        .expect("error while generating presigned PUT url");
+    let get_url = aws_os
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Get,
+            Duration::from_secs(300),
+        )
+        .expect("error while generating presigned GET url");
+
+    // validate:
+    let http_client = reqwest::blocking::Client::new();
+    assert!(http_client
+        .put(put_url.as_str())
+        .body("content")
+        .send()
+        .expect("error while uploading through presigned url")
+        .status()
+        .is_success());
+    assert_eq!(
+        "content",
+        http_client
+            .get(get_url.as_str())
+            .send()
+            .expect("error while downloading through presigned url")
+            .text()
+            .unwrap()
+    );
+
+    // clean-up:
+    aws_os
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
@@ -0,0 +1,55 @@
+use qovery_engine::dns_provider::{DnsProvider, DnsRecord, DnsRecordType};
+use test_utilities::bind::bind_dns_provider;
+use test_utilities::utilities::{context, generate_id};
+
+#[cfg(feature = "test-local-bind")]
+#[test]
+fn test_upsert_list_and_delete_records() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let bind = bind_dns_provider(&context);
+    let record_name = format!("app-{}.{}", generate_id(), bind.domain());
+    let record = DnsRecord::new(record_name.as_str(), DnsRecordType::A, "10.0.0.1", 60);
+
+    // compute & validate:
+    assert!(bind.is_valid().is_ok());
+    assert_eq!(record, bind.upsert_record(&record).expect("error while creating record"));
+
+    // upserting an existing record replaces its content
+    let updated_record = DnsRecord::new(record_name.as_str(), DnsRecordType::A, "10.0.0.2", 60);
+    assert!(bind.upsert_record(&updated_record).is_ok());
+    let records = bind.list_records().expect("error while listing records");
+    assert_eq!(
+        vec![&updated_record],
+        records.iter().filter(|r| r.name == record_name).collect::<Vec<_>>()
+    );
+
+    assert!(bind.delete_record(record_name.as_str(), DnsRecordType::A).is_ok());
+    let records = bind.list_records().expect("error while listing records");
+    assert!(!records.iter().any(|r| r.name == record_name));
+
+    // deleting a missing record is not an error
+    assert!(bind.delete_record(record_name.as_str(), DnsRecordType::A).is_ok());
+}
+
+#[cfg(feature = "test-local-bind")]
+#[test]
+fn test_upsert_txt_record() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let bind = bind_dns_provider(&context);
+    let record_name = format!("_acme-challenge-{}.{}", generate_id(), bind.domain());
+    let record = DnsRecord::new(record_name.as_str(), DnsRecordType::Txt, "challenge token", 60);
+
+    // compute:
+    let upsert_result = bind.upsert_record(&record);
+    let records = bind.list_records().expect("error while listing records");
+
+    // validate:
+    assert!(upsert_result.is_ok());
+    assert!(records.contains(&record));
+
+    // clean-up:
+    bind.delete_record(record_name.as_str(), DnsRecordType::Txt)
+        .unwrap_or_else(|_| panic!("error deleting record {}", record_name));
+}
@@ -0,0 +1 @@
+mod bind_rfc2136;
@@ -293,6 +293,7 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
             total_ram_in_mib: 512,
             disk_size_in_gib: 10,
             mode: database_mode.clone(),
+            scheduling: Default::default(),
             database_instance_type: if database_mode == MANAGED {
                 DO_MANAGED_DATABASE_INSTANCE_TYPE
             } else {
@@ -323,6 +324,7 @@ fn postgresql_deploy_a_working_environment_and_redeploy() {
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }];
                 app.environment_vars = btreemap! {
@@ -326,6 +326,7 @@ fn digitalocean_doks_build_with_buildpacks_and_deploy_a_working_environment() {
                     public_port: Some(443),
                     name: None,
                     publicly_accessible: true,
+                    expose_with_load_balancer: false,
                     protocol: Protocol::HTTP,
                 }];
                 app.commit_id = "f59237d603829636138e2f22a0549e33b5dd6e1f".to_string();
@@ -459,6 +460,7 @@ fn digitalocean_doks_deploy_a_working_environment_with_storage() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -543,6 +545,7 @@ fn digitalocean_doks_redeploy_same_app() {
                     size_in_gib: storage_size,
                     mount_point: "/mnt/photos".to_string(),
                     snapshot_retention_in_days: 0,
+                    restore_from_snapshot: None,
                 }];
                 app
             })
@@ -1,6 +1,7 @@
 use qovery_engine::models::digital_ocean::DoRegion;
 use qovery_engine::object_storage::spaces::{BucketDeleteStrategy, Spaces};
-use qovery_engine::object_storage::ObjectStorage;
+use qovery_engine::object_storage::{ObjectStorage, PresignedUrlMethod};
+use std::time::Duration;
 use tempfile::NamedTempFile;
 use test_utilities::utilities::{context, generate_id, FuncTestsSecrets};
 
@@ -230,3 +231,168 @@ fn test_get_file() {
         .delete_bucket(bucket_name.as_str())
         .unwrap_or_else(|_| panic!("error deleting object storage bucket {}", bucket_name));
 }
+
+#[cfg(feature = "test-do-infra")]
+#[test]
+fn test_list_head_and_delete_objects() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let secrets = FuncTestsSecrets::new();
+
+    let spaces = Spaces::new(
+        context,
+        "test-fake".to_string(),
+        "test-fake".to_string(),
+        secrets.DIGITAL_OCEAN_SPACES_ACCESS_ID.unwrap(),
+        secrets.DIGITAL_OCEAN_SPACES_SECRET_ID.unwrap(),
+        TEST_REGION,
+        BucketDeleteStrategy::HardDelete,
+    );
+
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-prefix/test-object-{}", generate_id());
+
+    spaces
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+    spaces
+        .put_from_reader(bucket_name.as_str(), object_key.as_str(), &mut "content".as_bytes())
+        .unwrap_or_else(|_| panic!("error while putting object {} into bucket {}", object_key, bucket_name));
+
+    // compute:
+    let listed_objects = spaces.list_objects(bucket_name.as_str(), Some("test-prefix/"));
+    let not_listed_objects = spaces.list_objects(bucket_name.as_str(), Some("other-prefix/"));
+    let object_metadata = spaces.head_object(bucket_name.as_str(), object_key.as_str());
+    let delete_result = spaces.delete_object(bucket_name.as_str(), object_key.as_str());
+
+    // validate:
+    assert_eq!(
+        vec![object_key.to_string()],
+        listed_objects
+            .expect("error while listing objects")
+            .into_iter()
+            .map(|o| o.key)
+            .collect::<Vec<String>>()
+    );
+    assert!(not_listed_objects.expect("error while listing objects").is_empty());
+    assert_eq!(7, object_metadata.expect("error while getting object metadata").size);
+    assert!(delete_result.is_ok());
+    assert!(spaces.head_object(bucket_name.as_str(), object_key.as_str()).is_err());
+
+    // clean-up:
+    spaces
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-do-infra")]
+#[test]
+fn test_put_from_reader_multipart_upload() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let secrets = FuncTestsSecrets::new();
+
+    let spaces = Spaces::new(
+        context,
+        "test-fake".to_string(),
+        "test-fake".to_string(),
+        secrets.DIGITAL_OCEAN_SPACES_ACCESS_ID.unwrap(),
+        secrets.DIGITAL_OCEAN_SPACES_SECRET_ID.unwrap(),
+        TEST_REGION,
+        BucketDeleteStrategy::HardDelete,
+    );
+
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+    // bigger than a single upload part
+    let content = vec![42u8; 20 * 1024 * 1024];
+
+    spaces
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    let result = spaces.put_from_reader(bucket_name.as_str(), object_key.as_str(), &mut content.as_slice());
+
+    // validate:
+    assert!(result.is_ok());
+    assert_eq!(
+        content.len() as i64,
+        spaces
+            .head_object(bucket_name.as_str(), object_key.as_str())
+            .expect("error while getting object metadata")
+            .size
+    );
+
+    // clean-up:
+    spaces
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-do-infra")]
+#[test]
+fn test_presigned_url() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let secrets = FuncTestsSecrets::new();
+
+    let spaces = Spaces::new(
+        context,
+        "test-fake".to_string(),
+        "test-fake".to_string(),
+        secrets.DIGITAL_OCEAN_SPACES_ACCESS_ID.unwrap(),
+        secrets.DIGITAL_OCEAN_SPACES_SECRET_ID.unwrap(),
+        TEST_REGION,
+        BucketDeleteStrategy::HardDelete,
+    );
+
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+
+    spaces
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    let put_url = spaces
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Put,
+            Duration::from_secs(300),
+        )
+        .expect("error while generating presigned PUT url");
+    let get_url = spaces
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Get,
+            Duration::from_secs(300),
+        )
+        .expect("error while generating presigned GET url");
+
+    // validate:
+    let http_client = reqwest::blocking::Client::new();
+    assert!(http_client
+        .put(put_url.as_str())
+        .body("content")
+        .send()
+        .expect("error while uploading through presigned url")
+        .status()
+        .is_success());
+    assert_eq!(
+        "content",
+        http_client
+            .get(get_url.as_str())
+            .send()
+            .expect("error while downloading through presigned url")
+            .text()
+            .unwrap()
+    );
+
+    // clean-up:
+    spaces
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
@@ -2,6 +2,8 @@
 extern crate maplit;
 
 mod aws;
+mod bind;
 mod digitalocean;
 mod helm;
+mod minio;
 mod scaleway;
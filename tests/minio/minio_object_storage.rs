@@ -0,0 +1,267 @@
+use qovery_engine::object_storage::encryption::{EncryptionKey, ObjectStorageEncryption};
+use qovery_engine::object_storage::{ObjectStorage, PresignedUrlMethod};
+use std::io::Read;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use test_utilities::minio::minio_object_storage;
+use test_utilities::utilities::{context, context_with_object_storage_encryption, generate_id};
+
+#[cfg(feature = "test-local-minio")]
+#[test]
+fn test_create_and_delete_bucket() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let minio = minio_object_storage(&context);
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+
+    // compute & validate:
+    assert!(minio.is_valid().is_ok());
+    assert!(minio.create_bucket(bucket_name.as_str()).is_ok());
+    assert!(minio.bucket_exists(bucket_name.as_str()));
+
+    // creating an existing bucket is not an error
+    assert!(minio.create_bucket(bucket_name.as_str()).is_ok());
+
+    assert!(minio.delete_bucket(bucket_name.as_str()).is_ok());
+    assert!(!minio.bucket_exists(bucket_name.as_str()));
+}
+
+#[cfg(feature = "test-local-minio")]
+#[test]
+fn test_put_and_get_file() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let minio = minio_object_storage(&context);
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}.yaml", generate_id());
+
+    minio
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    let temp_file = NamedTempFile::new().expect("error while creating tempfile");
+    std::fs::write(temp_file.path(), "apiVersion: v1").expect("error while writing tempfile");
+    let tempfile_path = temp_file.into_temp_path();
+    let tempfile_path = tempfile_path.to_str().unwrap();
+
+    // compute:
+    let put_result = minio.put(bucket_name.as_str(), object_key.as_str(), tempfile_path);
+    let get_result = minio.get(bucket_name.as_str(), object_key.as_str(), false);
+
+    // validate:
+    assert!(put_result.is_ok());
+    let (_, mut file) = get_result.expect("error while getting object");
+    let mut content = String::new();
+    file.read_to_string(&mut content).unwrap();
+    assert_eq!("apiVersion: v1", content);
+
+    // clean-up:
+    minio
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-local-minio")]
+#[test]
+fn test_list_head_and_delete_objects() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let minio = minio_object_storage(&context);
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+
+    minio
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+    for object_key in &["logs/a.log", "logs/b.log", "kubeconfig.yaml"] {
+        minio
+            .put_from_reader(bucket_name.as_str(), object_key, &mut "content".as_bytes())
+            .unwrap_or_else(|_| panic!("error while putting object {} into bucket {}", object_key, bucket_name));
+    }
+
+    // compute:
+    let listed_objects = minio
+        .list_objects(bucket_name.as_str(), Some("logs/"))
+        .expect("error while listing objects");
+    let object_metadata = minio
+        .head_object(bucket_name.as_str(), "kubeconfig.yaml")
+        .expect("error while getting object metadata");
+    let delete_result = minio.delete_object(bucket_name.as_str(), "logs/a.log");
+
+    // validate:
+    assert_eq!(
+        vec!["logs/a.log".to_string(), "logs/b.log".to_string()],
+        listed_objects.into_iter().map(|o| o.key).collect::<Vec<String>>()
+    );
+    assert_eq!(7, object_metadata.size);
+    assert!(object_metadata.etag.is_some());
+    assert!(object_metadata.last_modified.is_some());
+    assert!(delete_result.is_ok());
+    assert_eq!(
+        2,
+        minio
+            .list_objects(bucket_name.as_str(), None)
+            .expect("error while listing objects")
+            .len()
+    );
+
+    // clean-up:
+    minio
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-local-minio")]
+#[test]
+fn test_put_from_reader_multipart_upload() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let minio = minio_object_storage(&context);
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+    // bigger than a single upload part
+    let content = vec![42u8; 20 * 1024 * 1024];
+
+    minio
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    let result = minio.put_from_reader(bucket_name.as_str(), object_key.as_str(), &mut content.as_slice());
+
+    // validate:
+    assert!(result.is_ok());
+    assert_eq!(
+        content.len() as i64,
+        minio
+            .head_object(bucket_name.as_str(), object_key.as_str())
+            .expect("error while getting object metadata")
+            .size
+    );
+
+    // clean-up:
+    minio
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-local-minio")]
+#[test]
+fn test_presigned_url() {
+    // setup:
+    let context = context("fake_orga_id", "fake_cluster_id");
+    let minio = minio_object_storage(&context);
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+
+    minio
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    let put_url = minio
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Put,
+            Duration::from_secs(300),
+        )
+        .expect("error while generating presigned PUT url");
+    let get_url = minio
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Get,
+            Duration::from_secs(300),
+        )
+        .expect("error while generating presigned GET url");
+
+    // validate:
+    let http_client = reqwest::blocking::Client::new();
+    assert!(http_client
+        .put(put_url.as_str())
+        .body("content")
+        .send()
+        .expect("error while uploading through presigned url")
+        .status()
+        .is_success());
+    assert_eq!(
+        "content",
+        http_client
+            .get(get_url.as_str())
+            .send()
+            .expect("error while downloading through presigned url")
+            .text()
+            .unwrap()
+    );
+
+    // clean-up:
+    minio
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
+
+#[cfg(feature = "test-local-minio")]
+#[test]
+fn test_encrypted_object() {
+    // setup:
+    let context = context_with_object_storage_encryption(
+        "fake_orga_id",
+        "fake_cluster_id",
+        Some(ObjectStorageEncryption {
+            current_key: EncryptionKey {
+                id: "key-1".to_string(),
+                key: base64::encode(vec![1u8; 32]),
+            },
+            previous_keys: vec![],
+        }),
+    );
+    let minio = minio_object_storage(&context);
+    let bucket_name = format!("qovery-test-bucket-{}", generate_id());
+    let object_key = format!("test-object-{}", generate_id());
+    let content = "apiVersion: v1";
+
+    minio
+        .create_bucket(bucket_name.as_str())
+        .expect("error while creating object-storage bucket");
+
+    // compute:
+    minio
+        .put_from_reader(bucket_name.as_str(), object_key.as_str(), &mut content.as_bytes())
+        .expect("error while uploading encrypted object");
+    let (_, mut file) = minio
+        .get(bucket_name.as_str(), object_key.as_str(), false)
+        .expect("error while getting encrypted object");
+    let presigned_get_url = minio.presigned_url(
+        bucket_name.as_str(),
+        object_key.as_str(),
+        PresignedUrlMethod::Get,
+        Duration::from_secs(300),
+    );
+
+    // validate:
+    let mut downloaded_content = String::new();
+    file.read_to_string(&mut downloaded_content).unwrap();
+    assert_eq!(content, downloaded_content);
+    assert_eq!(
+        content.len() as i64,
+        minio
+            .head_object(bucket_name.as_str(), object_key.as_str())
+            .expect("error while getting object metadata")
+            .size
+    );
+    // presigned urls would bypass the encryption
+    assert!(presigned_get_url.is_err());
+    assert!(minio
+        .presigned_url(
+            bucket_name.as_str(),
+            object_key.as_str(),
+            PresignedUrlMethod::Put,
+            Duration::from_secs(300),
+        )
+        .is_err());
+
+    // clean-up:
+    minio
+        .delete_bucket(bucket_name.as_str())
+        .unwrap_or_else(|_| panic!("error deleting bucket {}", bucket_name));
+}
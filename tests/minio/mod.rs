@@ -0,0 +1 @@
+mod minio_object_storage;